@@ -133,6 +133,14 @@ yargs(hideBin(process.argv))
                 console.log("Transferring fees")
                 ix = bridge.transfer_fees_ix(bridge_id.toString(), from.publicKey.toString(), vaa);
                 break
+            case 5:
+                console.log("Setting emitter fee")
+                ix = bridge.set_emitter_fee_ix(bridge_id.toString(), from.publicKey.toString(), vaa);
+                break
+            case 6:
+                console.log("Setting guardian set expiration")
+                ix = bridge.set_guardian_set_expiration_ix(bridge_id.toString(), from.publicKey.toString(), vaa);
+                break
             default:
                 throw new Error("unknown governance action")
         }
@@ -97,3 +97,24 @@ impl GovernanceAction for GovernanceTransferFees {
         ))
     }
 }
+
+pub struct GovernanceGuardianSetExpiration {
+    pub guardian_set_index: u32,
+    pub expiration_time:    u32,
+}
+
+impl GovernanceAction for GovernanceGuardianSetExpiration {
+    const MODULE: &'static [u8] = b"Core";
+    const ACTION: u8 = 6;
+    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+        let (i, guardian_set_index) = u32(Endianness::Big)(input)?;
+        let (i, expiration_time) = u32(Endianness::Big)(i)?;
+        Ok((
+            i,
+            Self {
+                guardian_set_index,
+                expiration_time,
+            },
+        ))
+    }
+}
@@ -0,0 +1,14 @@
+#![feature(adt_const_params)]
+#![allow(warnings)]
+
+//! Library side of the bridge client, shared by the CLI and other Rust tooling that needs to
+//! drive the bridge program over RPC.
+
+pub mod post_vaa;
+
+pub use post_vaa::{
+    post_vaa,
+    print_progress,
+    PostVAAOptions,
+    PostVAAProgress,
+};
@@ -8,13 +8,39 @@ use std::{
     process::exit,
 };
 
-use borsh::BorshDeserialize;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
 use bridge::{
     accounts::{
         Bridge,
         BridgeData,
+        EmitterFee,
+        EmitterFeeData,
+        EmitterFeeDerivationData,
         FeeCollector,
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        PostedMessageData,
+        Sequence,
+        SequenceDerivationData,
+        SequenceTracker,
+    },
+    instructions,
+    types::{
+        GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetEmitterFee,
+        GovernancePayloadSetGuardianSetExpiration,
+        GovernancePayloadSetMessageFee,
+        GovernancePayloadTransferFees,
+        GovernancePayloadUpgrade,
     },
+    vaa::VAA,
+    DeserializePayload,
+    PostMessageAccounts,
+    PostMessageData,
 };
 use clap::{
     crate_description,
@@ -24,9 +50,15 @@ use clap::{
     App,
     AppSettings,
     Arg,
+    ArgMatches,
     SubCommand,
 };
+use client::{
+    print_progress,
+    PostVAAOptions,
+};
 use hex;
+use serde_json::json;
 use solana_clap_utils::{
     input_parsers::{
         keypair_of,
@@ -48,6 +80,7 @@ use solana_sdk::{
         CommitmentConfig,
         CommitmentLevel,
     },
+    instruction::Instruction,
     native_token::*,
     program_error::ProgramError::AccountAlreadyInitialized,
     pubkey::Pubkey,
@@ -57,6 +90,7 @@ use solana_sdk::{
         Signer,
     },
     system_instruction::transfer,
+    sysvar,
     transaction::Transaction,
 };
 use solitaire::{
@@ -64,7 +98,11 @@ use solitaire::{
     AccountState,
     Info,
 };
-use solitaire_client::Derive;
+use solitaire_client::{
+    AccEntry,
+    Derive,
+    ToInstruction,
+};
 
 struct Config {
     rpc_client: RpcClient,
@@ -120,13 +158,7 @@ fn command_post_message(
     println!("Posting a message to the wormhole");
 
     // Fetch the message fee
-    let bridge_config_account = config
-        .rpc_client
-        .get_account(&Bridge::<'_, { AccountState::Initialized }>::key(
-            None, bridge,
-        ))?;
-    let bridge_config = BridgeData::try_from_slice(bridge_config_account.data.as_slice())?;
-    let fee = bridge_config.config.fee;
+    let fee = message_fee(config, bridge, &config.owner.pubkey())?;
     println!("Message fee: {} lamports", fee);
 
     let transfer_ix = transfer(
@@ -148,16 +180,42 @@ fn command_post_message(
             commitment,
         )
         .unwrap(),
-        None => bridge::instructions::post_message(
-            *bridge,
-            config.owner.pubkey(),
-            config.owner.pubkey(),
-            message.pubkey(),
-            nonce,
-            payload,
-            commitment,
-        )
-        .unwrap(),
+        None => {
+            let emitter = config.owner.pubkey();
+            let accounts = PostMessageAccounts {
+                bridge: AccEntry::Derived(*bridge),
+                message: AccEntry::SignerKey(message.pubkey()),
+                emitter: AccEntry::SignerKeyRO(emitter),
+                sequence: AccEntry::derived_from::<Sequence<'_>, _>(
+                    &SequenceDerivationData {
+                        emitter_key: &emitter,
+                    },
+                    bridge,
+                ),
+                payer: AccEntry::SignerKey(config.owner.pubkey()),
+                fee_collector: AccEntry::Derived(*bridge),
+                clock: AccEntry::Sysvar(sysvar::clock::id()),
+                emitter_fee: AccEntry::derived_from_ro::<
+                    EmitterFee<'_, { AccountState::MaybeInitialized }>,
+                    _,
+                >(
+                    &EmitterFeeDerivationData {
+                        emitter_key: emitter,
+                    },
+                    bridge,
+                ),
+            };
+            let data = (
+                bridge::instruction::Instruction::PostMessage,
+                PostMessageData {
+                    nonce,
+                    payload,
+                    consistency_level: commitment,
+                },
+            )
+                .try_to_vec()?;
+            accounts.to_ix(*bridge, &data)?.0
+        }
     };
     let mut transaction =
         Transaction::new_with_payer(&[transfer_ix, ix], Some(&config.fee_payer.pubkey()));
@@ -171,6 +229,355 @@ fn command_post_message(
     Ok(Some(transaction))
 }
 
+// Fee the emitter has to pay per message, an emitter fee override takes precedence over the bridge
+// wide fee.
+fn message_fee(config: &Config, bridge: &Pubkey, emitter: &Pubkey) -> Result<u64, Error> {
+    let bridge_config: BridgeData = fetch_account(
+        config,
+        &Bridge::<'_, { AccountState::Initialized }>::key(None, bridge),
+    )?;
+
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: *emitter,
+        },
+        bridge,
+    );
+    match config
+        .rpc_client
+        .get_account_with_commitment(&emitter_fee, config.commitment_config)?
+        .value
+    {
+        Some(account) => Ok(EmitterFeeData::deserialize(&mut account.data.as_slice())?.fee),
+        None => Ok(bridge_config.config.fee),
+    }
+}
+
+fn command_post_vaa(config: &Config, bridge: &Pubkey, vaa: &VAA) -> Result<Pubkey, Error> {
+    println!(
+        "Posting VAA {}/{}/{}",
+        vaa.emitter_chain,
+        hex::encode(vaa.emitter_address),
+        vaa.sequence
+    );
+
+    client::post_vaa(
+        &config.rpc_client,
+        bridge,
+        &config.fee_payer,
+        vaa,
+        &PostVAAOptions {
+            commitment: config.commitment_config,
+            ..PostVAAOptions::default()
+        },
+        print_progress,
+    )
+}
+
+// Post a governance VAA if it is not on chain yet, and build the transaction executing it. `build`
+// receives the posted VAA account and the governance emitter.
+fn command_governance(
+    config: &Config,
+    bridge: &Pubkey,
+    vaa: &VAA,
+    build: impl FnOnce(Pubkey, Pubkey) -> Instruction,
+) -> CommmandResult {
+    let message = command_post_vaa(config, bridge, vaa)?;
+    let ix = build(message, Pubkey::new(&vaa.emitter_address));
+
+    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+    transaction.sign(&[&config.fee_payer], recent_blockhash);
+    Ok(Some(transaction))
+}
+
+fn command_upgrade_guardian_set(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let payload: GovernancePayloadGuardianSetChange = governance_payload(vaa)?;
+    let old_index = payload
+        .new_guardian_set_index
+        .checked_sub(1)
+        .ok_or("guardian set 0 can not be the target of an upgrade")?;
+    println!(
+        "Upgrading guardian set {} to {}",
+        old_index, payload.new_guardian_set_index
+    );
+
+    command_governance(config, bridge, vaa, |message, emitter| {
+        instructions::upgrade_guardian_set(
+            *bridge,
+            config.fee_payer.pubkey(),
+            message,
+            emitter,
+            old_index,
+            payload.new_guardian_set_index,
+            vaa.sequence,
+        )
+    })
+}
+
+fn command_upgrade_contract(
+    config: &Config,
+    bridge: &Pubkey,
+    vaa: &VAA,
+    spill: Option<Pubkey>,
+) -> CommmandResult {
+    let payload: GovernancePayloadUpgrade = governance_payload(vaa)?;
+    println!("Upgrading contract to {}", payload.new_contract);
+
+    command_governance(config, bridge, vaa, |message, emitter| {
+        instructions::upgrade_contract(
+            *bridge,
+            config.fee_payer.pubkey(),
+            message,
+            emitter,
+            payload.new_contract,
+            spill.unwrap_or_else(|| config.fee_payer.pubkey()),
+            vaa.sequence,
+        )
+    })
+}
+
+fn command_set_fees(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let payload: GovernancePayloadSetMessageFee = governance_payload(vaa)?;
+    println!("Setting message fee to {} lamports", payload.fee);
+
+    command_governance(config, bridge, vaa, |message, emitter| {
+        instructions::set_fees(
+            *bridge,
+            config.fee_payer.pubkey(),
+            message,
+            emitter,
+            vaa.sequence,
+        )
+    })
+}
+
+fn command_transfer_fees(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let payload: GovernancePayloadTransferFees = governance_payload(vaa)?;
+    let recipient = Pubkey::new(&payload.to);
+    println!("Transferring {} lamports to {}", payload.amount, recipient);
+
+    command_governance(config, bridge, vaa, |message, emitter| {
+        instructions::transfer_fees(
+            *bridge,
+            config.fee_payer.pubkey(),
+            message,
+            emitter,
+            vaa.sequence,
+            recipient,
+        )
+    })
+}
+
+fn command_set_emitter_fee(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let payload: GovernancePayloadSetEmitterFee = governance_payload(vaa)?;
+    println!(
+        "Setting message fee of {} to {} lamports",
+        payload.emitter, payload.fee
+    );
+
+    command_governance(config, bridge, vaa, |message, emitter| {
+        instructions::set_emitter_fee(
+            *bridge,
+            config.fee_payer.pubkey(),
+            message,
+            emitter,
+            vaa.sequence,
+            payload.emitter,
+        )
+    })
+}
+
+fn command_set_guardian_set_expiration(
+    config: &Config,
+    bridge: &Pubkey,
+    vaa: &VAA,
+) -> CommmandResult {
+    let payload: GovernancePayloadSetGuardianSetExpiration = governance_payload(vaa)?;
+    println!(
+        "Setting expiration of guardian set {} to {}",
+        payload.guardian_set_index, payload.expiration_time
+    );
+
+    command_governance(config, bridge, vaa, |message, emitter| {
+        instructions::set_guardian_set_expiration(
+            *bridge,
+            config.fee_payer.pubkey(),
+            message,
+            emitter,
+            vaa.sequence,
+            payload.guardian_set_index,
+        )
+    })
+}
+
+fn command_dump_bridge(config: &Config, bridge: &Pubkey) -> CommmandResult {
+    let address = Bridge::<'_, { AccountState::Initialized }>::key(None, bridge);
+    let data: BridgeData = fetch_account(config, &address)?;
+
+    print_json(&json!({
+        "address": address.to_string(),
+        "guardian_set_index": data.guardian_set_index,
+        "last_lamports": data.last_lamports,
+        "config": {
+            "guardian_set_expiration_time": data.config.guardian_set_expiration_time,
+            "fee": data.config.fee,
+        },
+    }));
+    Ok(None)
+}
+
+fn command_dump_guardian_set(
+    config: &Config,
+    bridge: &Pubkey,
+    index: Option<u32>,
+) -> CommmandResult {
+    let index = match index {
+        Some(index) => index,
+        None => {
+            let bridge_data: BridgeData = fetch_account(
+                config,
+                &Bridge::<'_, { AccountState::Initialized }>::key(None, bridge),
+            )?;
+            bridge_data.guardian_set_index
+        }
+    };
+    let address = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index },
+        bridge,
+    );
+    let data: GuardianSetData = fetch_account(config, &address)?;
+
+    print_json(&json!({
+        "address": address.to_string(),
+        "index": data.index,
+        "keys": data.keys.iter().map(hex::encode).collect::<Vec<_>>(),
+        "creation_time": data.creation_time,
+        "expiration_time": data.expiration_time,
+    }));
+    Ok(None)
+}
+
+fn command_dump_sequence(config: &Config, bridge: &Pubkey, emitter: &Pubkey) -> CommmandResult {
+    let address = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: emitter,
+        },
+        bridge,
+    );
+
+    // The tracker is only created with the first message, until then the next sequence is 0.
+    let sequence = match config
+        .rpc_client
+        .get_account_with_commitment(&address, config.commitment_config)?
+        .value
+    {
+        Some(account) => Some(SequenceTracker::deserialize(&mut account.data.as_slice())?),
+        None => None,
+    };
+
+    print_json(&json!({
+        "address": address.to_string(),
+        "emitter": emitter.to_string(),
+        "initialized": sequence.is_some(),
+        "sequence": sequence.map(|s| s.sequence).unwrap_or(0),
+    }));
+    Ok(None)
+}
+
+fn command_dump_message(config: &Config, message: &Pubkey) -> CommmandResult {
+    let account = config.rpc_client.get_account(message)?;
+    let kind = String::from_utf8_lossy(&account.data[..3.min(account.data.len())]).to_string();
+    if kind != "msg" && kind != "vaa" {
+        return Err(format!("{} is not a posted message or VAA", message).into());
+    }
+    let data = PostedMessageData::deserialize(&mut account.data.as_slice())?;
+
+    print_json(&json!({
+        "address": message.to_string(),
+        "kind": kind,
+        "vaa_version": data.vaa_version,
+        "consistency_level": data.consistency_level,
+        "vaa_time": data.vaa_time,
+        "vaa_signature_account": data.vaa_signature_account.to_string(),
+        "submission_time": data.submission_time,
+        "nonce": data.nonce,
+        "sequence": data.sequence,
+        "emitter_chain": data.emitter_chain,
+        "emitter_address": hex::encode(data.emitter_address),
+        "payload": hex::encode(&data.payload),
+    }));
+    Ok(None)
+}
+
+fn command_derive_addresses(bridge: &Pubkey, emitter: &Pubkey) -> CommmandResult {
+    print_json(&json!({
+        "bridge": Bridge::<'_, { AccountState::Initialized }>::key(None, bridge).to_string(),
+        "fee_collector": FeeCollector::key(None, bridge).to_string(),
+        "upgrade_authority": <Derive<Info<'_>, "upgrade">>::key(None, bridge).to_string(),
+        "emitter": emitter.to_string(),
+        "sequence": Sequence::key(
+            &SequenceDerivationData {
+                emitter_key: emitter,
+            },
+            bridge,
+        )
+        .to_string(),
+        "emitter_fee": EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+            &EmitterFeeDerivationData {
+                emitter_key: *emitter,
+            },
+            bridge,
+        )
+        .to_string(),
+    }));
+    Ok(None)
+}
+
+fn fetch_account<T: BorshDeserialize>(config: &Config, address: &Pubkey) -> Result<T, Error> {
+    let data = config.rpc_client.get_account_data(address)?;
+    Ok(T::deserialize(&mut data.as_slice())?)
+}
+
+fn governance_payload<T: DeserializePayload>(vaa: &VAA) -> Result<T, Error> {
+    T::deserialize(&mut vaa.payload.as_slice())
+        .map_err(|e| format!("Invalid governance payload: {:?}", e).into())
+}
+
+fn print_json(value: &serde_json::Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap());
+}
+
+fn bridge_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("bridge")
+        .long("bridge")
+        .value_name("BRIDGE_KEY")
+        .validator(is_pubkey_or_keypair)
+        .takes_value(true)
+        .index(1)
+        .required(true)
+        .help("Specify the bridge program address")
+}
+
+fn vaa_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("vaa")
+        .validator(is_hex)
+        .value_name("VAA")
+        .takes_value(true)
+        .index(2)
+        .required(true)
+        .help("Signed VAA in hex")
+}
+
+fn vaa_of(matches: &ArgMatches<'_>) -> VAA {
+    let data = hex::decode(value_of::<String>(matches, "vaa").unwrap()).unwrap();
+    VAA::deserialize(&data).unwrap_or_else(|err| {
+        eprintln!("Invalid VAA: {}", err);
+        exit(1)
+    })
+}
+
 fn main() {
     let matches = App::new(crate_name!())
         .about(crate_description!())
@@ -325,6 +732,117 @@ fn main() {
                         .help("CPI Proxy to use"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("post-vaa")
+                .about("Verify the signatures of a VAA and post it")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("upgrade-guardian-set")
+                .about("Submit a guardian set upgrade governance VAA")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("upgrade-contract")
+                .about("Submit a contract upgrade governance VAA")
+                .arg(bridge_arg())
+                .arg(vaa_arg())
+                .arg(
+                    Arg::with_name("spill")
+                        .long("spill")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("SPILL")
+                        .takes_value(true)
+                        .help(
+                            "Account receiving the buffer's excess lamports, defaults to the fee payer",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-fees")
+                .about("Submit a message fee governance VAA")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer-fees")
+                .about("Submit a fee transfer governance VAA")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("set-emitter-fee")
+                .about("Submit an emitter fee governance VAA")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("set-guardian-set-expiration")
+                .about("Submit a guardian set expiration governance VAA")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("dump-bridge")
+                .about("Print the bridge config as JSON")
+                .arg(bridge_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("dump-guardian-set")
+                .about("Print a guardian set as JSON")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("index")
+                        .validator(is_u32)
+                        .value_name("INDEX")
+                        .takes_value(true)
+                        .index(2)
+                        .help("Guardian set index, defaults to the current set"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("dump-sequence")
+                .about("Print the sequence tracker of an emitter as JSON")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("emitter")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("EMITTER")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Emitter address"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("dump-message")
+                .about("Print a posted message or VAA as JSON")
+                .arg(
+                    Arg::with_name("message")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("MESSAGE")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Address of the posted message or VAA"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("derive-addresses")
+                .about("Print the bridge addresses derived for an emitter as JSON")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("emitter")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("EMITTER")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Emitter address"),
+                ),
+        )
         .get_matches();
 
     let config = {
@@ -404,6 +922,77 @@ fn main() {
                 proxy,
             )
         }
+        ("post-vaa", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_post_vaa(&config, &bridge, &vaa).map(|_| None)
+        }
+        ("upgrade-guardian-set", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_upgrade_guardian_set(&config, &bridge, &vaa)
+        }
+        ("upgrade-contract", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+            let spill = pubkey_of(arg_matches, "spill");
+
+            command_upgrade_contract(&config, &bridge, &vaa, spill)
+        }
+        ("set-fees", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_set_fees(&config, &bridge, &vaa)
+        }
+        ("transfer-fees", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_transfer_fees(&config, &bridge, &vaa)
+        }
+        ("set-emitter-fee", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_set_emitter_fee(&config, &bridge, &vaa)
+        }
+        ("set-guardian-set-expiration", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_set_guardian_set_expiration(&config, &bridge, &vaa)
+        }
+        ("dump-bridge", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+
+            command_dump_bridge(&config, &bridge)
+        }
+        ("dump-guardian-set", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let index: Option<u32> = value_of(arg_matches, "index");
+
+            command_dump_guardian_set(&config, &bridge, index)
+        }
+        ("dump-sequence", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let emitter = pubkey_of(arg_matches, "emitter").unwrap();
+
+            command_dump_sequence(&config, &bridge, &emitter)
+        }
+        ("dump-message", Some(arg_matches)) => {
+            let message = pubkey_of(arg_matches, "message").unwrap();
+
+            command_dump_message(&config, &message)
+        }
+        ("derive-addresses", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let emitter = pubkey_of(arg_matches, "emitter").unwrap();
+
+            command_derive_addresses(&bridge, &emitter)
+        }
 
         _ => unreachable!(),
     }
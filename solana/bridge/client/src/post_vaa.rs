@@ -0,0 +1,320 @@
+//! Posting a signed VAA to the bridge takes several transactions: the guardian signatures are
+//! verified in batches into a signature set account, after which the VAA itself is posted. This
+//! module drives that whole process so callers only need a single call.
+
+use borsh::BorshDeserialize;
+use bridge::{
+    accounts::{
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        PostedVAA,
+        PostedVAADerivationData,
+    },
+    instructions,
+    vaa::VAA,
+    PostVAAData,
+};
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::RpcSendTransactionConfig,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{
+        Keypair,
+        Signature,
+        Signer,
+    },
+    transaction::Transaction,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+
+type Error = Box<dyn std::error::Error>;
+
+/// Progress events emitted while a VAA is being posted.
+#[derive(Debug)]
+pub enum PostVAAProgress {
+    /// The VAA has already been posted, nothing was submitted.
+    AlreadyPosted(Pubkey),
+
+    /// A batch of signatures has been verified into the signature set.
+    SignaturesVerified {
+        batch: usize,
+        batches: usize,
+        signature: Signature,
+    },
+
+    /// A transaction failed and is about to be resubmitted.
+    Retrying { attempt: usize, error: String },
+
+    /// The VAA was posted to the given account.
+    Posted {
+        message: Pubkey,
+        signature: Signature,
+    },
+}
+
+/// Progress reporter for command line tools, printing each event on its own line and retries to
+/// stderr.
+pub fn print_progress(progress: &PostVAAProgress) {
+    match progress {
+        PostVAAProgress::AlreadyPosted(message) => println!("VAA already posted: {}", message),
+        PostVAAProgress::SignaturesVerified {
+            batch,
+            batches,
+            signature,
+        } => println!("Verified signatures {}/{}: {}", batch, batches, signature),
+        PostVAAProgress::Retrying { attempt, error } => {
+            eprintln!("Retrying ({}): {}", attempt, error)
+        }
+        PostVAAProgress::Posted { message, signature } => {
+            println!("Posted VAA {}: {}", message, signature)
+        }
+    }
+}
+
+/// Options controlling how transactions are submitted.
+pub struct PostVAAOptions {
+    /// Commitment to wait for on each transaction.
+    pub commitment: CommitmentConfig,
+
+    /// Number of times a failed transaction is resubmitted before giving up.
+    pub max_retries: usize,
+}
+
+impl Default for PostVAAOptions {
+    fn default() -> Self {
+        PostVAAOptions {
+            commitment: CommitmentConfig::confirmed(),
+            max_retries: 5,
+        }
+    }
+}
+
+/// Verify the signatures of `vaa` and post it to the bridge at `program_id`, returning the
+/// address of the posted VAA account. `progress` is called after every step.
+pub fn post_vaa(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    vaa: &VAA,
+    options: &PostVAAOptions,
+    mut progress: impl FnMut(&PostVAAProgress),
+) -> Result<Pubkey, Error> {
+    let vaa_data: PostVAAData = vaa.clone().into();
+    let message = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: instructions::hash_vaa(&vaa_data).to_vec(),
+        },
+        program_id,
+    );
+
+    // Posting is idempotent on chain, but skipping it here saves the verification fees.
+    if rpc_client
+        .get_account_with_commitment(&message, options.commitment)?
+        .value
+        .is_some()
+    {
+        progress(&PostVAAProgress::AlreadyPosted(message));
+        return Ok(message);
+    }
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        program_id,
+    );
+    let guardian_set = GuardianSetData::deserialize(
+        &mut rpc_client.get_account_data(&guardian_set_key)?.as_slice(),
+    )?;
+
+    let signature_set = Keypair::new();
+    let plan = instructions::verify_signatures_plan(
+        *program_id,
+        payer.pubkey(),
+        signature_set.pubkey(),
+        &guardian_set.keys,
+        vaa,
+    )
+    .map_err(|e| format!("unable to plan signature verification: {:?}", e))?;
+
+    let batches = plan.len();
+    for (batch, instructions) in plan.iter().enumerate() {
+        let signature = execute_with_retry(
+            rpc_client,
+            payer,
+            &[payer, &signature_set],
+            instructions,
+            options,
+            &mut progress,
+        )?;
+        progress(&PostVAAProgress::SignaturesVerified {
+            batch: batch + 1,
+            batches,
+            signature,
+        });
+    }
+
+    let signature = execute_with_retry(
+        rpc_client,
+        payer,
+        &[payer],
+        &[instructions::post_vaa(
+            *program_id,
+            payer.pubkey(),
+            signature_set.pubkey(),
+            vaa_data,
+        )],
+        options,
+        &mut progress,
+    )?;
+    progress(&PostVAAProgress::Posted { message, signature });
+
+    Ok(message)
+}
+
+// Sign and submit a transaction, resubmitting with a fresh blockhash on failure. Resubmitting a
+// signature verification that already landed is harmless as it only sets the same flags again.
+fn execute_with_retry(
+    rpc_client: &RpcClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    instructions: &[Instruction],
+    options: &PostVAAOptions,
+    progress: &mut impl FnMut(&PostVAAProgress),
+) -> Result<Signature, Error> {
+    let mut attempt = 0;
+    loop {
+        let result = rpc_client
+            .get_recent_blockhash()
+            .and_then(|(blockhash, _)| {
+                let mut transaction =
+                    Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+                transaction.sign(&signers.to_vec(), blockhash);
+                rpc_client.send_and_confirm_transaction_with_spinner_and_config(
+                    &transaction,
+                    options.commitment,
+                    RpcSendTransactionConfig {
+                        skip_preflight: true,
+                        preflight_commitment: None,
+                        encoding: None,
+                        max_retries: None,
+                    },
+                )
+            });
+
+        match result {
+            Ok(signature) => return Ok(signature),
+            Err(e) if attempt < options.max_retries => {
+                attempt += 1;
+                progress(&PostVAAProgress::Retrying {
+                    attempt,
+                    error: e.to_string(),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge::{
+        instructions::max_signatures_per_transaction,
+        vaa::VAASignature,
+        VerifySignaturesData,
+        MAX_LEN_GUARDIAN_KEYS,
+    };
+
+    // Offsets of the instruction indices within each 11 byte secp256k1 signature offsets entry.
+    const SECP_INDEX_OFFSETS: [usize; 3] = [2, 5, 10];
+
+    #[test]
+    fn signatures_are_split_into_batches() {
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let signature_set = Pubkey::new_unique();
+        let per_tx = max_signatures_per_transaction(program_id, payer, signature_set, 0).unwrap();
+        assert!(per_tx < MAX_LEN_GUARDIAN_KEYS);
+
+        let guardian_keys: Vec<[u8; 20]> = (0..MAX_LEN_GUARDIAN_KEYS)
+            .map(|index| [index as u8; 20])
+            .collect();
+        let vaa = VAA {
+            signatures: (0..MAX_LEN_GUARDIAN_KEYS)
+                .map(|index| VAASignature {
+                    signature: vec![index as u8; 65],
+                    guardian_index: index as u8,
+                })
+                .collect(),
+            ..VAA::default()
+        };
+        let plan = instructions::verify_signatures_plan(
+            program_id,
+            payer,
+            signature_set,
+            &guardian_keys,
+            &vaa,
+        )
+        .unwrap();
+        assert_eq!(plan.len(), (MAX_LEN_GUARDIAN_KEYS + per_tx - 1) / per_tx);
+
+        for (batch, instructions) in plan.iter().enumerate() {
+            let first = batch * per_tx;
+            let count = per_tx.min(MAX_LEN_GUARDIAN_KEYS - first);
+
+            // The secp256k1 instruction comes first and all of its offsets point into itself.
+            let secp = &instructions[0];
+            assert_eq!(secp.program_id, solana_sdk::secp256k1_program::id());
+            assert_eq!(secp.data[0] as usize, count);
+            for entry in 0..count {
+                for offset in SECP_INDEX_OFFSETS.iter() {
+                    assert_eq!(secp.data[1 + entry * 11 + offset], 0);
+                }
+            }
+
+            // Guardians of the batch map to their position in it, all others are skipped.
+            let data = VerifySignaturesData::try_from_slice(&instructions[1].data[1..]).unwrap();
+            for (index, signer) in data.signers.iter().enumerate() {
+                match index.checked_sub(first) {
+                    Some(position) if position < count => assert_eq!(*signer, position as i8),
+                    _ => assert_eq!(*signer, -1),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn failed_transactions_are_retried() {
+        // The mock client fails every request.
+        let rpc_client = RpcClient::new_mock("fails".to_string());
+        let payer = Keypair::new();
+        let options = PostVAAOptions {
+            max_retries: 2,
+            ..PostVAAOptions::default()
+        };
+
+        let mut attempts = Vec::new();
+        let result = execute_with_retry(
+            &rpc_client,
+            &payer,
+            &[&payer],
+            &[],
+            &options,
+            &mut |progress| match progress {
+                PostVAAProgress::Retrying { attempt, .. } => attempts.push(*attempt),
+                _ => panic!("unexpected progress {:?}", progress),
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(attempts, vec![1, 2]);
+    }
+}
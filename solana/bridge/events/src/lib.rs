@@ -0,0 +1,328 @@
+//! Structured events emitted by the Wormhole core bridge on Solana.
+//!
+//! Every state changing instruction logs a single line of the form
+//!
+//! ```text
+//! Program log: WormholeEvent: <base64(version || borsh(BridgeEvent))>
+//! ```
+//!
+//! The version byte is bumped whenever an existing variant changes shape. New variants are only
+//! ever appended to `BridgeEvent` so that older decoders fail cleanly on unknown events instead of
+//! misinterpreting them.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use std::fmt;
+
+/// Prefix of the log line carrying an encoded event, as passed to `msg!`.
+pub const EVENT_PREFIX: &str = "WormholeEvent: ";
+
+/// Prefix the Solana runtime adds to every line logged by a program.
+pub const PROGRAM_LOG_PREFIX: &str = "Program log: ";
+
+/// Version of the event encoding produced by this crate.
+pub const EVENT_VERSION: u8 = 1;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum BridgeEvent {
+    /// The bridge was initialized with its first guardian set.
+    Initialized {
+        guardian_set_index: u32,
+        guardians: Vec<[u8; 20]>,
+        fee: u64,
+        guardian_set_expiration_time: u32,
+    },
+
+    /// A message was posted for the guardians to observe.
+    MessagePosted {
+        message: [u8; 32],
+        emitter: [u8; 32],
+        sequence: u64,
+        nonce: u32,
+        consistency_level: u8,
+        fee: u64,
+    },
+
+    /// Guardian signatures were verified and recorded in a signature set.
+    SignaturesVerified {
+        signature_set: [u8; 32],
+        hash: [u8; 32],
+        guardian_set_index: u32,
+        guardian_indices: Vec<u8>,
+    },
+
+    /// A VAA with a quorum of signatures was posted.
+    VAAPosted {
+        message: [u8; 32],
+        signature_set: [u8; 32],
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+        guardian_set_index: u32,
+    },
+
+    /// A contract upgrade was executed through governance.
+    ContractUpgraded { new_contract: [u8; 32] },
+
+    /// A new guardian set was activated through governance.
+    GuardianSetChanged {
+        old_guardian_set_index: u32,
+        new_guardian_set_index: u32,
+        old_guardian_set_expiration_time: u32,
+        guardians: Vec<[u8; 20]>,
+    },
+
+    /// The bridge wide message fee was changed through governance.
+    FeesSet { fee: u64 },
+
+    /// Collected fees were transferred out through governance.
+    FeesTransferred { recipient: [u8; 32], amount: u64 },
+
+    /// The message fee of a single emitter was overridden through governance.
+    EmitterFeeSet { emitter: [u8; 32], fee: u64 },
+
+    /// The expiration of a previous guardian set was changed through governance.
+    GuardianSetExpirationSet {
+        guardian_set_index: u32,
+        expiration_time: u32,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EventError {
+    /// The event data is not valid base64.
+    InvalidEncoding,
+
+    /// The event was encoded with a version this decoder does not understand.
+    UnsupportedVersion(u8),
+
+    /// The event data could not be deserialized.
+    InvalidData,
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventError::InvalidEncoding => write!(f, "event is not valid base64"),
+            EventError::UnsupportedVersion(v) => write!(f, "unsupported event version {}", v),
+            EventError::InvalidData => write!(f, "event data could not be deserialized"),
+        }
+    }
+}
+
+impl std::error::Error for EventError {
+}
+
+impl BridgeEvent {
+    /// Encode the event into the base64 representation that follows `EVENT_PREFIX` in the logs.
+    pub fn encode(&self) -> String {
+        encode(self)
+    }
+
+    /// Decode the base64 representation produced by `encode`.
+    pub fn decode(data: &str) -> Result<Self, EventError> {
+        decode(data)
+    }
+}
+
+/// Encode an event of any program into the base64 representation that follows `EVENT_PREFIX`.
+/// Programs built on the bridge, such as the token bridge, log their own event types this way.
+pub fn encode<E: BorshSerialize>(event: &E) -> String {
+    let mut data = vec![EVENT_VERSION];
+    // Serializing into a Vec can not fail.
+    event.serialize(&mut data).unwrap();
+    base64::encode(data)
+}
+
+/// Decode the base64 representation produced by `encode`.
+pub fn decode<E: BorshDeserialize>(data: &str) -> Result<E, EventError> {
+    let data = base64::decode(data.trim()).map_err(|_| EventError::InvalidEncoding)?;
+    match data.split_first() {
+        Some((&EVENT_VERSION, mut event)) => {
+            let decoded = E::deserialize(&mut event).map_err(|_| EventError::InvalidData)?;
+            // Trailing bytes indicate the event was not produced by this encoder.
+            if !event.is_empty() {
+                return Err(EventError::InvalidData);
+            }
+            Ok(decoded)
+        }
+        Some((&version, _)) => Err(EventError::UnsupportedVersion(version)),
+        None => Err(EventError::InvalidData),
+    }
+}
+
+/// Decode a single log line, returns `None` if the line does not carry an event.
+///
+/// This does not check which program logged the line, use `parse_logs` when processing the logs
+/// of a whole transaction.
+pub fn parse_log(line: &str) -> Option<Result<BridgeEvent, EventError>> {
+    parse_line(line)
+}
+
+fn parse_line<E: BorshDeserialize>(line: &str) -> Option<Result<E, EventError>> {
+    let line = line.strip_prefix(PROGRAM_LOG_PREFIX).unwrap_or(line);
+    line.strip_prefix(EVENT_PREFIX).map(decode)
+}
+
+/// Decode all events logged by `program_id` (base58) in the logs of a transaction.
+///
+/// Any program can log a line that looks like an event, so the invocation stack is tracked and
+/// only lines logged while the bridge itself is executing are considered. This includes events
+/// from CPI calls into the bridge.
+pub fn parse_logs<S: AsRef<str>>(
+    program_id: &str,
+    logs: &[S],
+) -> Vec<Result<BridgeEvent, EventError>> {
+    parse_events(program_id, logs)
+}
+
+/// Like `parse_logs`, for the event type `E` of another program logging events with `encode`.
+pub fn parse_events<E: BorshDeserialize, S: AsRef<str>>(
+    program_id: &str,
+    logs: &[S],
+) -> Vec<Result<E, EventError>> {
+    let invoke_prefix = format!("Program {} invoke [", program_id);
+    let mut stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs.iter().map(AsRef::as_ref) {
+        if line.starts_with(PROGRAM_LOG_PREFIX) {
+            if stack.last() == Some(&true) {
+                if let Some(event) = parse_line(line) {
+                    events.push(event);
+                }
+            }
+        } else if line.starts_with("Program ") && line.contains(" invoke [") {
+            stack.push(line.starts_with(&invoke_prefix));
+        } else if line.starts_with("Program ")
+            && (line.ends_with(" success") || line.contains(" failed: "))
+        {
+            stack.pop();
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BRIDGE: &str = "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o";
+    const OTHER: &str = "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE";
+
+    fn message_posted(sequence: u64) -> BridgeEvent {
+        BridgeEvent::MessagePosted {
+            message: [1u8; 32],
+            emitter: [2u8; 32],
+            sequence,
+            nonce: 42,
+            consistency_level: 32,
+            fee: 100,
+        }
+    }
+
+    fn log_line(event: &BridgeEvent) -> String {
+        format!("{}{}{}", PROGRAM_LOG_PREFIX, EVENT_PREFIX, event.encode())
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let events = [
+            message_posted(7),
+            BridgeEvent::GuardianSetChanged {
+                old_guardian_set_index: 0,
+                new_guardian_set_index: 1,
+                old_guardian_set_expiration_time: 86400,
+                guardians: vec![[3u8; 20], [4u8; 20]],
+            },
+            BridgeEvent::FeesSet { fee: 0 },
+            BridgeEvent::EmitterFeeSet {
+                emitter: [5u8; 32],
+                fee: 900,
+            },
+        ];
+
+        for event in events.iter() {
+            assert_eq!(Some(Ok(event.clone())), parse_log(&log_line(event)));
+        }
+    }
+
+    #[test]
+    fn test_invalid_events() {
+        assert_eq!(None, parse_log("Program log: Sequence: 7"));
+        assert_eq!(
+            Some(Err(EventError::InvalidEncoding)),
+            parse_log("Program log: WormholeEvent: !!!")
+        );
+
+        let mut data = vec![EVENT_VERSION + 1];
+        message_posted(7).serialize(&mut data).unwrap();
+        assert_eq!(
+            Err(EventError::UnsupportedVersion(EVENT_VERSION + 1)),
+            BridgeEvent::decode(&base64::encode(&data))
+        );
+
+        let mut data = vec![EVENT_VERSION];
+        message_posted(7).serialize(&mut data).unwrap();
+        data.push(0);
+        assert_eq!(
+            Err(EventError::InvalidData),
+            BridgeEvent::decode(&base64::encode(&data))
+        );
+    }
+
+    #[test]
+    fn test_parse_logs_tracks_invocations() {
+        let logs = vec![
+            format!("Program {} invoke [1]", OTHER),
+            // Spoofed event logged by another program must be ignored.
+            log_line(&message_posted(1)),
+            format!("Program {} invoke [2]", BRIDGE),
+            "Program log: Sequence: 2".to_string(),
+            log_line(&message_posted(2)),
+            format!("Program {} consumed 20000 of 180000 compute units", BRIDGE),
+            format!("Program {} success", BRIDGE),
+            log_line(&message_posted(3)),
+            format!("Program {} success", OTHER),
+            format!("Program {} invoke [1]", BRIDGE),
+            log_line(&BridgeEvent::FeesSet { fee: 5 }),
+            format!("Program {} success", BRIDGE),
+        ];
+
+        assert_eq!(
+            vec![Ok(message_posted(2)), Ok(BridgeEvent::FeesSet { fee: 5 })],
+            parse_logs(BRIDGE, &logs)
+        );
+    }
+
+    #[test]
+    fn test_parse_events_of_other_programs() {
+        #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+        enum OtherEvent {
+            Ping { value: u64 },
+        }
+
+        let logs = vec![
+            format!("Program {} invoke [1]", OTHER),
+            format!(
+                "{}{}{}",
+                PROGRAM_LOG_PREFIX,
+                EVENT_PREFIX,
+                encode(&OtherEvent::Ping { value: 9 })
+            ),
+            format!("Program {} invoke [2]", BRIDGE),
+            log_line(&message_posted(1)),
+            format!("Program {} success", BRIDGE),
+            format!("Program {} success", OTHER),
+        ];
+
+        assert_eq!(
+            vec![Ok(OtherEvent::Ping { value: 9 })],
+            parse_events::<OtherEvent, _>(OTHER, &logs)
+        );
+        assert_eq!(vec![Ok(message_posted(1))], parse_logs(BRIDGE, &logs));
+    }
+}
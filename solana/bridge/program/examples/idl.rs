@@ -0,0 +1,7 @@
+//! Print the program's IDL as JSON.
+//!
+//! cargo run --example idl --features idl > bridge.json
+
+fn main() {
+    println!("{}", bridge::instruction::idl().to_json());
+}
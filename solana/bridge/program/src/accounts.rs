@@ -1,5 +1,6 @@
 pub mod bridge;
 pub mod claim;
+pub mod emitter_fee;
 pub mod fee_collector;
 pub mod guardian_set;
 pub mod posted_message;
@@ -9,6 +10,7 @@ pub mod signature_set;
 
 pub use bridge::*;
 pub use claim::*;
+pub use emitter_fee::*;
 pub use fee_collector::*;
 pub use guardian_set::*;
 pub use posted_message::*;
@@ -10,6 +10,7 @@ use serde::{
     Serialize,
 };
 use solitaire::{
+    idl::IdlSeed,
     processors::seeded::Seeded,
     AccountOwner,
     AccountState,
@@ -44,4 +45,12 @@ impl<'b, const State: AccountState> Seeded<&ClaimDerivationData> for Claim<'b, {
             data.sequence.to_be_bytes().to_vec(),
         ];
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Arg("emitter_address"),
+            IdlSeed::Arg("emitter_chain"),
+            IdlSeed::Arg("sequence"),
+        ]
+    }
 }
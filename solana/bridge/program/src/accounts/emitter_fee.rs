@@ -0,0 +1,95 @@
+//! EmitterFee accounts hold a governance defined message fee for a single emitter, overriding the
+//! bridge wide fee in `BridgeConfig`.
+
+use crate::accounts::BridgeConfig;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    idl::IdlSeed,
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+
+pub type EmitterFee<'b, const State: AccountState> = Data<'b, EmitterFeeData, { State }>;
+
+#[derive(Default, Clone, Copy, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct EmitterFeeData {
+    /// Amount of lamports the emitter has to pay to post a message. A fee of zero waives the fee.
+    pub fee: u64,
+
+    /// Bump seed of the account address, stored to skip the bump search when posting messages.
+    pub bump: u8,
+}
+
+pub struct EmitterFeeDerivationData {
+    pub emitter_key: Pubkey,
+}
+
+impl<'b, const State: AccountState> Seeded<&EmitterFeeDerivationData>
+    for EmitterFee<'b, { State }>
+{
+    fn seeds(data: &EmitterFeeDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            "EmitterFee".as_bytes().to_vec(),
+            data.emitter_key.to_bytes().to_vec(),
+        ]
+    }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("EmitterFee"), IdlSeed::Arg("emitter_key")]
+    }
+
+    fn stored_bump(&self) -> Option<u8> {
+        match self.is_initialized() {
+            true => Some(self.bump),
+            false => None,
+        }
+    }
+}
+
+/// Fee an emitter has to pay per message once governance has created its override.
+pub fn effective_fee<const State: AccountState>(
+    config: &BridgeConfig,
+    emitter_fee: &EmitterFee<'_, { State }>,
+) -> u64 {
+    let fee_override = match emitter_fee.is_initialized() {
+        true => Some(emitter_fee.fee),
+        false => None,
+    };
+    fee_with_override(config, fee_override)
+}
+
+/// Overrides lower or waive the bridge wide fee, they never raise it. `PostMessage` takes the
+/// override account optionally, a higher override could be avoided by leaving it out, so
+/// `SetEmitterFee` rejects them. Lowering the bridge wide fee later caps existing overrides.
+pub fn fee_with_override(config: &BridgeConfig, fee_override: Option<u64>) -> u64 {
+    match fee_override {
+        Some(fee) => fee.min(config.fee),
+        None => config.fee,
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for EmitterFeeData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for EmitterFeeData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
+    }
+}
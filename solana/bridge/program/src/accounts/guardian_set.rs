@@ -1,7 +1,10 @@
 //! GuardianSet represents an account containing information about the current active guardians
 //! responsible for signing wormhole VAAs.
 
-use crate::types::GuardianPublicKey;
+use crate::{
+    types::GuardianPublicKey,
+    MAX_LEN_GUARDIAN_KEYS,
+};
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -11,6 +14,7 @@ use serde::{
     Serialize,
 };
 use solitaire::{
+    idl::IdlSeed,
     processors::seeded::Seeded,
     AccountOwner,
     AccountState,
@@ -18,7 +22,8 @@ use solitaire::{
     Owned,
 };
 
-pub type GuardianSet<'b, const State: AccountState> = Data<'b, GuardianSetData, { State }>;
+pub type GuardianSet<'b, const State: AccountState> =
+    Data<'b, GuardianSetData, { State }, { GuardianSetData::MAX_SIZE }>;
 
 #[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct GuardianSetData {
@@ -49,9 +54,18 @@ impl<'a, const State: AccountState> Seeded<&GuardianSetDerivationData>
             data.index.to_be_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("GuardianSet"), IdlSeed::Arg("index")]
+    }
 }
 
 impl GuardianSetData {
+    /// Guardian sets are allocated for the largest set that fits in a VAA, so that the account
+    /// never has to grow. Sets created before this was introduced are exactly sized, which still
+    /// parse as the data is read without requiring the whole account to be consumed.
+    pub const MAX_SIZE: usize = 4 + 4 + 20 * MAX_LEN_GUARDIAN_KEYS + 4 + 4;
+
     /// Number of guardians in the set
     pub fn num_guardians(&self) -> u8 {
         self.keys.iter().filter(|v| **v != [0u8; 20]).count() as u8
@@ -4,6 +4,7 @@ use borsh::{
     BorshSerialize,
 };
 use solitaire::{
+    idl::IdlSeed,
     processors::seeded::Seeded,
     AccountOwner,
     AccountState,
@@ -28,6 +29,10 @@ impl<'a, const State: AccountState> Seeded<&PostedVAADerivationData> for PostedV
     fn seeds(data: &PostedVAADerivationData) -> Vec<Vec<u8>> {
         vec!["PostedVAA".as_bytes().to_vec(), data.payload_hash.to_vec()]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("PostedVAA"), IdlSeed::Arg("payload_hash")]
+    }
 }
 
 #[repr(transparent)]
@@ -4,18 +4,45 @@ use borsh::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    idl::IdlSeed,
     processors::seeded::Seeded,
     AccountState,
     AccountOwner,
     Data,
     Owned,
 };
+use std::io::Write;
 
 pub type Sequence<'b> = Data<'b, SequenceTracker, { AccountState::MaybeInitialized }>;
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+#[derive(Default, Clone, Copy)]
 pub struct SequenceTracker {
     pub sequence: u64,
+
+    /// Bump seed of the tracker address. Trackers created before bumps were stored do not carry
+    /// one, the field is only serialized when present so both layouts remain readable.
+    pub bump: Option<u8>,
+}
+
+impl BorshSerialize for SequenceTracker {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.sequence.serialize(writer)?;
+        if let Some(bump) = self.bump {
+            bump.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for SequenceTracker {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let sequence = u64::deserialize(buf)?;
+        let bump = match buf.is_empty() {
+            true => None,
+            false => Some(u8::deserialize(buf)?),
+        };
+        Ok(SequenceTracker { sequence, bump })
+    }
 }
 
 pub struct SequenceDerivationData<'a> {
@@ -29,6 +56,17 @@ impl<'b> Seeded<&SequenceDerivationData<'b>> for Sequence<'b> {
             data.emitter_key.to_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("Sequence"), IdlSeed::Arg("emitter_key")]
+    }
+
+    fn stored_bump(&self) -> Option<u8> {
+        match self.is_initialized() {
+            true => self.bump,
+            false => None,
+        }
+    }
 }
 
 impl Owned for SequenceTracker {
@@ -16,17 +16,27 @@ use solitaire::{
 use crate::{
     accounts::{
         Bridge,
+        EmitterFee,
+        EmitterFeeDerivationData,
         GuardianSet,
         GuardianSetDerivationData,
     },
     error::Error::{
+        EmitterFeeTooHigh,
         InvalidFeeRecipient,
         InvalidGovernanceKey,
         InvalidGovernanceWithdrawal,
+        InvalidGuardianSetExpiration,
         InvalidGuardianSetUpgrade,
     },
+    events::{
+        emit,
+        BridgeEvent,
+    },
     types::{
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetEmitterFee,
+        GovernancePayloadSetGuardianSetExpiration,
         GovernancePayloadSetMessageFee,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
@@ -89,7 +99,7 @@ pub struct UpgradeContract<'b> {
 impl<'b> InstructionContext<'b> for UpgradeContract<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -115,6 +125,10 @@ pub fn upgrade_contract(
     let seeds = seeds.as_slice();
     invoke_signed(&upgrade_ix, ctx.accounts, &[seeds])?;
 
+    emit(BridgeEvent::ContractUpgraded {
+        new_contract: accs.vaa.message.new_contract.to_bytes(),
+    });
+
     Ok(())
 }
 
@@ -139,7 +153,7 @@ pub struct UpgradeGuardianSet<'b> {
 impl<'b> InstructionContext<'b> for UpgradeGuardianSet<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct UpgradeGuardianSetData {}
 
 pub fn upgrade_guardian_set(
@@ -197,6 +211,66 @@ pub fn upgrade_guardian_set(
     // Set guardian set index
     accs.bridge.guardian_set_index = accs.vaa.new_guardian_set_index;
 
+    emit(BridgeEvent::GuardianSetChanged {
+        old_guardian_set_index: accs.guardian_set_old.index,
+        new_guardian_set_index: accs.guardian_set_new.index,
+        old_guardian_set_expiration_time: accs.guardian_set_old.expiration_time,
+        guardians: accs.guardian_set_new.keys.clone(),
+    });
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetGuardianSetExpiration<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Governance VAA
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetGuardianSetExpiration>,
+
+    /// Guardian set to update
+    pub guardian_set: Mut<GuardianSet<'b, { AccountState::Initialized }>>,
+}
+
+impl<'b> InstructionContext<'b> for SetGuardianSetExpiration<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct SetGuardianSetExpirationData {}
+
+pub fn set_guardian_set_expiration(
+    ctx: &ExecutionContext,
+    accs: &mut SetGuardianSetExpiration,
+    _data: SetGuardianSetExpirationData,
+) -> Result<()> {
+    // The active set never expires, and an expiration of 0 would revive a previous set forever.
+    if accs.vaa.guardian_set_index >= accs.bridge.guardian_set_index
+        || accs.vaa.expiration_time == 0
+    {
+        return Err(InvalidGuardianSetExpiration.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    accs.vaa.verify(ctx.program_id)?;
+    accs.guardian_set.verify_derivation(
+        ctx.program_id,
+        &GuardianSetDerivationData {
+            index: accs.vaa.guardian_set_index,
+        },
+    )?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    accs.guardian_set.expiration_time = accs.vaa.expiration_time;
+
+    emit(BridgeEvent::GuardianSetExpirationSet {
+        guardian_set_index: accs.guardian_set.index,
+        expiration_time: accs.guardian_set.expiration_time,
+    });
+
     Ok(())
 }
 
@@ -215,7 +289,7 @@ pub struct SetFees<'b> {
 impl<'b> InstructionContext<'b> for SetFees<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct SetFeesData {}
 
 pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData) -> Result<()> {
@@ -224,6 +298,73 @@ pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData)
     accs.vaa.claim(ctx, accs.payer.key)?;
     accs.bridge.config.fee = accs.vaa.fee.as_u64();
 
+    emit(BridgeEvent::FeesSet {
+        fee: accs.bridge.config.fee,
+    });
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetEmitterFee<'b> {
+    /// Payer for account creation (vaa-claim, emitter fee)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config, overrides may not exceed its fee
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Governance VAA
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetEmitterFee>,
+
+    /// Fee override of the emitter referenced in the VAA
+    pub emitter_fee: Mut<EmitterFee<'b, { AccountState::MaybeInitialized }>>,
+}
+
+impl<'b> InstructionContext<'b> for SetEmitterFee<'b> {
+}
+
+impl From<&SetEmitterFee<'_>> for EmitterFeeDerivationData {
+    fn from(accs: &SetEmitterFee<'_>) -> Self {
+        EmitterFeeDerivationData {
+            emitter_key: accs.vaa.emitter,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct SetEmitterFeeData {}
+
+pub fn set_emitter_fee(
+    ctx: &ExecutionContext,
+    accs: &mut SetEmitterFee,
+    _data: SetEmitterFeeData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    accs.vaa.verify(ctx.program_id)?;
+    let derivation_data: EmitterFeeDerivationData = (&*accs).into();
+    let bump = accs
+        .emitter_fee
+        .derivation_bump(ctx.program_id, &derivation_data)?;
+
+    // Emitters can leave the override out of `PostMessage`, so it can only lower the fee.
+    if accs.vaa.fee.as_u64() > accs.bridge.config.fee {
+        return Err(EmitterFeeTooHigh.into());
+    }
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    // The first override for an emitter allocates its account.
+    if !accs.emitter_fee.is_initialized() {
+        accs.emitter_fee.bump = bump;
+        accs.emitter_fee
+            .create_with_bump(&derivation_data, ctx, accs.payer.key, Exempt, bump)?;
+    }
+    accs.emitter_fee.fee = accs.vaa.fee.as_u64();
+
+    emit(BridgeEvent::EmitterFeeSet {
+        emitter: accs.vaa.emitter.to_bytes(),
+        fee: accs.emitter_fee.fee,
+    });
+
     Ok(())
 }
 
@@ -251,7 +392,7 @@ pub struct TransferFees<'b> {
 impl<'b> InstructionContext<'b> for TransferFees<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct TransferFeesData {}
 
 pub fn transfer_fees(
@@ -290,5 +431,10 @@ pub fn transfer_fees(
     let seeds = seeds.as_slice();
     invoke_signed(&transfer_ix, ctx.accounts, &[seeds])?;
 
+    emit(BridgeEvent::FeesTransferred {
+        recipient: accs.recipient.key.to_bytes(),
+        amount: accs.vaa.amount.as_u64(),
+    });
+
     Ok(())
 }
@@ -7,6 +7,10 @@ use crate::{
         GuardianSetDerivationData,
     },
     error::Error::TooManyGuardians,
+    events::{
+        emit,
+        BridgeEvent,
+    },
     MAX_LEN_GUARDIAN_KEYS,
 };
 use solana_program::sysvar::clock::Clock;
@@ -17,7 +21,7 @@ use solitaire::{
 
 type Payer<'a> = Signer<Info<'a>>;
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct Initialize<'b> {
     /// Bridge config.
     pub bridge: Mut<Bridge<'b, { AccountState::Uninitialized }>>,
@@ -38,7 +42,7 @@ pub struct Initialize<'b> {
 impl<'b> InstructionContext<'b> for Initialize<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct InitializeData {
     /// Period for how long a guardian set is valid after it has been replaced by a new one.  This
     /// guarantees that VAAs issued by that set can still be submitted for a certain period.  In
@@ -94,5 +98,12 @@ pub fn initialize(
     )?;
     accs.bridge.last_lamports = accs.fee_collector.lamports();
 
+    emit(BridgeEvent::Initialized {
+        guardian_set_index: index,
+        guardians: data.initial_guardians,
+        fee: data.fee,
+        guardian_set_expiration_time: data.guardian_set_expiration_time,
+    });
+
     Ok(())
 }
@@ -1,6 +1,10 @@
 use crate::{
     accounts::{
+        fee_with_override,
         Bridge,
+        EmitterFee,
+        EmitterFeeData,
+        EmitterFeeDerivationData,
         FeeCollector,
         PostedMessage,
         Sequence,
@@ -10,11 +14,16 @@ use crate::{
         InsufficientFees,
         MathOverflow,
     },
+    events::{
+        emit,
+        BridgeEvent,
+    },
     types::ConsistencyLevel,
     CHAIN_ID_SOLANA,
 };
 use solana_program::{
     msg,
+    pubkey::Pubkey,
     sysvar::clock::Clock,
 };
 use solitaire::{
@@ -34,7 +43,7 @@ impl<'a> From<&PostMessage<'a>> for SequenceDerivationData<'a> {
     }
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct PostMessage<'b> {
     /// Bridge config needed for fee calculation.
     pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
@@ -60,7 +69,7 @@ pub struct PostMessage<'b> {
 impl<'b> InstructionContext<'b> for PostMessage<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct PostMessageData {
     /// Unique nonce for this message
     pub nonce: u32,
@@ -81,10 +90,22 @@ pub fn post_message(
     trace!("Emitter Address: {}", accs.emitter.info().key);
     trace!("Nonce: {}", data.nonce);
 
-    accs.sequence
-        .verify_derivation(ctx.program_id, &(&*accs).into())?;
+    // Stored bumps skip the bump search, only trackers created before bumps were stored still
+    // need it.
+    let sequence_derivation: SequenceDerivationData = (&*accs).into();
+    let sequence_bump = match accs.sequence.stored_bump() {
+        Some(bump) => {
+            accs.sequence
+                .verify_derivation(ctx.program_id, &sequence_derivation)?;
+            bump
+        }
+        None => accs
+            .sequence
+            .derivation_bump(ctx.program_id, &sequence_derivation)?,
+    };
 
-    let fee = accs.bridge.config.fee;
+    let fee_override = emitter_fee_override(ctx, accs.emitter.key)?;
+    let fee = fee_with_override(&accs.bridge.config, fee_override);
     // Fee handling, checking previously known balance allows us to not care who is the payer of
     // this submission.
     if accs
@@ -107,8 +128,14 @@ pub fn post_message(
     // Init sequence tracker if it does not exist yet.
     if !accs.sequence.is_initialized() {
         trace!("Initializing Sequence account to 0.");
-        accs.sequence
-            .create(&(&*accs).into(), ctx, accs.payer.key, Exempt)?;
+        accs.sequence.bump = Some(sequence_bump);
+        accs.sequence.create_with_bump(
+            &sequence_derivation,
+            ctx,
+            accs.payer.key,
+            Exempt,
+            sequence_bump,
+        )?;
     }
 
     // DO NOT REMOVE - CRITICAL OUTPUT
@@ -138,9 +165,49 @@ pub fn post_message(
     );
     solana_program::program::invoke(&ix, ctx.accounts)?;
 
+    emit(BridgeEvent::MessagePosted {
+        message: accs.message.info().key.to_bytes(),
+        emitter: accs.emitter.key.to_bytes(),
+        sequence: accs.sequence.sequence,
+        nonce: accs.message.nonce,
+        consistency_level: accs.message.consistency_level,
+        fee,
+    });
+
     // Bump sequence number
     trace!("New Sequence: {}", accs.sequence.sequence + 1);
     accs.sequence.sequence += 1;
 
     Ok(())
 }
+
+/// Position of the optional fee override of the emitter. It follows the rent sysvar and the system
+/// program, the last accounts `PostMessage` took before overrides existed, so that account lists
+/// built for that layout are still accepted.
+pub const EMITTER_FEE_ACCOUNT_INDEX: usize = 9;
+
+/// Fee override of the emitter if the caller passed its account and governance created it.
+/// Omitting the account can only cost the caller, overrides never exceed the bridge wide fee.
+fn emitter_fee_override(ctx: &ExecutionContext, emitter: &Pubkey) -> Result<Option<u64>> {
+    let info = match ctx.accounts.get(EMITTER_FEE_ACCOUNT_INDEX) {
+        Some(info) => info,
+        None => return Ok(None),
+    };
+    if info.owner != ctx.program_id || info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let emitter_fee = EmitterFeeData::try_from_slice(&info.data.borrow())?;
+    let derived = EmitterFee::<'_, { AccountState::Initialized }>::key_with_bump(
+        &EmitterFeeDerivationData {
+            emitter_key: *emitter,
+        },
+        emitter_fee.bump,
+        ctx.program_id,
+    )?;
+    if derived != *info.key {
+        return Err(SolitaireError::InvalidDerive(*info.key, derived));
+    }
+
+    Ok(Some(emitter_fee.fee))
+}
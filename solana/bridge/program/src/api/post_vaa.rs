@@ -23,6 +23,10 @@ use crate::{
         PostVAAConsensusFailed,
         PostVAAGuardianSetExpired,
     },
+    events::{
+        emit,
+        BridgeEvent,
+    },
 };
 use byteorder::{
     BigEndian,
@@ -51,7 +55,7 @@ impl From<&PostVAAData> for GuardianSetDerivationData {
     }
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct PostVAA<'b> {
     /// Information about the current guardian set.
     pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
@@ -85,7 +89,7 @@ pub struct Signature {
 
 pub type ForeignAddress = [u8; 32];
 
-#[derive(Default, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(Default, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize, BorshSchema)]
 pub struct PostVAAData {
     // Header part
     pub version: u8,
@@ -152,6 +156,15 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     accs.message
         .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
 
+    emit(BridgeEvent::VAAPosted {
+        message: accs.message.info().key.to_bytes(),
+        signature_set: accs.signature_set.info().key.to_bytes(),
+        emitter_chain: accs.message.emitter_chain,
+        emitter_address: accs.message.emitter_address,
+        sequence: accs.message.sequence,
+        guardian_set_index: accs.guardian_set.index,
+    });
+
     Ok(())
 }
 
@@ -7,6 +7,10 @@ use crate::{
         InvalidHash,
         InvalidSecpInstruction,
     },
+    events::{
+        emit,
+        BridgeEvent,
+    },
     GuardianSet,
     GuardianSetDerivationData,
     SignatureSet,
@@ -19,7 +23,7 @@ use solitaire::{
     CreationLamports::Exempt,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct VerifySignatures<'b> {
     /// Payer for account creation
     pub payer: Mut<Signer<Info<'b>>>,
@@ -45,7 +49,7 @@ impl From<&VerifySignatures<'_>> for GuardianSetDerivationData {
     }
 }
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct VerifySignaturesData {
     /// instruction indices of signers (-1 for missing)
     pub signers: [i8; MAX_LEN_GUARDIAN_KEYS],
@@ -196,6 +200,7 @@ pub fn verify_signatures(
     }
 
     // Write sigs of checked addresses into sig_state
+    let mut guardian_indices = Vec::with_capacity(sig_infos.len());
     for s in sig_infos {
         if s.signer_index > accs.guardian_set.num_guardians() {
             return Err(ProgramError::InvalidArgument.into());
@@ -213,7 +218,40 @@ pub fn verify_signatures(
 
         // Overwritten content should be zeros except double signs by the signer or harmless replays
         accs.signature_set.signatures[s.signer_index as usize] = true;
+        guardian_indices.push(s.signer_index);
     }
 
+    emit(BridgeEvent::SignaturesVerified {
+        signature_set: accs.signature_set.info().key.to_bytes(),
+        hash: accs.signature_set.hash,
+        guardian_set_index: accs.signature_set.guardian_set_index,
+        guardian_indices,
+    });
+
     Ok(())
 }
+
+/// Reclaim the rent of a signature set once it is no longer needed, usually after its VAA was
+/// posted. Only the keypair that created the set can close it, so the rent cannot be taken by
+/// whoever posts the VAA.
+#[derive(FromAccounts, ToInstruction)]
+pub struct CloseSignatureSet<'b> {
+    pub signature_set: Mut<Signer<SignatureSet<'b, { AccountState::Initialized }>>>,
+
+    /// Receives the lamports of the signature set.
+    pub recipient: Mut<Info<'b>>,
+}
+
+impl<'b> InstructionContext<'b> for CloseSignatureSet<'b> {
+}
+
+#[derive(Default, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct CloseSignatureSetData {}
+
+pub fn close_signature_set(
+    ctx: &ExecutionContext,
+    accs: &mut CloseSignatureSet,
+    _data: CloseSignatureSetData,
+) -> Result<()> {
+    accs.signature_set.close(ctx, accs.recipient.info())
+}
@@ -1,35 +1,28 @@
 //! Define application level errors that can be returned by the various instruction handlers that
 //! make up the wormhole bridge.
 
-use crate::trace;
-use solitaire::SolitaireError;
+use solitaire::program_errors;
 
-#[derive(Debug)]
-pub enum Error {
-    GuardianSetMismatch,
-    InstructionAtWrongIndex,
-    InsufficientFees,
-    InvalidFeeRecipient,
-    InvalidGovernanceAction,
-    InvalidGovernanceChain,
-    InvalidGovernanceKey,
-    InvalidGovernanceModule,
-    InvalidGovernanceWithdrawal,
-    InvalidGuardianSetUpgrade,
-    InvalidHash,
-    InvalidSecpInstruction,
-    MathOverflow,
-    PostVAAConsensusFailed,
-    PostVAAGuardianSetExpired,
-    TooManyGuardians,
-    VAAAlreadyExecuted,
-}
-
-/// Errors thrown by the program will bubble up to the solitaire wrapper, which needs a way to
-/// translate these errors into something Solitaire can log and handle.
-impl From<Error> for SolitaireError {
-    fn from(e: Error) -> SolitaireError {
-        trace!("ProgramError: {:?}", e);
-        SolitaireError::Custom(e as u64)
+program_errors! {
+    pub enum Error: 0x1000 {
+        GuardianSetMismatch = 0,
+        InstructionAtWrongIndex = 1,
+        InsufficientFees = 2,
+        InvalidFeeRecipient = 3,
+        InvalidGovernanceAction = 4,
+        InvalidGovernanceChain = 5,
+        InvalidGovernanceKey = 6,
+        InvalidGovernanceModule = 7,
+        InvalidGovernanceWithdrawal = 8,
+        InvalidGuardianSetExpiration = 9,
+        InvalidGuardianSetUpgrade = 10,
+        InvalidHash = 11,
+        InvalidSecpInstruction = 12,
+        MathOverflow = 13,
+        PostVAAConsensusFailed = 14,
+        PostVAAGuardianSetExpired = 15,
+        TooManyGuardians = 16,
+        VAAAlreadyExecuted = 17,
+        EmitterFeeTooHigh = 18,
     }
 }
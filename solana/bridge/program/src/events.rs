@@ -0,0 +1,10 @@
+//! Structured events logged by state changing instructions. The event types and the decoder used
+//! by indexers live in the `wormhole-bridge-events` crate.
+
+pub use bridge_events::*;
+use solana_program::msg;
+
+/// Log an event in the format expected by `bridge_events::parse_logs`.
+pub fn emit(event: BridgeEvent) {
+    msg!("{}{}", EVENT_PREFIX, event.encode());
+}
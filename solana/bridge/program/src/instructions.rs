@@ -4,12 +4,15 @@ use solana_program::{
         AccountMeta,
         Instruction,
     },
+    message::Message,
+    program_error::ProgramError,
     pubkey::Pubkey,
     sysvar,
 };
 
 use byteorder::{
     BigEndian,
+    LittleEndian,
     WriteBytesExt,
 };
 use sha3::Digest;
@@ -27,6 +30,8 @@ use crate::{
         Bridge,
         Claim,
         ClaimDerivationData,
+        EmitterFee,
+        EmitterFeeDerivationData,
         FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
@@ -36,17 +41,34 @@ use crate::{
         SequenceDerivationData,
     },
     types::ConsistencyLevel,
+    vaa::VAA,
+    CloseSignatureSetData,
     InitializeData,
     PostMessageData,
     PostVAAData,
+    SetEmitterFeeData,
     SetFeesData,
+    SetGuardianSetExpirationData,
     TransferFeesData,
     UpgradeContractData,
     UpgradeGuardianSetData,
     VerifySignaturesData,
     CHAIN_ID_SOLANA,
+    MAX_LEN_GUARDIAN_KEYS,
 };
 
+/// Maximum size of a serialized transaction, mirrors `solana_sdk::packet::PACKET_DATA_SIZE`.
+pub const PACKET_DATA_SIZE: usize = 1280 - 40 - 8;
+
+/// Size of a single signature offsets entry in the secp256k1 program instruction data.
+const SECP_SIGNATURE_OFFSETS_LEN: usize = 11;
+
+/// Size of a recoverable secp256k1 signature (r, s, recovery id).
+const SECP_SIGNATURE_LEN: usize = 65;
+
+/// Size of an Ethereum style guardian address.
+const ETH_ADDRESS_LEN: usize = 20;
+
 pub fn initialize(
     program_id: Pubkey,
     payer: Pubkey,
@@ -101,6 +123,12 @@ pub fn post_message(
         },
         &program_id,
     );
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: emitter,
+        },
+        &program_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -115,6 +143,7 @@ pub fn post_message(
             AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(emitter_fee, false),
         ],
 
         data: (
@@ -159,6 +188,27 @@ pub fn verify_signatures(
     })
 }
 
+pub fn close_signature_set(
+    program_id: Pubkey,
+    signature_set: Pubkey,
+    recipient: Pubkey,
+) -> solitaire::Result<Instruction> {
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(signature_set, true),
+            AccountMeta::new(recipient, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseSignatureSet,
+            CloseSignatureSetData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
 pub fn post_vaa(
     program_id: Pubkey,
     payer: Pubkey,
@@ -339,6 +389,97 @@ pub fn set_fees(
     }
 }
 
+pub fn set_emitter_fee(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    fee_emitter: Pubkey,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_SOLANA,
+            sequence,
+        },
+        &program_id,
+    );
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: fee_emitter,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(emitter_fee, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetEmitterFee,
+            SetEmitterFeeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_guardian_set_expiration(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    guardian_set_index: u32,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_SOLANA,
+            sequence,
+        },
+        &program_id,
+    );
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(guardian_set, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGuardianSetExpiration,
+            SetGuardianSetExpirationData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 pub fn transfer_fees(
     program_id: Pubkey,
     payer: Pubkey,
@@ -403,3 +544,163 @@ pub fn hash_vaa(vaa: &PostVAAData) -> [u8; 32] {
     h.write(body.as_slice()).unwrap();
     h.finalize().into()
 }
+
+// Build a secp256k1 program instruction that checks each (address, signature) pair against the
+// same message. All offsets point into the instruction's own data, so the instruction has to be
+// placed at `instruction_index` within its transaction.
+pub fn secp256k1_instruction(
+    signatures: &[([u8; 20], [u8; 65])],
+    message: &[u8],
+    instruction_index: u8,
+) -> Instruction {
+    let data_start = 1 + signatures.len() * SECP_SIGNATURE_OFFSETS_LEN;
+    let message_offset = data_start + signatures.len() * (ETH_ADDRESS_LEN + SECP_SIGNATURE_LEN);
+
+    let mut offsets = Cursor::new(Vec::new());
+    let mut payload = Vec::new();
+    for (address, signature) in signatures {
+        let address_offset = data_start + payload.len();
+        payload.extend_from_slice(address);
+        let signature_offset = data_start + payload.len();
+        payload.extend_from_slice(signature);
+
+        offsets
+            .write_u16::<LittleEndian>(signature_offset as u16)
+            .unwrap();
+        offsets.write_u8(instruction_index).unwrap();
+        offsets
+            .write_u16::<LittleEndian>(address_offset as u16)
+            .unwrap();
+        offsets.write_u8(instruction_index).unwrap();
+        offsets
+            .write_u16::<LittleEndian>(message_offset as u16)
+            .unwrap();
+        offsets
+            .write_u16::<LittleEndian>(message.len() as u16)
+            .unwrap();
+        offsets.write_u8(instruction_index).unwrap();
+    }
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.push(signatures.len() as u8);
+    data.extend(offsets.into_inner());
+    data.extend(payload);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: solana_program::secp256k1_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+// Serialized size of a transaction verifying `count` signatures, a transaction is encoded as a
+// compact array of signatures followed by the message.
+fn verify_signatures_tx_size(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    guardian_set_index: u32,
+    count: usize,
+) -> solitaire::Result<usize> {
+    let signatures = vec![([0u8; 20], [0u8; 65]); count];
+    let instructions = [
+        secp256k1_instruction(&signatures, &[0u8; 32], 0),
+        verify_signatures(
+            program_id,
+            payer,
+            guardian_set_index,
+            signature_set,
+            VerifySignaturesData::default(),
+        )?,
+    ];
+
+    let message = Message::new(&instructions, Some(&payer));
+    let signers = message.header.num_required_signatures as usize;
+    Ok(1 + signers * 64 + message.serialize().len())
+}
+
+/// Maximum number of guardian signatures that can be verified by a single transaction consisting
+/// of a secp256k1 instruction followed by `VerifySignatures`.
+pub fn max_signatures_per_transaction(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    guardian_set_index: u32,
+) -> solitaire::Result<usize> {
+    let mut count = 0;
+    while count < MAX_LEN_GUARDIAN_KEYS
+        && verify_signatures_tx_size(
+            program_id,
+            payer,
+            signature_set,
+            guardian_set_index,
+            count + 1,
+        )? <= PACKET_DATA_SIZE
+    {
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Plan the transactions required to verify all guardian signatures of a VAA. Each entry of the
+/// result is the instruction list of one transaction, packing as many signatures as the size
+/// limit allows, and must be signed by both `payer` and `signature_set`. Once all of them have
+/// been executed, `post_vaa` can be submitted with the same `signature_set`.
+pub fn verify_signatures_plan(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    guardian_keys: &[[u8; 20]],
+    vaa: &VAA,
+) -> solitaire::Result<Vec<Vec<Instruction>>> {
+    let body_hash = hash_vaa(&vaa.clone().into());
+
+    // Resolve each signature to the guardian key it claims to be from.
+    let mut signatures = Vec::with_capacity(vaa.signatures.len());
+    for signature in vaa.signatures.iter() {
+        let index = signature.guardian_index as usize;
+        if index >= guardian_keys.len()
+            || index >= MAX_LEN_GUARDIAN_KEYS
+            || signature.signature.len() != SECP_SIGNATURE_LEN
+        {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+        let mut sig = [0u8; SECP_SIGNATURE_LEN];
+        sig.copy_from_slice(&signature.signature);
+        signatures.push((index, guardian_keys[index], sig));
+    }
+
+    let per_tx =
+        max_signatures_per_transaction(program_id, payer, signature_set, vaa.guardian_set_index)?;
+    if per_tx == 0 {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
+    let mut plan = Vec::new();
+    for chunk in signatures.chunks(per_tx) {
+        // Map guardian indices to their position within the secp256k1 instruction.
+        let mut signers = [-1i8; MAX_LEN_GUARDIAN_KEYS];
+        for (position, (index, _, _)) in chunk.iter().enumerate() {
+            signers[*index] = position as i8;
+        }
+
+        let pairs: Vec<([u8; 20], [u8; 65])> = chunk
+            .iter()
+            .map(|(_, address, signature)| (*address, *signature))
+            .collect();
+
+        plan.push(vec![
+            secp256k1_instruction(&pairs, &body_hash, 0),
+            verify_signatures(
+                program_id,
+                payer,
+                vaa.guardian_set_index,
+                signature_set,
+                VerifySignaturesData { signers },
+            )?,
+        ]);
+    }
+
+    Ok(plan)
+}
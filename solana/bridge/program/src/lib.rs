@@ -26,6 +26,9 @@ pub use accounts::{
     Claim,
     ClaimData,
     ClaimDerivationData, 
+    EmitterFee,
+    EmitterFeeData,
+    EmitterFeeDerivationData,
     FeeCollector,
     GuardianSet,
     GuardianSetData,
@@ -44,22 +47,31 @@ pub use accounts::{
 
 pub mod api;
 pub use api::{
+    close_signature_set,
     initialize,
     post_message,
     post_vaa,
+    set_emitter_fee,
     set_fees,
+    set_guardian_set_expiration,
     transfer_fees,
     upgrade_contract,
     upgrade_guardian_set,
     verify_signatures,
+    CloseSignatureSet,
+    CloseSignatureSetData,
     Initialize,
     InitializeData,
     PostMessage,
     PostMessageData,
     PostVAA,
     PostVAAData,
+    SetEmitterFee,
+    SetEmitterFeeData,
     SetFees,
     SetFeesData,
+    SetGuardianSetExpiration,
+    SetGuardianSetExpirationData,
     Signature,
     TransferFees,
     TransferFeesData,
@@ -72,7 +84,17 @@ pub use api::{
     VerifySignaturesData,
 };
 
+// Client side account structs generated by the ToInstruction derive.
+#[cfg(feature = "client")]
+pub use api::{
+    InitializeAccounts,
+    PostMessageAccounts,
+    PostVAAAccounts,
+    VerifySignaturesAccounts,
+};
+
 pub mod error;
+pub mod events;
 pub mod types;
 pub mod vaa;
 
@@ -85,12 +107,15 @@ pub use vaa::{
 };
 
 solitaire! {
-    Initialize(InitializeData)                  => initialize,
-    PostMessage(PostMessageData)                => post_message,
-    PostVAA(PostVAAData)                        => post_vaa,
-    SetFees(SetFeesData)                        => set_fees,
-    TransferFees(TransferFeesData)              => transfer_fees,
-    UpgradeContract(UpgradeContractData)        => upgrade_contract,
-    UpgradeGuardianSet(UpgradeGuardianSetData)  => upgrade_guardian_set,
-    VerifySignatures(VerifySignaturesData)      => verify_signatures,
+    Initialize(InitializeData)                             = 0 => initialize,
+    PostMessage(PostMessageData)                           = 1 => post_message,
+    PostVAA(PostVAAData)                                   = 2 => post_vaa,
+    SetFees(SetFeesData)                                   = 3 => set_fees,
+    TransferFees(TransferFeesData)                         = 4 => transfer_fees,
+    UpgradeContract(UpgradeContractData)                   = 5 => upgrade_contract,
+    UpgradeGuardianSet(UpgradeGuardianSetData)             = 6 => upgrade_guardian_set,
+    VerifySignatures(VerifySignaturesData)                 = 7 => verify_signatures,
+    SetEmitterFee(SetEmitterFeeData)                       = 8 => set_emitter_fee,
+    SetGuardianSetExpiration(SetGuardianSetExpirationData) = 9 => set_guardian_set_expiration,
+    CloseSignatureSet(CloseSignatureSetData)               = 10 => close_signature_set,
 }
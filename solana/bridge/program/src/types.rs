@@ -9,6 +9,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use byteorder::{
@@ -38,7 +39,7 @@ use std::{
 pub type GuardianPublicKey = [u8; 20];
 
 #[repr(u8)]
-#[derive(BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(BorshSerialize, BorshDeserialize, BorshSchema, Clone, Serialize, Deserialize)]
 pub enum ConsistencyLevel {
     Confirmed,
     Finalized,
@@ -233,3 +234,104 @@ impl SerializeGovernancePayload for GovernancePayloadTransferFees {
 
 impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
 }
+
+pub struct GovernancePayloadSetEmitterFee {
+    // Emitter the fee applies to
+    pub emitter: Pubkey,
+
+    // New fee in lamports
+    pub fee: U256,
+}
+
+impl SerializePayload for GovernancePayloadSetEmitterFee {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        let mut fee_data = [0u8; 32];
+        self.fee.to_big_endian(&mut fee_data);
+        v.write(&self.emitter.to_bytes())?;
+        v.write(&fee_data[..])?;
+
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetEmitterFee
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut emitter = [0u8; 32];
+        c.read_exact(&mut emitter)?;
+
+        let mut fee_data: [u8; 32] = [0; 32];
+        c.read_exact(&mut fee_data)?;
+        let fee = U256::from_big_endian(&fee_data);
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetEmitterFee {
+            emitter: Pubkey::new(&emitter[..]),
+            fee,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetEmitterFee {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 5;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetEmitterFee {
+}
+
+pub struct GovernancePayloadSetGuardianSetExpiration {
+    // Index of the guardian set to update
+    pub guardian_set_index: u32,
+
+    // Unix timestamp after which the guardian set is no longer accepted
+    pub expiration_time: u32,
+}
+
+impl SerializePayload for GovernancePayloadSetGuardianSetExpiration {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u32::<BigEndian>(self.guardian_set_index)?;
+        v.write_u32::<BigEndian>(self.expiration_time)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGuardianSetExpiration
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let guardian_set_index = c.read_u32::<BigEndian>()?;
+        let expiration_time = c.read_u32::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetGuardianSetExpiration {
+            guardian_set_index,
+            expiration_time,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetGuardianSetExpiration {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetGuardianSetExpiration {
+}
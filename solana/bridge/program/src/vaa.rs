@@ -25,6 +25,7 @@ use serde::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    idl::IdlAccount,
     processors::seeded::Seeded,
     trace,
     Context,
@@ -127,6 +128,10 @@ impl<'a, 'b: 'a, 'c, T: DeserializePayload> Peel<'a, 'b, 'c> for PayloadMessage<
         Data::<'b, PostedVAAData, { AccountState::Initialized }>::deps()
     }
 
+    fn describe(account: IdlAccount) -> Vec<IdlAccount> {
+        Data::<'b, PostedVAAData, { AccountState::Initialized }>::describe(account)
+    }
+
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         Data::persist(&self.0, program_id)
     }
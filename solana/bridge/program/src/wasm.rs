@@ -34,7 +34,9 @@ use crate::{
         hash_vaa,
         post_message,
         post_vaa,
+        set_emitter_fee,
         set_fees,
+        set_guardian_set_expiration,
         transfer_fees,
         upgrade_contract,
         upgrade_guardian_set,
@@ -43,6 +45,8 @@ use crate::{
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetEmitterFee,
+        GovernancePayloadSetGuardianSetExpiration,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
     },
@@ -154,6 +158,53 @@ pub fn set_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_emitter_fee_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadSetEmitterFee::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_emitter_fee(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        payload.emitter,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_guardian_set_expiration_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadSetGuardianSetExpiration::deserialize(&mut vaa.payload.as_slice())
+            .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_guardian_set_expiration(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        payload.guardian_set_index,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn transfer_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -321,7 +372,7 @@ pub fn guardian_set_address(bridge: String, index: u32) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_guardian_set(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&GuardianSetData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&GuardianSetData::deserialize(&mut data.as_slice()).unwrap()).unwrap()
 }
 
 #[wasm_bindgen]
@@ -76,6 +76,10 @@ use bridge::{
     instruction,
     instructions,
     types::ConsistencyLevel,
+    vaa::{
+        VAASignature,
+        VAA,
+    },
     Initialize,
     InitializeData,
     PostMessageData,
@@ -150,7 +154,7 @@ mod helpers {
     /// Fetch account data, the loop is there to re-attempt until data is available.
     pub fn get_account_data<T: BorshDeserialize>(client: &RpcClient, account: &Pubkey) -> T {
         let account = client.get_account(account).unwrap();
-        T::try_from_slice(&account.data).unwrap()
+        T::deserialize(&mut account.data.as_slice()).unwrap()
     }
 
     /// Generate `count` secp256k1 private keys, along with their ethereum-styled public key
@@ -238,6 +242,38 @@ mod helpers {
         (vaa, body, body_hash)
     }
 
+    /// Sign a VAA body hash with each guardian key, producing the VAA as guardians would publish
+    /// it on the network.
+    pub fn sign_vaa(vaa: &PostVAAData, body_hash: [u8; 32], secret_keys: &[SecretKey]) -> VAA {
+        let message = Secp256k1Message::parse(&body_hash);
+        let signatures = secret_keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let (signature, recovery_id) = secp256k1::sign(&message, key).unwrap();
+                let mut signature = signature.serialize().to_vec();
+                signature.push(recovery_id.serialize());
+                VAASignature {
+                    signature,
+                    guardian_index: i as u8,
+                }
+            })
+            .collect();
+
+        VAA {
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            signatures,
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload.clone(),
+        }
+    }
+
     pub fn transfer(
         client: &RpcClient,
         from: &Keypair,
@@ -353,6 +389,37 @@ mod helpers {
         Ok(signature_set.pubkey())
     }
 
+    /// Verify all signatures of a VAA using the batched transactions planned by
+    /// `instructions::verify_signatures_plan`.
+    pub fn verify_signatures_planned(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        guardian_keys: &[[u8; 20]],
+        vaa: &VAA,
+    ) -> Result<(Pubkey, usize), ClientError> {
+        let signature_set = Keypair::new();
+        let plan = instructions::verify_signatures_plan(
+            *program,
+            payer.pubkey(),
+            signature_set.pubkey(),
+            guardian_keys,
+            vaa,
+        )
+        .unwrap();
+
+        for instructions in plan.iter() {
+            execute(
+                client,
+                payer,
+                &[payer, &signature_set],
+                instructions,
+                CommitmentConfig::processed(),
+            )?;
+        }
+        Ok((signature_set.pubkey(), plan.len()))
+    }
+
     pub fn post_vaa(
         client: &RpcClient,
         program: &Pubkey,
@@ -451,6 +518,56 @@ mod helpers {
         )
     }
 
+    pub fn set_emitter_fee(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        fee_emitter: Pubkey,
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_emitter_fee(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                fee_emitter,
+            )],
+            CommitmentConfig::processed(),
+        )
+    }
+
+    pub fn set_guardian_set_expiration(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        guardian_set_index: u32,
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_guardian_set_expiration(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                guardian_set_index,
+            )],
+            CommitmentConfig::processed(),
+        )
+    }
+
     pub fn transfer_fees(
         client: &RpcClient,
         program: &Pubkey,
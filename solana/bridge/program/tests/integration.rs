@@ -42,6 +42,10 @@ use solitaire::{
     processors::seeded::Seeded,
     AccountState,
 };
+use solitaire_client::{
+    decode_transaction_error,
+    DecodedError,
+};
 use std::{
     convert::TryInto,
     io::{
@@ -59,6 +63,9 @@ use bridge::{
         Bridge,
         BridgeConfig,
         BridgeData,
+        EmitterFee,
+        EmitterFeeData,
+        EmitterFeeDerivationData,
         FeeCollector,
         GuardianSet,
         GuardianSetData,
@@ -67,15 +74,20 @@ use bridge::{
         PostedVAA,
         PostedVAAData,
         PostedVAADerivationData,
+        Sequence,
+        SequenceDerivationData,
         SequenceTracker,
         SignatureSet,
         SignatureSetData,
     },
+    error::Error as BridgeError,
     instruction,
     instructions::hash_vaa,
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetEmitterFee,
+        GovernancePayloadSetGuardianSetExpiration,
         GovernancePayloadSetMessageFee,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
@@ -140,12 +152,16 @@ fn run_integration_tests() {
     // integration tests so for now we work around it by simply chain-calling our tests.
     test_bridge_messages(&mut context);
     test_foreign_bridge_messages(&mut context);
+    test_planned_signature_verification(&mut context);
     test_invalid_emitter(&mut context);
     test_duplicate_messages_fail(&mut context);
     test_guardian_set_change(&mut context);
     test_guardian_set_change_fails(&mut context);
+    test_set_guardian_set_expiration(&mut context);
     test_set_fees(&mut context);
     test_set_fees_fails(&mut context);
+    test_set_emitter_fee(&mut context);
+    test_set_emitter_fee_fails(&mut context);
     test_free_fees(&mut context);
     test_transfer_fees(&mut context);
     test_transfer_fees_fails(&mut context);
@@ -244,6 +260,17 @@ fn test_bridge_messages(context: &mut Context) {
         }
     }
 
+    // The tracker created by the first message stores its canonical bump.
+    let (sequence_key, sequence_bump) = Sequence::key_and_bump(
+        &SequenceDerivationData {
+            emitter_key: &emitter.pubkey(),
+        },
+        &program,
+    );
+    let tracker: SequenceTracker = common::get_account_data(client, &sequence_key);
+    assert_eq!(tracker.sequence, 2);
+    assert_eq!(tracker.bump, Some(sequence_bump));
+
     // Prepare another message with no data in its message to confirm it succeeds.
     let nonce = rand::thread_rng().gen();
     let message = b"".to_vec();
@@ -535,6 +562,80 @@ fn test_guardian_set_change_fails(context: &mut Context) {
     .is_err());
 }
 
+fn test_set_guardian_set_expiration(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as u32;
+
+    // Retire the previous guardian set right away.
+    for (guardian_set_index, expiration_time, succeeds) in [
+        (0, now - 10, true),
+        // The active set can not be expired.
+        (1, now - 10, false),
+        // An expiration of 0 would revive the previous set.
+        (0, 0, false),
+    ] {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadSetGuardianSetExpiration {
+            guardian_set_index,
+            expiration_time,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let message_key = common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .unwrap();
+
+        let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+        let result = common::set_guardian_set_expiration(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            sequence,
+            guardian_set_index,
+        );
+        assert_eq!(result.is_ok(), succeeds);
+        common::sync(client, payer);
+    }
+
+    // Verify only the previous guardian set was changed.
+    let old_guardian_set: GuardianSetData = common::get_account_data(
+        client,
+        &GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+            &GuardianSetDerivationData { index: 0 },
+            &program,
+        ),
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(
+        client,
+        &GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+            &GuardianSetDerivationData { index: 1 },
+            &program,
+        ),
+    );
+    assert_eq!(old_guardian_set.expiration_time, now - 10);
+    assert_eq!(guardian_set.expiration_time, 0);
+}
+
 fn test_set_fees(context: &mut Context) {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref payer, ref client, ref program) = common::setup();
@@ -678,7 +779,7 @@ fn test_set_fees_fails(context: &mut Context) {
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
-    assert!(common::set_fees(
+    let error = common::set_fees(
         client,
         program,
         payer,
@@ -686,8 +787,72 @@ fn test_set_fees_fails(context: &mut Context) {
         emitter.pubkey(),
         sequence,
     )
-    .is_err());
+    .unwrap_err();
+    common::sync(client, payer);
+
+    // The failure decodes back into the named bridge error.
+    assert_eq!(
+        decode_transaction_error(&error.get_transaction_error().unwrap()),
+        Some((0, DecodedError::Program(BridgeError::InvalidGovernanceKey)))
+    );
+}
+
+fn test_set_emitter_fee_fails(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Overrides above the bridge wide fee of 100 could be avoided by leaving them out.
+    let fee_emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetEmitterFee {
+        emitter: fee_emitter.pubkey(),
+        fee: U256::from(200),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        100,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    let error = common::set_emitter_fee(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        fee_emitter.pubkey(),
+    )
+    .unwrap_err();
     common::sync(client, payer);
+
+    assert_eq!(
+        decode_transaction_error(&error.get_transaction_error().unwrap()),
+        Some((0, DecodedError::Program(BridgeError::EmitterFeeTooHigh)))
+    );
+
+    // No override was created.
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: fee_emitter.pubkey(),
+        },
+        &program,
+    );
+    assert!(client.get_account(&emitter_fee_key).is_err());
 }
 
 fn test_free_fees(context: &mut Context) {
@@ -780,6 +945,166 @@ fn test_free_fees(context: &mut Context) {
     }
 }
 
+fn test_set_emitter_fee(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Charge a single emitter less than the bridge wide fee of 100 set by `test_set_fees`.
+    let fee_emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetEmitterFee {
+        emitter: fee_emitter.pubkey(),
+        fee: U256::from(20),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        100,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::set_emitter_fee(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        fee_emitter.pubkey(),
+    )
+    .unwrap();
+    common::sync(client, payer);
+
+    // Fetch the override to check on-state value.
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: fee_emitter.pubkey(),
+        },
+        &program,
+    );
+    let (_, emitter_fee_bump) = EmitterFee::<'_, { AccountState::Uninitialized }>::key_and_bump(
+        &EmitterFeeDerivationData {
+            emitter_key: fee_emitter.pubkey(),
+        },
+        &program,
+    );
+    let emitter_fee: EmitterFeeData = common::get_account_data(client, &emitter_fee_key);
+    assert_eq!(emitter_fee.fee, 20);
+    assert_eq!(emitter_fee.bump, emitter_fee_bump);
+
+    // Posting below the override fails.
+    let fee_collector = FeeCollector::key(None, &program);
+    let account_balance = client.get_account(&fee_collector).unwrap().lamports;
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    assert!(common::post_message(
+        client,
+        program,
+        payer,
+        &fee_emitter,
+        nonce,
+        message.clone(),
+        10
+    )
+    .is_err());
+    common::sync(client, payer);
+
+    assert_eq!(
+        client.get_account(&fee_collector).unwrap().lamports,
+        account_balance,
+    );
+
+    // Account lists without the override, as built before overrides existed, pay the bridge wide
+    // fee.
+    let msg_account = Keypair::new();
+    let mut instruction = bridge::instructions::post_message(
+        *program,
+        payer.pubkey(),
+        fee_emitter.pubkey(),
+        msg_account.pubkey(),
+        nonce,
+        message.clone(),
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    instruction.accounts.truncate(9);
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer, &fee_emitter, &msg_account],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 20),
+            instruction,
+        ],
+        solana_sdk::commitment_config::CommitmentConfig::processed(),
+    )
+    .is_err());
+    common::sync(client, payer);
+
+    assert_eq!(
+        client.get_account(&fee_collector).unwrap().lamports,
+        account_balance,
+    );
+
+    // And succeeds when paying the override.
+    context.seq.next(fee_emitter.pubkey().to_bytes());
+    common::post_message(
+        client,
+        program,
+        payer,
+        &fee_emitter,
+        nonce,
+        message.clone(),
+        20,
+    )
+    .unwrap();
+    common::sync(client, payer);
+
+    assert_eq!(
+        client.get_account(&fee_collector).unwrap().lamports,
+        account_balance + 20,
+    );
+
+    // Other emitters keep paying the bridge wide fee.
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    assert!(
+        common::post_message(client, program, payer, &emitter, nonce, message.clone(), 20).is_err()
+    );
+    common::sync(client, payer);
+
+    context.seq.next(emitter.pubkey().to_bytes());
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        100,
+    )
+    .unwrap();
+    common::sync(client, payer);
+
+    assert_eq!(
+        client.get_account(&fee_collector).unwrap().lamports,
+        account_balance + 120,
+    );
+}
+
 fn test_transfer_fees(context: &mut Context) {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref payer, ref client, ref program) = common::setup();
@@ -972,6 +1297,51 @@ fn test_foreign_bridge_messages(context: &mut Context) {
     }
 }
 
+fn test_planned_signature_verification(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Sign the VAA the way guardians do, so it can be fed to the planner as received off-chain.
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 2);
+    let signed_vaa = common::sign_vaa(&vaa, body_hash, &context.secret);
+
+    // All guardians of the current set fit into a single transaction.
+    let per_tx = bridge::instructions::max_signatures_per_transaction(
+        *program,
+        payer.pubkey(),
+        Keypair::new().pubkey(),
+        0,
+    )
+    .unwrap();
+    assert!(per_tx >= context.secret.len());
+
+    let (signature_set, batches) =
+        common::verify_signatures_planned(client, program, payer, &context.public, &signed_vaa)
+            .unwrap();
+    assert_eq!(batches, 1);
+
+    common::post_vaa(client, program, payer, signature_set, vaa.clone()).unwrap();
+    common::sync(client, payer);
+
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+    let posted_message: PostedVAAData = common::get_account_data(client, &message_key);
+    let signatures: SignatureSetData = common::get_account_data(client, &signature_set);
+
+    assert_eq!(posted_message.0.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.0.sequence, sequence);
+    assert_eq!(posted_message.0.payload, message);
+    assert_eq!(signatures.hash, body);
+    assert!(signatures.signatures.iter().all(|v| *v));
+}
+
 fn test_transfer_total_fails(context: &mut Context) {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref payer, ref client, ref program) = common::setup();
@@ -23,7 +23,7 @@ pub use api::{
 use bridge::PostVAAData;
 
 solitaire! {
-    Initialize(InitializeData)                  => initialize,
-    PostMessage(PostMessageData)                => post_message,
-    PostVAA(PostVAAData)                        => post_vaa,
+    Initialize(InitializeData)   = 0 => initialize,
+    PostMessage(PostMessageData) = 1 => post_message,
+    PostVAA(PostVAAData)         = 2 => post_vaa,
 }
@@ -5,6 +5,7 @@ use crate::types::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    idl::IdlSeed,
     processors::seeded::Seeded,
     AccountState,
     Data,
@@ -25,6 +26,10 @@ impl<'b, const STATE: AccountState> Seeded<&ShareMintDerivationData> for ShareMi
             accs.pool.to_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("share_mint"), IdlSeed::Arg("pool")]
+    }
 }
 
 pub type FromCustodyTokenAccount<'a, const STATE: AccountState> = Data<'a, SplAccount, { STATE }>;
@@ -42,6 +47,10 @@ impl<'b, const STATE: AccountState> Seeded<&FromCustodyTokenAccountDerivationDat
             accs.pool.to_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("from_custody"), IdlSeed::Arg("pool")]
+    }
 }
 
 pub type ToCustodyTokenAccount<'a, const STATE: AccountState> = Data<'a, SplAccount, { STATE }>;
@@ -59,6 +68,10 @@ impl<'b, const STATE: AccountState> Seeded<&ToCustodyTokenAccountDerivationData>
             accs.pool.to_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("to_custody"), IdlSeed::Arg("pool")]
+    }
 }
 
 pub type MigrationPool<'a, const STATE: AccountState> = Data<'a, PoolData, { STATE }>;
@@ -78,6 +91,14 @@ impl<'b, const STATE: AccountState> Seeded<&MigrationPoolDerivationData>
             accs.to.to_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("pool"),
+            IdlSeed::Arg("from"),
+            IdlSeed::Arg("to"),
+        ]
+    }
 }
 
 pub type CustodySigner<'a> = Derive<Info<'a>, "custody_signer">;
@@ -30,13 +30,19 @@ use solitaire::{
 
 #[derive(FromAccounts)]
 pub struct MigrateTokens<'b> {
+    #[has_one(from = from_mint, to = to_mint, error = WrongMint)]
+    #[derivation(&MigrationPoolDerivationData { from: pool.from, to: pool.to })]
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
     pub to_mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    #[derivation(&ToCustodyTokenAccountDerivationData { pool: *pool.info().key })]
     pub to_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Initialized }>>,
+    #[derivation(&FromCustodyTokenAccountDerivationData { pool: *pool.info().key })]
     pub from_token_custody: Mut<FromCustodyTokenAccount<'b, { AccountState::Initialized }>>,
 
+    #[token(mint = from_mint, error = WrongMint)]
     pub user_from_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[token(mint = to_mint, error = WrongMint)]
     pub user_to_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub custody_signer: CustodySigner<'b>,
     pub authority_signer: AuthoritySigner<'b>,
@@ -52,38 +58,6 @@ pub fn migrate_tokens(
     accs: &mut MigrateTokens,
     data: MigrateTokensData,
 ) -> Result<()> {
-    if *accs.from_mint.info().key != accs.pool.from {
-        return Err(WrongMint.into());
-    }
-    if *accs.to_mint.info().key != accs.pool.to {
-        return Err(WrongMint.into());
-    }
-    if accs.user_from_acc.mint != accs.pool.from {
-        return Err(WrongMint.into());
-    }
-    if accs.user_to_acc.mint != accs.pool.to {
-        return Err(WrongMint.into());
-    }
-    accs.to_token_custody.verify_derivation(
-        ctx.program_id,
-        &ToCustodyTokenAccountDerivationData {
-            pool: *accs.pool.info().key,
-        },
-    )?;
-    accs.from_token_custody.verify_derivation(
-        ctx.program_id,
-        &FromCustodyTokenAccountDerivationData {
-            pool: *accs.pool.info().key,
-        },
-    )?;
-    accs.pool.verify_derivation(
-        ctx.program_id,
-        &MigrationPoolDerivationData {
-            from: accs.pool.from,
-            to: accs.pool.to,
-        },
-    )?;
-
     // Transfer in-tokens in
     let transfer_ix = spl_token::instruction::transfer(
         &spl_token::id(),
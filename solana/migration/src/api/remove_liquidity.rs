@@ -29,13 +29,18 @@ use solitaire::{
 
 #[derive(FromAccounts)]
 pub struct RemoveLiquidity<'b> {
+    #[has_one(from = from_mint, to = to_mint, error = WrongMint)]
+    #[derivation(&MigrationPoolDerivationData { from: pool.from, to: pool.to })]
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
     pub to_mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    #[derivation(&ToCustodyTokenAccountDerivationData { pool: *pool.info().key })]
     pub to_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Initialized }>>,
+    #[derivation(&ShareMintDerivationData { pool: *pool.info().key })]
     pub share_mint: Mut<ShareMint<'b, { AccountState::Initialized }>>,
 
     pub to_lp_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[token(mint = share_mint, error = WrongMint)]
     pub lp_share_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub custody_signer: CustodySigner<'b>,
     pub authority_signer: AuthoritySigner<'b>,
@@ -51,35 +56,6 @@ pub fn remove_liquidity(
     accs: &mut RemoveLiquidity,
     data: RemoveLiquidityData,
 ) -> Result<()> {
-    if *accs.from_mint.info().key != accs.pool.from {
-        return Err(WrongMint.into());
-    }
-    if *accs.to_mint.info().key != accs.pool.to {
-        return Err(WrongMint.into());
-    }
-    if accs.lp_share_acc.mint != *accs.share_mint.info().key {
-        return Err(WrongMint.into());
-    }
-    accs.to_token_custody.verify_derivation(
-        ctx.program_id,
-        &ToCustodyTokenAccountDerivationData {
-            pool: *accs.pool.info().key,
-        },
-    )?;
-    accs.share_mint.verify_derivation(
-        ctx.program_id,
-        &ShareMintDerivationData {
-            pool: *accs.pool.info().key,
-        },
-    )?;
-    accs.pool.verify_derivation(
-        ctx.program_id,
-        &MigrationPoolDerivationData {
-            from: accs.pool.from,
-            to: accs.pool.to,
-        },
-    )?;
-
     // The out amount needs to be decimal adjusted
     let out_amount = if accs.from_mint.decimals > accs.to_mint.decimals {
         data.amount
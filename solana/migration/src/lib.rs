@@ -10,6 +10,7 @@ use api::{
     remove_liquidity::*,
 };
 use solitaire::{
+    program_errors,
     solitaire,
     SolitaireError,
 };
@@ -29,20 +30,16 @@ extern crate wasm_bindgen;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 pub mod wasm;
 
-pub enum MigrationError {
-    WrongMint,
-}
-
-impl From<MigrationError> for SolitaireError {
-    fn from(t: MigrationError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
+program_errors! {
+    pub enum MigrationError: 0x4000 {
+        WrongMint = 0,
     }
 }
 
 solitaire! {
-    AddLiquidity(AddLiquidityData) => add_liquidity,
-    RemoveLiquidity(RemoveLiquidityData) => remove_liquidity,
-    ClaimShares(ClaimSharesData) => claim_shares,
-    CreatePool(CreatePoolData) => create_pool,
-    MigrateTokens(MigrateTokensData) => migrate_tokens,
+    AddLiquidity(AddLiquidityData) = 0 => add_liquidity,
+    RemoveLiquidity(RemoveLiquidityData) = 1 => remove_liquidity,
+    ClaimShares(ClaimSharesData) = 2 => claim_shares,
+    CreatePool(CreatePoolData) = 3 => create_pool,
+    MigrateTokens(MigrateTokensData) = 4 => migrate_tokens,
 }
@@ -0,0 +1,7 @@
+//! Print the program's IDL as JSON.
+//!
+//! cargo run --example idl --features idl > nft_bridge.json
+
+fn main() {
+    println!("{}", nft_bridge::instruction::idl().to_json());
+}
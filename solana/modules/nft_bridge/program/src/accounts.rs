@@ -1,11 +1,15 @@
 use crate::types::*;
 use bridge::{
-    accounts::BridgeData,
+    accounts::{
+        BridgeData,
+        EmitterFeeData,
+    },
     api::ForeignAddress,
 };
 use primitive_types::U256;
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    idl::IdlSeed,
     processors::seeded::Seeded,
     *,
 };
@@ -16,6 +20,8 @@ pub type MintSigner<'b> = Derive<Info<'b>, "mint_signer">;
 
 pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, BridgeData, { STATE }>;
 
+pub type CoreEmitterFee<'a, const STATE: AccountState> = Data<'a, EmitterFeeData, { STATE }>;
+
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
 pub type ConfigAccount<'b, const STATE: AccountState> =
@@ -33,6 +39,10 @@ impl<'b, const STATE: AccountState> Seeded<&CustodyAccountDerivationData>
     fn seeds(accs: &CustodyAccountDerivationData) -> Vec<Vec<u8>> {
         vec![accs.mint.to_bytes().to_vec()]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Arg("mint")]
+    }
 }
 
 pub type WrappedMint<'b, const STATE: AccountState> = Data<'b, SplMint, { STATE }>;
@@ -54,6 +64,15 @@ impl<'b, const STATE: AccountState> Seeded<&WrappedDerivationData> for WrappedMi
             token_id,
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("wrapped"),
+            IdlSeed::Arg("token_chain"),
+            IdlSeed::Arg("token_address"),
+            IdlSeed::Arg("token_id"),
+        ]
+    }
 }
 
 pub type WrappedTokenMeta<'b, const STATE: AccountState> = Data<'b, WrappedMeta, { STATE }>;
@@ -71,6 +90,10 @@ impl<'b, const STATE: AccountState> Seeded<&WrappedMetaDerivationData>
             data.mint_key.to_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("meta"), IdlSeed::Arg("mint_key")]
+    }
 }
 
 /// Registered chain endpoint
@@ -89,6 +112,13 @@ impl<'b, const STATE: AccountState> Seeded<&EndpointDerivationData> for Endpoint
             data.emitter_address.to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Arg("emitter_chain"),
+            IdlSeed::Arg("emitter_address"),
+        ]
+    }
 }
 
 pub type SplTokenMeta<'b> = Info<'b>;
@@ -105,4 +135,12 @@ impl<'b> Seeded<&SplTokenMetaDerivationData> for SplTokenMeta<'b> {
             data.mint.as_ref().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("metadata"),
+            IdlSeed::Address(spl_token_metadata::id()),
+            IdlSeed::Arg("mint"),
+        ]
+    }
 }
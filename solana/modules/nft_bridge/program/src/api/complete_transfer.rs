@@ -49,6 +49,9 @@ pub struct CompleteNative<'b> {
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
     pub to_authority: MaybeMut<Info<'b>>,
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
+    #[token(mint = mint, error = InvalidMint)]
+    #[token(owner = custody_signer, error = WrongAccountOwner)]
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
 
@@ -75,7 +78,7 @@ impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
 impl<'b> InstructionContext<'b> for CompleteNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct CompleteNativeData {}
 
 pub fn complete_native(
@@ -88,19 +91,6 @@ pub fn complete_native(
     accs.chain_registration
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify mints
-    if *accs.mint.info().key != accs.custody.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.custody_signer.key != accs.custody.owner {
-        return Err(WrongAccountOwner.into());
-    }
-
     // Verify VAA
     // Please refer to transfer.rs for why the token id is used to store the mint
     if accs.vaa.token_address != [1u8; 32] {
@@ -206,7 +196,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -345,7 +335,7 @@ impl<'a> From<&CompleteWrappedMeta<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrappedMeta<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct CompleteWrappedMetaData {}
 
 pub fn complete_wrapped_meta(
@@ -86,7 +86,7 @@ pub struct UpgradeContract<'b> {
 impl<'b> InstructionContext<'b> for UpgradeContract<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -138,7 +138,7 @@ impl<'a> From<&RegisterChain<'a>> for EndpointDerivationData {
 impl<'b> InstructionContext<'b> for RegisterChain<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct RegisterChainData {}
 
 pub fn register_chain(
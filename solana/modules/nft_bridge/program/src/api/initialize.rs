@@ -15,7 +15,7 @@ pub struct Initialize<'b> {
     pub config: Mut<ConfigAccount<'b, { AccountState::Uninitialized }>>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct InitializeData {
     pub bridge: Pubkey,
 }
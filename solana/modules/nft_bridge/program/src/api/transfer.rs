@@ -3,6 +3,7 @@ use crate::{
         AuthoritySigner,
         ConfigAccount,
         CoreBridge,
+        CoreEmitterFee,
         CustodyAccount,
         CustodyAccountDerivationData,
         CustodySigner,
@@ -24,6 +25,7 @@ use crate::{
     },
 };
 use bridge::{
+    accounts::effective_fee,
     api::PostMessageData,
     types::ConsistencyLevel,
     vaa::SerializePayload,
@@ -58,12 +60,14 @@ pub struct TransferNative<'b> {
 
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
+    #[token(mint = mint, error = TokenBridgeError::InvalidMint)]
     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
 
     pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
     /// SPL Metadata for the associated Mint
     pub spl_metadata: SplTokenMeta<'b>,
 
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -88,6 +92,9 @@ pub struct TransferNative<'b> {
     pub fee_collector: Mut<Info<'b>>,
 
     pub clock: Sysvar<'b, Clock>,
+
+    /// Fee override of the emitter in the core bridge
+    pub emitter_fee: CoreEmitterFee<'b, { AccountState::MaybeInitialized }>,
 }
 
 impl<'a> From<&TransferNative<'a>> for CustodyAccountDerivationData {
@@ -109,7 +116,7 @@ impl<'a> From<&TransferNative<'a>> for SplTokenMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub target_address: Address,
@@ -121,20 +128,10 @@ pub fn transfer_native(
     accs: &mut TransferNative,
     data: TransferNativeData,
 ) -> Result<()> {
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     let derivation_data: SplTokenMetaDerivationData = (&*accs).into();
     accs.spl_metadata
         .verify_derivation(&spl_token_metadata::id(), &derivation_data)?;
 
-    // Verify mints
-    if accs.from.mint != *accs.mint.info().key {
-        return Err(TokenBridgeError::InvalidMint.into());
-    }
-
     // Token must have metadata
     if accs.spl_metadata.data_is_empty() {
         return Err(TokenNotNFT.into());
@@ -179,7 +176,7 @@ pub fn transfer_native(
     let transfer_ix = solana_program::system_instruction::transfer(
         accs.payer.key,
         accs.fee_collector.key,
-        accs.bridge.config.fee,
+        effective_fee(&accs.bridge.config, &accs.emitter_fee),
     );
     invoke(&transfer_ix, ctx.accounts)?;
 
@@ -222,6 +219,7 @@ pub fn transfer_native(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(*accs.emitter_fee.info().key, false),
         ],
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
@@ -234,6 +232,8 @@ pub struct TransferWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
+    #[token(owner = from_owner, error = WrongAccountOwner)]
+    #[token(mint = mint, error = TokenBridgeError::InvalidMint)]
     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub from_owner: MaybeMut<Signer<Info<'b>>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
@@ -259,6 +259,9 @@ pub struct TransferWrapped<'b> {
     pub fee_collector: Mut<Info<'b>>,
 
     pub clock: Sysvar<'b, Clock>,
+
+    /// Fee override of the emitter in the core bridge
+    pub emitter_fee: CoreEmitterFee<'b, { AccountState::MaybeInitialized }>,
 }
 
 impl<'a> From<&TransferWrapped<'a>> for WrappedMetaDerivationData {
@@ -280,7 +283,7 @@ impl<'a> From<&TransferWrapped<'a>> for SplTokenMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub target_address: Address,
@@ -292,16 +295,6 @@ pub fn transfer_wrapped(
     accs: &mut TransferWrapped,
     data: TransferWrappedData,
 ) -> Result<()> {
-    // Verify that the from account is owned by the from_owner
-    if &accs.from.owner != accs.from_owner.key {
-        return Err(WrongAccountOwner.into());
-    }
-
-    // Verify mints
-    if accs.mint.info().key != &accs.from.mint {
-        return Err(TokenBridgeError::InvalidMint.into());
-    }
-
     // Verify that meta is correct
     let derivation_data: WrappedMetaDerivationData = (&*accs).into();
     accs.wrapped_meta
@@ -322,7 +315,7 @@ pub fn transfer_wrapped(
     let transfer_ix = solana_program::system_instruction::transfer(
         accs.payer.key,
         accs.fee_collector.key,
-        accs.bridge.config.fee,
+        effective_fee(&accs.bridge.config, &accs.emitter_fee),
     );
 
     invoke(&transfer_ix, ctx.accounts)?;
@@ -381,6 +374,7 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(*accs.emitter_fee.info().key, false),
         ],
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
@@ -39,6 +39,8 @@ use bridge::{
         BridgeConfig,
         Claim,
         ClaimDerivationData,
+        EmitterFee,
+        EmitterFeeDerivationData,
         FeeCollector,
         PostedVAA,
         PostedVAAData,
@@ -358,6 +360,10 @@ pub fn transfer_native(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -376,6 +382,7 @@ pub fn transfer_native(
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
             AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(emitter_fee_key, false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -436,6 +443,10 @@ pub fn transfer_wrapped(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -454,6 +465,7 @@ pub fn transfer_wrapped(
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
             AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(emitter_fee_key, false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
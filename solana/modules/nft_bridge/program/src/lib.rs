@@ -49,35 +49,31 @@ pub use api::{
 
 use solitaire::*;
 
-pub enum TokenBridgeError {
-    AlreadyExecuted,
-    InvalidChain,
-    InvalidGovernanceKey,
-    InvalidMetadata,
-    InvalidMint,
-    InvalidPayload,
-    InvalidUTF8String,
-    TokenNotNative,
-    UninitializedMint,
-    WrongAccountOwner,
-    TokenNotNFT,
-    InvalidAssociatedAccount,
-    InvalidRecipient,
-}
-
-impl From<TokenBridgeError> for SolitaireError {
-    fn from(t: TokenBridgeError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
+program_errors! {
+    pub enum TokenBridgeError: 0x3000 {
+        AlreadyExecuted = 0,
+        InvalidChain = 1,
+        InvalidGovernanceKey = 2,
+        InvalidMetadata = 3,
+        InvalidMint = 4,
+        InvalidPayload = 5,
+        InvalidUTF8String = 6,
+        TokenNotNative = 7,
+        UninitializedMint = 8,
+        WrongAccountOwner = 9,
+        TokenNotNFT = 10,
+        InvalidAssociatedAccount = 11,
+        InvalidRecipient = 12,
     }
 }
 
 solitaire! {
-    Initialize(InitializeData) => initialize,
-    CompleteNative(CompleteNativeData) => complete_native,
-    CompleteWrapped(CompleteWrappedData) => complete_wrapped,
-    CompleteWrappedMeta(CompleteWrappedMetaData) => complete_wrapped_meta,
-    TransferWrapped(TransferWrappedData) => transfer_wrapped,
-    TransferNative(TransferNativeData) => transfer_native,
-    RegisterChain(RegisterChainData) => register_chain,
-    UpgradeContract(UpgradeContractData) => upgrade_contract,
+    Initialize(InitializeData) = 0 => initialize,
+    CompleteNative(CompleteNativeData) = 1 => complete_native,
+    CompleteWrapped(CompleteWrappedData) = 2 => complete_wrapped,
+    CompleteWrappedMeta(CompleteWrappedMetaData) = 3 => complete_wrapped_meta,
+    TransferWrapped(TransferWrappedData) = 4 => transfer_wrapped,
+    TransferNative(TransferNativeData) = 5 => transfer_native,
+    RegisterChain(RegisterChainData) = 6 => register_chain,
+    UpgradeContract(UpgradeContractData) = 7 => upgrade_contract,
 }
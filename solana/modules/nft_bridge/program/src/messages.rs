@@ -1,6 +1,9 @@
-use crate::types::{
-    Address,
-    ChainID,
+use crate::{
+    types::{
+        Address,
+        ChainID,
+    },
+    TokenBridgeError,
 };
 use bridge::{
     vaa::{
@@ -55,7 +58,7 @@ impl DeserializePayload for PayloadTransfer {
         let mut v = Cursor::new(buf);
 
         if v.read_u8()? != 1 {
-            return Err(SolitaireError::Custom(0));
+            return Err(TokenBridgeError::InvalidPayload.into());
         };
 
         let mut token_address = Address::default();
@@ -0,0 +1,127 @@
+//! Helpers for clients of the token bridge.
+//!
+//! # Account layout of `CompleteNative` and `CompleteWrapped`
+//!
+//! Transfer limits and the custody ledger added required accounts to both instructions, clients
+//! building the account list by hand have to insert them. Between the last token account of the
+//! instruction (`custody_signer` for `CompleteNative`, `mint_authority` for `CompleteWrapped`) and
+//! the rent sysvar, both now take:
+//!
+//! 1. the clock sysvar,
+//! 2. the transfer limit of the mint, writable,
+//! 3. the transfer limit of the source chain, writable,
+//! 4. the pending transfer of the VAA, writable, created when a limit is exceeded.
+//!
+//! `CompleteNative` is followed by the custody ledger of the mint, writable, and ends with the
+//! Token-2022 program after the SPL token program. The builders in `token_bridge::instructions`
+//! derive all of these, transactions built with them need no changes.
+
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{
+        RpcAccountInfoConfig,
+        RpcProgramAccountsConfig,
+    },
+    rpc_filter::RpcFilterType,
+};
+use solana_program::{
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use solitaire::ErrorCode;
+use spl_token::state::Mint;
+use std::error::Error;
+use token_bridge::{
+    decimals::normalize,
+    query,
+    types::{
+        Address,
+        ChainID,
+    },
+};
+
+pub use token_bridge::{
+    decimals::NormalizedTransfer,
+    query::WrappedAsset,
+};
+
+/// Precompute a transfer of `amount` units of `mint` paying `fee` to the relayer, without sending
+/// it. The result tells how much leaves the sender's account, how much dust stays behind and what
+/// the recipient receives on the target chain.
+///
+/// Fails with the error the program would raise if the transfer would be rejected.
+pub fn quote_transfer(
+    rpc_client: &RpcClient,
+    mint: &Pubkey,
+    amount: u64,
+    fee: u64,
+) -> Result<NormalizedTransfer, Box<dyn Error>> {
+    // Token-2022 mints start with the legacy layout, extensions follow it.
+    let data = rpc_client.get_account(mint)?.data;
+    let mint = Mint::unpack(data.get(..Mint::LEN).ok_or("not a mint")?)?;
+    normalize(amount, fee, mint.decimals).map_err(|e| e.name().into())
+}
+
+/// Look up the origin of `mint`. Returns `None` if it is not a mint wrapped by the token bridge at
+/// `program_id`.
+pub fn wrapped_asset(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Option<WrappedAsset>, Box<dyn Error>> {
+    let meta = query::wrapped_meta(program_id, mint);
+    let account = rpc_client
+        .get_account_with_commitment(&meta, rpc_client.commitment())?
+        .value;
+
+    Ok(account.and_then(|account| {
+        if account.owner != *program_id {
+            return None;
+        }
+        query::wrapped_asset(program_id, &meta, &account.data)
+    }))
+}
+
+/// Look up the mint wrapping `token_address` of `chain`. Returns `None` if the token has not been
+/// wrapped yet.
+pub fn wrapped_mint(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    chain: ChainID,
+    token_address: Address,
+) -> Result<Option<Pubkey>, Box<dyn Error>> {
+    let mint = query::wrapped_mint(program_id, chain, token_address);
+    let created = rpc_client
+        .get_account_with_commitment(&mint, rpc_client.commitment())?
+        .value
+        .is_some();
+
+    Ok(Some(mint).filter(|_| created))
+}
+
+/// List every mint wrapped by the token bridge at `program_id`. This scans the accounts of the
+/// program, so it needs an RPC node which allows `getProgramAccounts`.
+pub fn wrapped_assets(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+) -> Result<Vec<WrappedAsset>, Box<dyn Error>> {
+    let accounts = rpc_client.get_program_accounts_with_config(
+        program_id,
+        RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::DataSize(
+                query::WRAPPED_META_SIZE as u64,
+            )]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )?;
+
+    Ok(accounts
+        .iter()
+        .filter_map(|(address, account)| query::wrapped_asset(program_id, address, &account.data))
+        .collect())
+}
@@ -9,6 +9,15 @@ use std::{
 };
 
 use borsh::BorshDeserialize;
+use bridge::{
+    vaa::VAA,
+    DeserializePayload,
+    PostVAAData,
+};
+use bridge_client::{
+    print_progress,
+    PostVAAOptions,
+};
 use clap::{
     crate_description,
     crate_name,
@@ -17,6 +26,7 @@ use clap::{
     App,
     AppSettings,
     Arg,
+    ArgMatches,
     SubCommand,
 };
 use hex;
@@ -36,12 +46,16 @@ use solana_client::{
     rpc_client::RpcClient,
     rpc_config::RpcSendTransactionConfig,
 };
-use solana_program::account_info::AccountInfo;
+use solana_program::{
+    account_info::AccountInfo,
+    program_pack::Pack,
+};
 use solana_sdk::{
     commitment_config::{
         CommitmentConfig,
         CommitmentLevel,
     },
+    instruction::Instruction,
     native_token::*,
     program_error::ProgramError::AccountAlreadyInitialized,
     pubkey::Pubkey,
@@ -59,6 +73,21 @@ use solitaire::{
     Info,
 };
 use solitaire_client::Derive;
+use token_bridge::{
+    accounts::AuthoritySigner,
+    messages::{
+        PayloadAssetMeta,
+        PayloadGovernanceRegisterChain,
+        PayloadTransfer,
+    },
+    CompleteNativeData,
+    CompleteNativeSolData,
+    CompleteWrappedData,
+    CreateWrappedData,
+    RegisterChainData,
+    TransferNativeData,
+    TransferWrappedData,
+};
 
 struct Config {
     rpc_client: RpcClient,
@@ -132,6 +161,337 @@ fn command_create_meta(
     Ok(Some(transaction))
 }
 
+// Sign a transaction paid for by the fee payer, `signers` are required in addition to it.
+fn signed_transaction(
+    config: &Config,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> CommmandResult {
+    let mut transaction =
+        Transaction::new_with_payer(instructions, Some(&config.fee_payer.pubkey()));
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+    let mut all_signers = vec![&config.fee_payer];
+    all_signers.extend_from_slice(signers);
+    transaction.sign(&all_signers, recent_blockhash);
+    Ok(Some(transaction))
+}
+
+// Verify the signatures of a VAA and post it to the core bridge, unless it is posted already.
+fn command_post_vaa(config: &Config, core_bridge: &Pubkey, vaa: &VAA) -> Result<Pubkey, Error> {
+    println!(
+        "Posting VAA {}/{}/{}",
+        vaa.emitter_chain,
+        hex::encode(vaa.emitter_address),
+        vaa.sequence
+    );
+
+    bridge_client::post_vaa(
+        &config.rpc_client,
+        core_bridge,
+        &config.fee_payer,
+        vaa,
+        &PostVAAOptions {
+            commitment: config.commitment_config,
+            ..PostVAAOptions::default()
+        },
+        print_progress,
+    )
+}
+
+fn command_attest(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    mint: &Pubkey,
+    nonce: u32,
+) -> CommmandResult {
+    println!("Attesting mint {}", mint);
+
+    let message = Keypair::new();
+    let ix = token_bridge::instructions::attest(
+        *bridge,
+        *core_bridge,
+        config.fee_payer.pubkey(),
+        message.pubkey(),
+        *mint,
+        nonce,
+    )
+    .unwrap();
+    println!("Message account: {}", message.pubkey());
+
+    signed_transaction(config, &[ix], &[&message])
+}
+
+// Transfer tokens out of the `from` account owned by the owner. Wrapped tokens are burned and
+// native tokens are locked in custody, which one applies is decided by the mint of the account.
+fn command_transfer(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    from: &Pubkey,
+    data: TransferNativeData,
+) -> CommmandResult {
+    // Token-2022 accounts start with the legacy layout, extensions follow it.
+    let from_account = config.rpc_client.get_account(from)?;
+    let account = spl_token::state::Account::unpack(
+        from_account
+            .data
+            .get(..spl_token::state::Account::LEN)
+            .ok_or("not a token account")?,
+    )?;
+    let wrapped_asset = client::wrapped_asset(&config.rpc_client, bridge, &account.mint)?;
+
+    // The bridge moves the tokens as a delegate of the owner. Both token programs share the
+    // instruction layout, the builder only accepts the legacy program.
+    let mut approve_ix = spl_token::instruction::approve(
+        &spl_token::id(),
+        from,
+        &AuthoritySigner::key(None, bridge),
+        &config.owner.pubkey(),
+        &[],
+        data.amount,
+    )?;
+    approve_ix.program_id = from_account.owner;
+
+    let message = Keypair::new();
+    let transfer_ix = match wrapped_asset {
+        Some(asset) => {
+            println!(
+                "Transferring {} wrapped tokens of {}/{} to {}/{}",
+                data.amount,
+                asset.chain,
+                hex::encode(asset.token_address),
+                data.target_chain,
+                hex::encode(data.target_address)
+            );
+            token_bridge::instructions::transfer_wrapped(
+                *bridge,
+                *core_bridge,
+                config.fee_payer.pubkey(),
+                message.pubkey(),
+                *from,
+                config.owner.pubkey(),
+                asset.chain,
+                asset.token_address,
+                TransferWrappedData {
+                    nonce: data.nonce,
+                    amount: data.amount,
+                    fee: data.fee,
+                    target_address: data.target_address,
+                    target_chain: data.target_chain,
+                },
+            )
+            .unwrap()
+        }
+        None => {
+            println!(
+                "Transferring {} native tokens of {} to {}/{}",
+                data.amount,
+                account.mint,
+                data.target_chain,
+                hex::encode(data.target_address)
+            );
+            token_bridge::instructions::transfer_native(
+                *bridge,
+                *core_bridge,
+                config.fee_payer.pubkey(),
+                message.pubkey(),
+                *from,
+                account.mint,
+                data,
+            )
+            .unwrap()
+        }
+    };
+    println!("Message account: {}", message.pubkey());
+
+    signed_transaction(
+        config,
+        &[approve_ix, transfer_ix],
+        &[&config.owner, &message],
+    )
+}
+
+// Post a transfer VAA and complete it. Native SOL is paid out as lamports unless the recipient is
+// a token account, fees can only be claimed into an account of the fee payer.
+fn command_redeem(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    vaa: &VAA,
+    fee_recipient: Option<Pubkey>,
+) -> CommmandResult {
+    let payload = PayloadTransfer::deserialize(&mut vaa.payload.as_slice())
+        .map_err(|e| format!("Invalid transfer payload: {:?}", e))?;
+    let message = command_post_vaa(config, core_bridge, vaa)?;
+    let vaa_data: PostVAAData = vaa.clone().into();
+    let to = Pubkey::new(&payload.to);
+    let payer = config.fee_payer.pubkey();
+
+    let ix = if payload.token_chain == bridge::CHAIN_ID_SOLANA {
+        let mint = Pubkey::new(&payload.token_address);
+        let to_is_token_account = config
+            .rpc_client
+            .get_account_with_commitment(&to, config.commitment_config)?
+            .value
+            .map_or(false, |account| {
+                token_bridge::token_2022::is_token_program(&account.owner)
+            });
+
+        if mint == spl_token::native_mint::id() && !to_is_token_account {
+            println!("Redeeming {} lamports to {}", payload.amount, to);
+            token_bridge::instructions::complete_native_sol(
+                *bridge,
+                *core_bridge,
+                payer,
+                message,
+                vaa_data,
+                to,
+                CompleteNativeSolData {},
+            )
+            .unwrap()
+        } else {
+            println!(
+                "Redeeming {} native tokens of {} to {}",
+                payload.amount, mint, to
+            );
+            token_bridge::instructions::complete_native(
+                *bridge,
+                *core_bridge,
+                payer,
+                message,
+                vaa_data,
+                to,
+                fee_recipient,
+                mint,
+                CompleteNativeData {},
+            )
+            .unwrap()
+        }
+    } else {
+        println!(
+            "Redeeming {} wrapped tokens of {}/{} to {}",
+            payload.amount,
+            payload.token_chain,
+            hex::encode(payload.token_address),
+            to
+        );
+        token_bridge::instructions::complete_wrapped(
+            *bridge,
+            *core_bridge,
+            payer,
+            message,
+            vaa_data,
+            payload,
+            to,
+            fee_recipient,
+            CompleteWrappedData {},
+        )
+        .unwrap()
+    };
+
+    signed_transaction(config, &[ix], &[])
+}
+
+fn command_create_wrapped(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    vaa: &VAA,
+) -> CommmandResult {
+    let payload = PayloadAssetMeta::deserialize(&mut vaa.payload.as_slice())
+        .map_err(|e| format!("Invalid asset meta payload: {:?}", e))?;
+    println!(
+        "Creating wrapped asset for {}/{}",
+        payload.token_chain,
+        hex::encode(payload.token_address)
+    );
+
+    let message = command_post_vaa(config, core_bridge, vaa)?;
+    let ix = token_bridge::instructions::create_wrapped(
+        *bridge,
+        *core_bridge,
+        config.fee_payer.pubkey(),
+        message,
+        vaa.clone().into(),
+        payload,
+        CreateWrappedData {},
+    )
+    .unwrap();
+
+    signed_transaction(config, &[ix], &[])
+}
+
+fn command_register_chain(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    vaa: &VAA,
+) -> CommmandResult {
+    let payload = PayloadGovernanceRegisterChain::deserialize(&mut vaa.payload.as_slice())
+        .map_err(|e| format!("Invalid governance payload: {:?}", e))?;
+    println!(
+        "Registering chain {} with endpoint {}",
+        payload.chain,
+        hex::encode(payload.endpoint_address)
+    );
+
+    let message = command_post_vaa(config, core_bridge, vaa)?;
+    let ix = token_bridge::instructions::register_chain(
+        *bridge,
+        *core_bridge,
+        config.fee_payer.pubkey(),
+        message,
+        vaa.clone().into(),
+        payload,
+        RegisterChainData {},
+    )
+    .unwrap();
+
+    signed_transaction(config, &[ix], &[])
+}
+
+fn command_wrapped_address(
+    config: &Config,
+    bridge: &Pubkey,
+    token_chain: u16,
+    token_address: [u8; 32],
+) -> CommmandResult {
+    let mint = token_bridge::query::wrapped_mint(bridge, token_chain, token_address);
+    let created = client::wrapped_mint(&config.rpc_client, bridge, token_chain, token_address)?;
+    println!("Mint: {}", mint);
+    println!("Created: {}", created.is_some());
+
+    Ok(None)
+}
+
+fn print_wrapped_asset(asset: &client::WrappedAsset) {
+    println!("Mint: {}", asset.mint);
+    println!("Chain: {}", asset.chain);
+    println!("Token Address: {}", hex::encode(asset.token_address));
+    println!("Original Decimals: {}", asset.original_decimals);
+}
+
+fn command_wrapped_asset(config: &Config, bridge: &Pubkey, mint: &Pubkey) -> CommmandResult {
+    match client::wrapped_asset(&config.rpc_client, bridge, mint)? {
+        Some(asset) => print_wrapped_asset(&asset),
+        None => println!("{} is not a wrapped mint", mint),
+    }
+
+    Ok(None)
+}
+
+fn command_wrapped_assets(config: &Config, bridge: &Pubkey) -> CommmandResult {
+    for asset in client::wrapped_assets(&config.rpc_client, bridge)? {
+        print_wrapped_asset(&asset);
+        println!();
+    }
+
+    Ok(None)
+}
+
 fn main() {
     let matches = App::new(crate_name!())
         .about(crate_description!())
@@ -234,6 +594,39 @@ fn main() {
                         .help("Specify the token mint to derive metadata for"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("quote")
+                .about("Show what a transfer moves once amounts are truncated to 8 decimals")
+                .arg(
+                    Arg::with_name("mint")
+                        .long("mint")
+                        .value_name("MINT_KEY")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Specify the token mint to transfer"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .value_name("AMOUNT")
+                        .validator(is_u64)
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Amount to transfer in token units"),
+                )
+                .arg(
+                    Arg::with_name("fee")
+                        .long("fee")
+                        .value_name("FEE")
+                        .validator(is_u64)
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Relayer fee in token units"),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("create-meta")
                 .about("Create token metadata")
@@ -275,6 +668,156 @@ fn main() {
                         .help("URI of the token metadata"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("attest")
+                .about("Attest a mint so that it can be wrapped on other chains")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(
+                    Arg::with_name("mint")
+                        .long("mint")
+                        .value_name("MINT_KEY")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Specify the token mint to attest"),
+                )
+                .arg(nonce_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer")
+                .about("Transfer native or wrapped tokens held by the owner to another chain")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(
+                    Arg::with_name("from")
+                        .long("from")
+                        .value_name("TOKEN_ACCOUNT")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Token account of the owner to transfer from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .value_name("AMOUNT")
+                        .validator(is_u64)
+                        .takes_value(true)
+                        .index(4)
+                        .required(true)
+                        .help("Amount to transfer in token units"),
+                )
+                .arg(
+                    Arg::with_name("target-chain")
+                        .long("target-chain")
+                        .value_name("CHAIN")
+                        .validator(is_u16)
+                        .takes_value(true)
+                        .index(5)
+                        .required(true)
+                        .help("Chain ID of the recipient"),
+                )
+                .arg(
+                    Arg::with_name("target-address")
+                        .long("target-address")
+                        .value_name("ADDRESS")
+                        .validator(is_address)
+                        .takes_value(true)
+                        .index(6)
+                        .required(true)
+                        .help("Address of the recipient in hex, left padded to 32 bytes"),
+                )
+                .arg(
+                    Arg::with_name("fee")
+                        .long("fee")
+                        .value_name("FEE")
+                        .validator(is_u64)
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Relayer fee in token units"),
+                )
+                .arg(nonce_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("redeem")
+                .about("Post a transfer VAA to the core bridge and complete the transfer")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(vaa_arg())
+                .arg(
+                    Arg::with_name("fee-recipient")
+                        .long("fee-recipient")
+                        .value_name("TOKEN_ACCOUNT")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .help(
+                            "Token account of the fee payer receiving the relayer fee. \
+                             Defaults to the recipient account.",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("create-wrapped")
+                .about("Post an asset meta VAA and create or update its wrapped asset")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("register-chain")
+                .about("Post a chain registration governance VAA and register the chain")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("wrapped-asset")
+                .about("Get the token a wrapped mint represents")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("mint")
+                        .long("mint")
+                        .value_name("MINT_KEY")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Specify the wrapped mint"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("wrapped-assets")
+                .about("List all wrapped mints, requires getProgramAccounts on the RPC node")
+                .arg(bridge_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("wrapped-address")
+                .about("Get the wrapped mint of a token from another chain")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("chain")
+                        .long("chain")
+                        .value_name("CHAIN")
+                        .validator(is_u16)
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Chain ID the token originates from"),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .long("address")
+                        .value_name("ADDRESS")
+                        .validator(is_address)
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Address of the token on its chain in hex, left padded to 32 bytes"),
+                ),
+        )
         .get_matches();
 
     let config = {
@@ -326,6 +869,22 @@ fn main() {
 
             Ok(None)
         }
+        ("quote", Some(arg_matches)) => {
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+            let amount: u64 = value_of(arg_matches, "amount").unwrap();
+            let fee: u64 = value_of(arg_matches, "fee").unwrap();
+
+            client::quote_transfer(&config.rpc_client, &mint, amount, fee).map(|quote| {
+                println!("Transferred: {}", quote.transferred);
+                println!("Dust: {}", quote.dust);
+                println!("Payload Amount: {}", quote.amount);
+                println!("Payload Fee: {}", quote.fee);
+                println!("Payload Decimals: {}", quote.decimals);
+                println!("Received: {}", quote.received());
+
+                None
+            })
+        }
         ("metadata", Some(arg_matches)) => {
             let mint = pubkey_of(arg_matches, "mint").unwrap();
             let meta_acc = Pubkey::find_program_address(
@@ -350,6 +909,68 @@ fn main() {
 
             Ok(None)
         }
+        ("attest", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+            let nonce: u32 = value_of(arg_matches, "nonce").unwrap();
+
+            command_attest(&config, &bridge, &core_bridge, &mint, nonce)
+        }
+        ("transfer", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let from = pubkey_of(arg_matches, "from").unwrap();
+            let data = TransferNativeData {
+                nonce: value_of(arg_matches, "nonce").unwrap(),
+                amount: value_of(arg_matches, "amount").unwrap(),
+                fee: value_of(arg_matches, "fee").unwrap(),
+                target_address: address_of(arg_matches, "target-address"),
+                target_chain: value_of(arg_matches, "target-chain").unwrap(),
+            };
+
+            command_transfer(&config, &bridge, &core_bridge, &from, data)
+        }
+        ("redeem", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+            let fee_recipient = pubkey_of(arg_matches, "fee-recipient");
+
+            command_redeem(&config, &bridge, &core_bridge, &vaa, fee_recipient)
+        }
+        ("create-wrapped", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_create_wrapped(&config, &bridge, &core_bridge, &vaa)
+        }
+        ("register-chain", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let vaa = vaa_of(arg_matches);
+
+            command_register_chain(&config, &bridge, &core_bridge, &vaa)
+        }
+        ("wrapped-address", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let chain: u16 = value_of(arg_matches, "chain").unwrap();
+            let address = address_of(arg_matches, "address");
+
+            command_wrapped_address(&config, &bridge, chain, address)
+        }
+        ("wrapped-asset", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+
+            command_wrapped_asset(&config, &bridge, &mint)
+        }
+        ("wrapped-assets", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+
+            command_wrapped_assets(&config, &bridge)
+        }
 
         _ => unreachable!(),
     }
@@ -391,6 +1012,20 @@ where
     }
 }
 
+pub fn is_u16<T>(amount: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    if amount.as_ref().parse::<u16>().is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unable to parse input amount as integer, provided: {}",
+            amount
+        ))
+    }
+}
+
 pub fn is_u32<T>(amount: T) -> Result<(), String>
 where
     T: AsRef<str> + Display,
@@ -428,6 +1063,75 @@ where
         .map_err(|e| format!("{}", e))
 }
 
+pub fn is_address<T>(value: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    let address = hex::decode(value.to_string()).map_err(|e| format!("{}", e))?;
+    if address.len() > 32 {
+        return Err(format!("Address is longer than 32 bytes: {}", value));
+    }
+    Ok(())
+}
+
+// Addresses of other chains are shorter on some chains, they are left padded to 32 bytes.
+fn address_of(matches: &ArgMatches<'_>, name: &str) -> [u8; 32] {
+    let data = hex::decode(value_of::<String>(matches, name).unwrap()).unwrap();
+    let mut address = [0u8; 32];
+    address[32 - data.len()..].copy_from_slice(&data);
+    address
+}
+
+fn bridge_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("bridge")
+        .long("bridge")
+        .value_name("BRIDGE_KEY")
+        .validator(is_pubkey_or_keypair)
+        .takes_value(true)
+        .index(1)
+        .required(true)
+        .help("Specify the token bridge program address")
+}
+
+fn core_bridge_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("core-bridge")
+        .long("core-bridge")
+        .value_name("CORE_BRIDGE_KEY")
+        .validator(is_pubkey_or_keypair)
+        .takes_value(true)
+        .index(2)
+        .required(true)
+        .help("Address of the Wormhole core bridge program")
+}
+
+fn vaa_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("vaa")
+        .validator(is_hex)
+        .value_name("VAA")
+        .takes_value(true)
+        .index(3)
+        .required(true)
+        .help("Signed VAA in hex")
+}
+
+fn nonce_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("nonce")
+        .long("nonce")
+        .value_name("NONCE")
+        .validator(is_u32)
+        .takes_value(true)
+        .default_value("0")
+        .help("Nonce of the emitted message")
+}
+
+fn vaa_of(matches: &ArgMatches<'_>) -> VAA {
+    let data = hex::decode(value_of::<String>(matches, "vaa").unwrap()).unwrap();
+    VAA::deserialize(&data).unwrap_or_else(|err| {
+        eprintln!("Invalid VAA: {}", err);
+        exit(1)
+    })
+}
+
 fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
     let balance = config
         .rpc_client
@@ -0,0 +1,7 @@
+//! Print the program's IDL as JSON.
+//!
+//! cargo run --example idl --features idl > token_bridge.json
+
+fn main() {
+    println!("{}", token_bridge::instruction::idl().to_json());
+}
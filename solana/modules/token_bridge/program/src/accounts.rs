@@ -1,6 +1,10 @@
 use crate::types::*;
 use bridge::{
-    accounts::BridgeData,
+    accounts::{
+        BridgeData,
+        EmitterFeeData,
+        PostedVAAData,
+    },
     api::ForeignAddress,
     vaa::{
         DeserializePayload,
@@ -9,6 +13,7 @@ use bridge::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    idl::IdlSeed,
     processors::seeded::Seeded,
     *,
 };
@@ -17,14 +22,21 @@ pub type AuthoritySigner<'b> = Derive<Info<'b>, "authority_signer">;
 pub type CustodySigner<'b> = Derive<Info<'b>, "custody_signer">;
 pub type MintSigner<'b> = Derive<Info<'b>, "mint_signer">;
 
+/// Temporary wSOL account used to unwrap SOL, it is created and closed within a single instruction.
+pub type NativeUnwrapAccount<'b> = Derive<Info<'b>, "native_unwrap">;
+
 pub type CoreBridge<'a, const State: AccountState> = Data<'a, BridgeData, { State }>;
 
+pub type CoreEmitterFee<'a, const State: AccountState> = Data<'a, EmitterFeeData, { State }>;
+
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
 pub type ConfigAccount<'b, const State: AccountState> =
     Derive<Data<'b, Config, { State }>, "config">;
 
-pub type CustodyAccount<'b, const State: AccountState> = Data<'b, SplAccount, { State }>;
+/// Token account of either token program, handlers check that it belongs to the program of its mint
+/// with `token_2022::check_account`.
+pub type CustodyAccount<'b, const State: AccountState> = Data<'b, TokenAccount, { State }>;
 
 pub struct CustodyAccountDerivationData {
     pub mint: Pubkey,
@@ -36,6 +48,38 @@ impl<'b, const State: AccountState> Seeded<&CustodyAccountDerivationData>
     fn seeds(accs: &CustodyAccountDerivationData) -> Vec<Vec<u8>> {
         vec![accs.mint.to_bytes().to_vec()]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Arg("mint")]
+    }
+}
+
+pub type CustodyLedgerAccount<'b, const State: AccountState> = Data<'b, CustodyLedger, { State }>;
+
+pub struct CustodyLedgerDerivationData {
+    pub mint: Pubkey,
+}
+
+impl<'b, const State: AccountState> Seeded<&CustodyLedgerDerivationData>
+    for CustodyLedgerAccount<'b, { State }>
+{
+    fn seeds(data: &CustodyLedgerDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("ledger").as_bytes().to_vec(),
+            data.mint.to_bytes().to_vec(),
+        ]
+    }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("ledger"), IdlSeed::Arg("mint")]
+    }
+
+    fn stored_bump(&self) -> Option<u8> {
+        match self.is_initialized() {
+            true => Some(self.bump),
+            false => None,
+        }
+    }
 }
 
 pub type WrappedMint<'b, const State: AccountState> = Data<'b, SplMint, { State }>;
@@ -53,6 +97,14 @@ impl<'b, const State: AccountState> Seeded<&WrappedDerivationData> for WrappedMi
             data.token_address.to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("wrapped"),
+            IdlSeed::Arg("token_chain"),
+            IdlSeed::Arg("token_address"),
+        ]
+    }
 }
 
 pub type WrappedTokenMeta<'b, const State: AccountState> = Data<'b, WrappedMeta, { State }>;
@@ -70,6 +122,35 @@ impl<'b, const State: AccountState> Seeded<&WrappedMetaDerivationData>
             data.mint_key.to_bytes().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("meta"), IdlSeed::Arg("mint_key")]
+    }
+}
+
+pub type WrappedMetadataOverrideAccount<'b, const State: AccountState> =
+    Data<'b, WrappedMetadataOverride, { State }, { WrappedMetadataOverride::MAX_SIZE }>;
+
+pub struct WrappedMetadataOverrideDerivationData {
+    pub mint_key: Pubkey,
+}
+
+impl<'b, const State: AccountState> Seeded<&WrappedMetadataOverrideDerivationData>
+    for WrappedMetadataOverrideAccount<'b, { State }>
+{
+    fn seeds(data: &WrappedMetadataOverrideDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("metadata_override").as_bytes().to_vec(),
+            data.mint_key.to_bytes().to_vec(),
+        ]
+    }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("metadata_override"),
+            IdlSeed::Arg("mint_key"),
+        ]
+    }
 }
 
 /// Registered chain endpoint
@@ -88,6 +169,110 @@ impl<'b, const State: AccountState> Seeded<&EndpointDerivationData> for Endpoint
             data.emitter_address.to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Arg("emitter_chain"),
+            IdlSeed::Arg("emitter_address"),
+        ]
+    }
+}
+
+/// Outflow limit for a mint or for transfers arriving from a chain, see `TransferLimit`.
+pub type TransferLimitAccount<'b, const State: AccountState> = Data<'b, TransferLimit, { State }>;
+
+pub struct MintLimitDerivationData {
+    pub mint: Pubkey,
+}
+
+impl<'b, const State: AccountState> Seeded<&MintLimitDerivationData>
+    for TransferLimitAccount<'b, { State }>
+{
+    fn seeds(data: &MintLimitDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("limit").as_bytes().to_vec(),
+            data.mint.to_bytes().to_vec(),
+        ]
+    }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("limit"), IdlSeed::Arg("mint")]
+    }
+
+    fn stored_bump(&self) -> Option<u8> {
+        match self.is_initialized() {
+            true => Some(self.bump),
+            false => None,
+        }
+    }
+}
+
+pub struct ChainLimitDerivationData {
+    pub chain: ChainID,
+}
+
+impl<'b, const State: AccountState> Seeded<&ChainLimitDerivationData>
+    for TransferLimitAccount<'b, { State }>
+{
+    fn seeds(data: &ChainLimitDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("limit").as_bytes().to_vec(),
+            data.chain.to_be_bytes().to_vec(),
+        ]
+    }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const("limit"), IdlSeed::Arg("chain")]
+    }
+
+    fn stored_bump(&self) -> Option<u8> {
+        match self.is_initialized() {
+            true => Some(self.bump),
+            false => None,
+        }
+    }
+}
+
+pub type PendingTransferAccount<'b, const State: AccountState> =
+    Loader<'b, PendingTransfer, { State }>;
+
+/// Pending transfers are derived from the VAA they complete, in the same way as its claim.
+pub struct PendingTransferDerivationData {
+    pub emitter_address: ForeignAddress,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+}
+
+impl From<&PostedVAAData> for PendingTransferDerivationData {
+    fn from(vaa: &PostedVAAData) -> Self {
+        PendingTransferDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        }
+    }
+}
+
+impl<'b, const State: AccountState> Seeded<&PendingTransferDerivationData>
+    for PendingTransferAccount<'b, { State }>
+{
+    fn seeds(data: &PendingTransferDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("pending").as_bytes().to_vec(),
+            data.emitter_address.to_vec(),
+            data.emitter_chain.to_be_bytes().to_vec(),
+            data.sequence.to_be_bytes().to_vec(),
+        ]
+    }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("pending"),
+            IdlSeed::Arg("emitter_address"),
+            IdlSeed::Arg("emitter_chain"),
+            IdlSeed::Arg("sequence"),
+        ]
+    }
 }
 
 pub type SplTokenMeta<'b> = Info<'b>;
@@ -104,4 +289,12 @@ impl<'b> Seeded<&SplTokenMetaDerivationData> for SplTokenMeta<'b> {
             data.mint.as_ref().to_vec(),
         ]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("metadata"),
+            IdlSeed::Address(spl_token_metadata::id()),
+            IdlSeed::Arg("mint"),
+        ]
+    }
 }
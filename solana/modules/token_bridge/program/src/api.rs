@@ -1,13 +1,17 @@
 pub mod attest;
 pub mod complete_transfer;
 pub mod create_wrapped;
+pub mod custody;
 pub mod governance;
 pub mod initialize;
 pub mod transfer;
+pub mod transfer_limit;
 
 pub use attest::*;
 pub use complete_transfer::*;
 pub use create_wrapped::*;
+pub use custody::*;
 pub use governance::*;
 pub use initialize::*;
 pub use transfer::*;
+pub use transfer_limit::*;
@@ -2,6 +2,7 @@ use crate::{
     accounts::{
         ConfigAccount,
         CoreBridge,
+        CoreEmitterFee,
         EmitterAccount,
         SplTokenMeta,
         SplTokenMetaDerivationData,
@@ -12,6 +13,7 @@ use crate::{
         PayloadAssetMeta,
         PayloadTransfer,
     },
+    token_2022,
     types::*,
     TokenBridgeError::{
         self,
@@ -19,7 +21,10 @@ use crate::{
     },
 };
 use bridge::{
-    accounts::Bridge,
+    accounts::{
+        effective_fee,
+        Bridge,
+    },
     api::{
         PostMessage,
         PostMessageData,
@@ -71,8 +76,8 @@ pub struct AttestToken<'b> {
 
     pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
 
-    /// Mint to attest
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    /// Mint to attest, of either token program
+    pub mint: Data<'b, TokenMint, { AccountState::Initialized }>,
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Uninitialized }>,
 
     /// SPL Metadata for the associated Mint
@@ -94,6 +99,9 @@ pub struct AttestToken<'b> {
     pub fee_collector: Mut<Info<'b>>,
 
     pub clock: Sysvar<'b, Clock>,
+
+    /// Fee override of the emitter in the core bridge
+    pub emitter_fee: CoreEmitterFee<'b, { AccountState::MaybeInitialized }>,
 }
 
 impl<'b> InstructionContext<'b> for AttestToken<'b> {
@@ -115,7 +123,7 @@ impl<'a> From<&AttestToken<'a>> for SplTokenMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct AttestTokenData {
     pub nonce: u32,
 }
@@ -125,11 +133,13 @@ pub fn attest_token(
     accs: &mut AttestToken,
     data: AttestTokenData,
 ) -> Result<()> {
+    token_2022::check_mint(accs.mint.info())?;
+
     // Pay fee
     let transfer_ix = solana_program::system_instruction::transfer(
         accs.payer.key,
         accs.fee_collector.key,
-        accs.bridge.config.fee,
+        effective_fee(&accs.bridge.config, &accs.emitter_fee),
     );
 
     invoke(&transfer_ix, ctx.accounts)?;
@@ -186,6 +196,7 @@ pub fn attest_token(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(*accs.emitter_fee.info().key, false),
         ],
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
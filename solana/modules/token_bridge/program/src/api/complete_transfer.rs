@@ -1,18 +1,32 @@
 use crate::{
     accounts::{
+        ChainLimitDerivationData,
         ConfigAccount,
         CustodyAccount,
         CustodyAccountDerivationData,
+        CustodyLedgerAccount,
+        CustodyLedgerDerivationData,
         CustodySigner,
         Endpoint,
         EndpointDerivationData,
+        MintLimitDerivationData,
         MintSigner,
+        NativeUnwrapAccount,
+        PendingTransferAccount,
+        PendingTransferDerivationData,
+        TransferLimitAccount,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
+    api::{
+        consume_limits,
+        open_ledger,
+    },
+    decimals::denormalize,
     messages::PayloadTransfer,
+    token_2022,
     types::*,
     TokenBridgeError::*,
 };
@@ -22,9 +36,14 @@ use bridge::{
 };
 use solana_program::{
     account_info::AccountInfo,
-    program::invoke_signed,
+    program::{
+        invoke,
+        invoke_signed,
+    },
     program_error::ProgramError,
+    program_pack::Pack,
     pubkey::Pubkey,
+    sysvar::clock::Clock,
 };
 use solitaire::{
     processors::seeded::{
@@ -51,12 +70,32 @@ pub struct CompleteNative<'b> {
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
-    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
-    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[token(mint = mint, error = InvalidMint)]
+    #[owner(*mint.info().owner, error = InvalidMint)]
+    pub to: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
+    /// Token account of the relayer, it must be owned by the payer.
+    #[token(mint = mint, error = InvalidMint)]
+    #[owner(*mint.info().owner, error = InvalidMint)]
+    pub to_fees: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
+    /// Created by the token program of the mint, which vouches for the mint.
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
+    #[token(mint = mint, error = InvalidMint)]
+    #[token(owner = custody_signer, error = WrongAccountOwner)]
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    pub mint: Data<'b, TokenMint, { AccountState::Initialized }>,
 
     pub custody_signer: CustodySigner<'b>,
+
+    pub clock: Sysvar<'b, Clock>,
+    #[derivation(&MintLimitDerivationData { mint: *mint.info().key })]
+    pub mint_limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+    #[derivation(&ChainLimitDerivationData { chain: vaa.meta().emitter_chain })]
+    pub chain_limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+    /// Created instead of paying out when the transfer exceeds a limit.
+    #[derivation(&PendingTransferDerivationData::from(vaa.meta()))]
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::Uninitialized }>>,
+    #[derivation(&CustodyLedgerDerivationData { mint: *mint.info().key })]
+    pub ledger: Mut<CustodyLedgerAccount<'b, { AccountState::MaybeInitialized }>>,
 }
 
 impl<'a> From<&CompleteNative<'a>> for EndpointDerivationData {
@@ -79,7 +118,7 @@ impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
 impl<'b> InstructionContext<'b> for CompleteNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct CompleteNativeData {}
 
 pub fn complete_native(
@@ -91,30 +130,16 @@ pub fn complete_native(
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
         .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify mints
-    if *accs.mint.info().key != accs.to.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.to_fees.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.custody.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.custody_signer.key != accs.custody.owner {
-        return Err(WrongAccountOwner.into());
+    if !accs.chain_registration.is_registered() {
+        return Err(EndpointDeregistered.into());
     }
 
     // Verify VAA
     if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
         return Err(InvalidMint.into());
     }
+    token_2022::check_mint(accs.mint.info())?;
+    token_2022::check_account(accs.custody.info(), accs.mint.info())?;
     if accs.vaa.token_chain != 1 {
         return Err(InvalidChain.into());
     }
@@ -129,40 +154,321 @@ pub fn complete_native(
     accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
-    let mut amount = accs.vaa.amount.as_u64();
-    let mut fee = accs.vaa.fee.as_u64();
-
     // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
-    if accs.mint.decimals > 8 {
-        amount *= 10u64.pow((accs.mint.decimals - 8) as u32);
-        fee *= 10u64.pow((accs.mint.decimals - 8) as u32);
+    let amount = denormalize(accs.vaa.amount, accs.mint.decimals)?;
+    let mut fee = denormalize(accs.vaa.fee, accs.mint.decimals)?;
+    if fee > amount {
+        return Err(InvalidFee.into());
+    }
+
+    // The fee is earned by the relayer submitting the transfer, recipients redeeming their own
+    // transfer keep it.
+    if accs.to.owner == *accs.payer.key {
+        fee = 0;
+    } else if fee > 0 && accs.to_fees.owner != *accs.payer.key {
+        return Err(InvalidFeeRecipient.into());
     }
 
+    // Transfers over a limit stay in custody until they are released.
+    if let Some(delay) = consume_limits(
+        &mut [&mut *accs.mint_limit, &mut *accs.chain_limit],
+        accs.vaa.amount.as_u64(),
+        accs.clock.unix_timestamp,
+    ) {
+        accs.pending.create(
+            &PendingTransferDerivationData::from(accs.vaa.meta()),
+            ctx,
+            accs.payer.key,
+            Exempt,
+        )?;
+        *accs.pending.init()? = PendingTransfer {
+            payer: *accs.payer.key,
+            mint: *accs.mint.info().key,
+            to: *accs.to.info().key,
+            to_fees: *accs.to_fees.info().key,
+            amount,
+            fee,
+            native: 1,
+            lamports: 0,
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+            release_after: accs.clock.unix_timestamp.saturating_add(delay as i64),
+            _padding: [0; 4],
+        };
+        return Ok(());
+    }
+
+    open_ledger(
+        ctx,
+        &mut accs.ledger,
+        accs.mint.info().key,
+        accs.payer.key,
+        accs.custody.amount,
+    )?;
+    accs.ledger.balance = accs
+        .ledger
+        .balance
+        .checked_sub(amount)
+        .ok_or(CustodyShortfall)?;
+
     // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
+    let transfer_ix = token_2022::transfer(
+        accs.mint.info().owner,
         accs.custody.info().key,
+        accs.mint.info().key,
         accs.to.info().key,
         accs.custody_signer.key,
-        &[],
-        amount.checked_sub(fee).unwrap(),
+        amount - fee,
+        accs.mint.decimals,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
     // Transfer fees
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
+    let transfer_ix = token_2022::transfer(
+        accs.mint.info().owner,
         accs.custody.info().key,
+        accs.mint.info().key,
         accs.to_fees.info().key,
         accs.custody_signer.key,
-        &[],
         fee,
+        accs.mint.decimals,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
     Ok(())
 }
 
+/// Complete a transfer of native SOL, the wSOL held in custody is unwrapped and the recipient
+/// receives lamports. The VAA must name the recipient wallet rather than a token account.
+#[derive(FromAccounts)]
+pub struct CompleteNativeSol<'b> {
+    /// Fronts the rent of the unwrap account, which is refunded when it is closed.
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: ClaimableVAA<'b, PayloadTransfer>,
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// Wallet receiving the lamports.
+    pub to: Mut<Info<'b>>,
+    /// Wallet of the relayer, the payer, which keeps the fee.
+    pub to_fees: Mut<Info<'b>>,
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
+    #[token(mint = mint, error = InvalidMint)]
+    #[token(owner = custody_signer, error = WrongAccountOwner)]
+    #[owner(spl_token::id())]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
+    /// The wSOL mint, `spl_token::native_mint`.
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    pub unwrap_account: Mut<NativeUnwrapAccount<'b>>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    pub clock: Sysvar<'b, Clock>,
+    #[derivation(&MintLimitDerivationData { mint: *mint.info().key })]
+    pub mint_limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+    #[derivation(&ChainLimitDerivationData { chain: vaa.meta().emitter_chain })]
+    pub chain_limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+    #[derivation(&CustodyLedgerDerivationData { mint: *mint.info().key })]
+    pub ledger: Mut<CustodyLedgerAccount<'b, { AccountState::MaybeInitialized }>>,
+    /// Created instead of paying out when the transfer exceeds a limit.
+    #[derivation(&PendingTransferDerivationData::from(vaa.meta()))]
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::Uninitialized }>>,
+}
+
+impl<'a> From<&CompleteNativeSol<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteNativeSol<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+impl<'b> InstructionContext<'b> for CompleteNativeSol<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct CompleteNativeSolData {}
+
+pub fn complete_native_sol(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteNativeSol,
+    data: CompleteNativeSolData,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+    if !accs.chain_registration.is_registered() {
+        return Err(EndpointDeregistered.into());
+    }
+
+    // Verify VAA
+    if *accs.mint.info().key != spl_token::native_mint::id() {
+        return Err(InvalidMint.into());
+    }
+    if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+    if accs.vaa.token_chain != 1 {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to.key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+
+    // Prevent vaa double signing
+    accs.vaa.verify(ctx.program_id)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
+    let amount = denormalize(accs.vaa.amount, accs.mint.decimals)?;
+    let mut fee = denormalize(accs.vaa.fee, accs.mint.decimals)?;
+    if fee > amount {
+        return Err(InvalidFee.into());
+    }
+
+    // The fee is earned by the relayer submitting the transfer, recipients redeeming their own
+    // transfer keep it.
+    if accs.to.key == accs.payer.key {
+        fee = 0;
+    } else if fee > 0 && accs.to_fees.key != accs.payer.key {
+        return Err(InvalidFeeRecipient.into());
+    }
+
+    // Transfers over a limit stay in custody until they are released. The release unwraps the
+    // amount, the payer also fronts the rent of the unwrap account then, refunded with the rest.
+    if let Some(delay) = consume_limits(
+        &mut [&mut *accs.mint_limit, &mut *accs.chain_limit],
+        accs.vaa.amount.as_u64(),
+        accs.clock.unix_timestamp,
+    ) {
+        let lamports = Exempt.amount(accs.pending.size()) + Exempt.amount(Account::LEN);
+        accs.pending.create(
+            &PendingTransferDerivationData::from(accs.vaa.meta()),
+            ctx,
+            accs.payer.key,
+            CreationLamports::Amount(lamports),
+        )?;
+        *accs.pending.init()? = PendingTransfer {
+            payer: *accs.payer.key,
+            mint: *accs.mint.info().key,
+            to: *accs.to.key,
+            to_fees: *accs.to_fees.key,
+            amount,
+            fee,
+            native: 1,
+            lamports: 1,
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+            release_after: accs.clock.unix_timestamp.saturating_add(delay as i64),
+            _padding: [0; 4],
+        };
+        return Ok(());
+    }
+
+    open_ledger(
+        ctx,
+        &mut accs.ledger,
+        accs.mint.info().key,
+        accs.payer.key,
+        accs.custody.amount,
+    )?;
+    accs.ledger.balance = accs
+        .ledger
+        .balance
+        .checked_sub(amount)
+        .ok_or(CustodyShortfall)?;
+
+    // Lamports can only be taken out of a native token account by closing it, the custody account
+    // has to stay so the amount is moved into a temporary account which is closed instead.
+    //
+    // The account is set up by hand rather than with create_account, which fails if anyone sent
+    // lamports to the address beforehand.
+    let rent = Exempt.amount(Account::LEN);
+    let missing = rent.saturating_sub(accs.unwrap_account.lamports());
+    if missing > 0 {
+        let transfer_ix = solana_program::system_instruction::transfer(
+            accs.payer.key,
+            accs.unwrap_account.key,
+            missing,
+        );
+        invoke(&transfer_ix, ctx.accounts)?;
+    }
+
+    // Closing releases the amount together with the rent to the payer, who keeps the fee and pays
+    // out the rest to the recipient.
+    unwrap_custody(
+        ctx,
+        &accs.unwrap_account,
+        accs.custody.info().key,
+        accs.mint.info().key,
+        &accs.custody_signer,
+        amount,
+        accs.payer.key,
+    )?;
+
+    if accs.to.key != accs.payer.key {
+        let transfer_ix =
+            solana_program::system_instruction::transfer(accs.payer.key, accs.to.key, amount - fee);
+        invoke(&transfer_ix, ctx.accounts)?;
+    }
+
+    Ok(())
+}
+
+/// Move `amount` of wSOL out of custody through the funded unwrap account, which is closed to
+/// `destination` with its rent.
+pub fn unwrap_custody(
+    ctx: &ExecutionContext,
+    unwrap_account: &NativeUnwrapAccount,
+    custody: &Pubkey,
+    mint: &Pubkey,
+    custody_signer: &CustodySigner,
+    amount: u64,
+    destination: &Pubkey,
+) -> Result<()> {
+    let allocate_ix =
+        solana_program::system_instruction::allocate(unwrap_account.key, Account::LEN as u64);
+    invoke_seeded(&allocate_ix, ctx, unwrap_account, None)?;
+    let assign_ix =
+        solana_program::system_instruction::assign(unwrap_account.key, &spl_token::id());
+    invoke_seeded(&assign_ix, ctx, unwrap_account, None)?;
+
+    let init_ix = spl_token::instruction::initialize_account(
+        &spl_token::id(),
+        unwrap_account.key,
+        mint,
+        custody_signer.key,
+    )?;
+    invoke_signed(&init_ix, ctx.accounts, &[])?;
+
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        custody,
+        unwrap_account.key,
+        custody_signer.key,
+        &[],
+        amount,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, custody_signer, None)?;
+
+    let close_ix = spl_token::instruction::close_account(
+        &spl_token::id(),
+        unwrap_account.key,
+        destination,
+        custody_signer.key,
+        &[],
+    )?;
+    invoke_seeded(&close_ix, ctx, custody_signer, None)?;
+
+    Ok(())
+}
+
 #[derive(FromAccounts)]
 pub struct CompleteWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
@@ -173,12 +479,24 @@ pub struct CompleteWrapped<'b> {
 
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
+    #[token(mint = mint, error = InvalidMint)]
     pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    /// Token account of the relayer, it must be owned by the payer.
+    #[token(mint = mint, error = InvalidMint)]
     pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
     pub mint_authority: MintSigner<'b>,
+
+    pub clock: Sysvar<'b, Clock>,
+    #[derivation(&MintLimitDerivationData { mint: *mint.info().key })]
+    pub mint_limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+    #[derivation(&ChainLimitDerivationData { chain: vaa.meta().emitter_chain })]
+    pub chain_limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+    /// Created instead of paying out when the transfer exceeds a limit.
+    #[derivation(&PendingTransferDerivationData::from(vaa.meta()))]
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::Uninitialized }>>,
 }
 
 impl<'a> From<&CompleteWrapped<'a>> for EndpointDerivationData {
@@ -202,7 +520,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -214,6 +532,9 @@ pub fn complete_wrapped(
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
         .verify_derivation(ctx.program_id, &derivation_data)?;
+    if !accs.chain_registration.is_registered() {
+        return Err(EndpointDeregistered.into());
+    }
 
     // Verify mint
     accs.wrapped_meta.verify_derivation(
@@ -228,14 +549,6 @@ pub fn complete_wrapped(
         return Err(InvalidMint.into());
     }
 
-    // Verify mints
-    if *accs.mint.info().key != accs.to.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.to_fees.mint {
-        return Err(InvalidMint.into());
-    }
-
     // Verify VAA
     if accs.vaa.to_chain != CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
@@ -247,6 +560,49 @@ pub fn complete_wrapped(
     accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
+    let amount = denormalize(accs.vaa.amount, accs.mint.decimals)?;
+    let mut fee = denormalize(accs.vaa.fee, accs.mint.decimals)?;
+    if fee > amount {
+        return Err(InvalidFee.into());
+    }
+
+    // The fee is earned by the relayer submitting the transfer, recipients redeeming their own
+    // transfer keep it.
+    if accs.to.owner == *accs.payer.key {
+        fee = 0;
+    } else if fee > 0 && accs.to_fees.owner != *accs.payer.key {
+        return Err(InvalidFeeRecipient.into());
+    }
+
+    // Transfers over a limit are minted once they are released.
+    if let Some(delay) = consume_limits(
+        &mut [&mut *accs.mint_limit, &mut *accs.chain_limit],
+        accs.vaa.amount.as_u64(),
+        accs.clock.unix_timestamp,
+    ) {
+        accs.pending.create(
+            &PendingTransferDerivationData::from(accs.vaa.meta()),
+            ctx,
+            accs.payer.key,
+            Exempt,
+        )?;
+        *accs.pending.init()? = PendingTransfer {
+            payer: *accs.payer.key,
+            mint: *accs.mint.info().key,
+            to: *accs.to.info().key,
+            to_fees: *accs.to_fees.info().key,
+            amount,
+            fee,
+            native: 0,
+            lamports: 0,
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+            release_after: accs.clock.unix_timestamp.saturating_add(delay as i64),
+            _padding: [0; 4],
+        };
+        return Ok(());
+    }
+
     // Mint tokens
     let mint_ix = spl_token::instruction::mint_to(
         &spl_token::id(),
@@ -254,11 +610,7 @@ pub fn complete_wrapped(
         accs.to.info().key,
         accs.mint_authority.key,
         &[],
-        accs.vaa
-            .amount
-            .as_u64()
-            .checked_sub(accs.vaa.fee.as_u64())
-            .unwrap(),
+        amount - fee,
     )?;
     invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
 
@@ -269,7 +621,7 @@ pub fn complete_wrapped(
         accs.to_fees.info().key,
         accs.mint_authority.key,
         &[],
-        accs.vaa.fee.as_u64(),
+        fee,
     )?;
     invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
 
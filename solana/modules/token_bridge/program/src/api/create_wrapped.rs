@@ -8,12 +8,15 @@ use crate::{
         SplTokenMetaDerivationData,
         WrappedDerivationData,
         WrappedMetaDerivationData,
+        WrappedMetadataOverrideAccount,
+        WrappedMetadataOverrideDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
     messages::PayloadAssetMeta,
     types::*,
     TokenBridgeError::{
+        EndpointDeregistered,
         InvalidChain,
         InvalidMetadata,
     },
@@ -46,6 +49,8 @@ use spl_token::{
 use spl_token_metadata::state::{
     Data as SplData,
     Metadata,
+    MAX_NAME_LENGTH,
+    MAX_SYMBOL_LENGTH,
 };
 use std::{
     cmp::min,
@@ -71,6 +76,10 @@ pub struct CreateWrapped<'b> {
     pub spl_metadata: Mut<SplTokenMeta<'b>>,
 
     pub mint_authority: MintSigner<'b>,
+
+    /// Metadata set by governance, attestations re-apply it instead of their own.
+    #[derivation(&WrappedMetadataOverrideDerivationData { mint_key: *mint.info().key })]
+    pub metadata_override: WrappedMetadataOverrideAccount<'b, { AccountState::MaybeInitialized }>,
 }
 
 impl<'a> From<&CreateWrapped<'a>> for EndpointDerivationData {
@@ -102,7 +111,7 @@ impl<'a> From<&CreateWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CreateWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct CreateWrappedData {}
 
 pub fn create_wrapped(
@@ -126,6 +135,9 @@ pub fn create_wrapped(
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
         .verify_derivation(ctx.program_id, &derivation_data)?;
+    if !accs.chain_registration.is_registered() {
+        return Err(EndpointDeregistered.into());
+    }
 
     accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
@@ -169,8 +181,8 @@ pub fn create_accounts(
     )?;
 
     // Normalize Token Metadata.
-    let name = truncate_utf8(&accs.vaa.name, 32 - 11) + " (Wormhole)";
-    let symbol = truncate_utf8(&accs.vaa.symbol, 10);
+    let name = wrapped_name(&accs.vaa.name, accs.vaa.token_chain);
+    let symbol = wrapped_symbol(&accs.vaa.symbol);
 
     let spl_token_metadata_ix = spl_token_metadata::instruction::create_metadata_accounts(
         spl_token_metadata::id(),
@@ -213,9 +225,16 @@ pub fn update_accounts(
         .ok_or(InvalidMetadata)?
         .data;
 
-    // Normalize token metadata.
-    metadata.name = truncate_utf8(&accs.vaa.name, 32 - 11) + " (Wormhole)";
-    metadata.symbol = truncate_utf8(&accs.vaa.symbol, 10);
+    // Metadata set by governance takes precedence over the attestation and is re-applied.
+    if accs.metadata_override.is_initialized() {
+        metadata.name = wrapped_name(&accs.metadata_override.name, accs.vaa.token_chain);
+        metadata.symbol = wrapped_symbol(&accs.metadata_override.symbol);
+        metadata.uri = accs.metadata_override.uri.clone();
+    } else {
+        // Normalize token metadata.
+        metadata.name = wrapped_name(&accs.vaa.name, accs.vaa.token_chain);
+        metadata.symbol = wrapped_symbol(&accs.vaa.symbol);
+    }
 
     // Update SPL Metadata
     let spl_token_metadata_ix = spl_token_metadata::instruction::update_metadata_accounts(
@@ -231,6 +250,32 @@ pub fn update_accounts(
     Ok(())
 }
 
+/// Short name of a chain, used to mark wrapped tokens with their origin.
+fn chain_name(chain: ChainID) -> String {
+    match chain {
+        2 => "ETH".to_string(),
+        3 => "Terra".to_string(),
+        4 => "BSC".to_string(),
+        5 => "Polygon".to_string(),
+        6 => "AVAX".to_string(),
+        7 => "Oasis".to_string(),
+        8 => "Algorand".to_string(),
+        9 => "Aurora".to_string(),
+        10 => "Fantom".to_string(),
+        _ => format!("Chain {}", chain),
+    }
+}
+
+/// Display name of a wrapped token, suffixed with the chain it originates from.
+pub fn wrapped_name(name: impl AsRef<[u8]>, token_chain: ChainID) -> String {
+    let suffix = format!(" (Wormhole {})", chain_name(token_chain));
+    truncate_utf8(name, MAX_NAME_LENGTH - suffix.len()) + &suffix
+}
+
+pub fn wrapped_symbol(symbol: impl AsRef<[u8]>) -> String {
+    truncate_utf8(symbol, MAX_SYMBOL_LENGTH)
+}
+
 // Byte-truncates potentially invalid UTF-8 encoded strings by converting to Unicode codepoints and
 // stripping unrecognised characters.
 pub fn truncate_utf8(data: impl AsRef<[u8]>, len: usize) -> String {
@@ -288,4 +333,20 @@ mod tests {
             assert_eq!(expected, super::truncate_utf8(input, 32));
         }
     }
+
+    #[test]
+    fn test_wrapped_name() {
+        assert_eq!(super::wrapped_name("Ether", 2), "Ether (Wormhole ETH)");
+        assert_eq!(
+            super::wrapped_name("A very long token name", 3),
+            "A very long tok (Wormhole Terra)"
+        );
+        assert_eq!(
+            super::wrapped_name("Token", 42),
+            "Token (Wormhole Chain 42)"
+        );
+        for chain in 0..12 {
+            assert!(super::wrapped_name("🔥🔥🔥🔥🔥🔥🔥🔥🔥", chain).len() <= 32);
+        }
+    }
 }
@@ -0,0 +1,131 @@
+use crate::{
+    accounts::{
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodyLedgerAccount,
+        CustodyLedgerDerivationData,
+    },
+    events::{
+        emit,
+        TokenBridgeEvent,
+    },
+    token_2022,
+    types::*,
+    TokenBridgeError::CustodyShortfall,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    program::invoke_signed,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solitaire::{
+    processors::seeded::invoke_seeded,
+    CreationLamports::Exempt,
+    *,
+};
+use spl_token::state::Account;
+use std::ops::{
+    Deref,
+    DerefMut,
+};
+
+/// Create the custody account of `mint` if it does not exist yet. The account belongs to the token
+/// program of the mint, which `Creatable` cannot express for token accounts of either program.
+pub fn open_custody(
+    ctx: &ExecutionContext,
+    custody: &CustodyAccount<'_, { AccountState::MaybeInitialized }>,
+    mint: &AccountInfo,
+    custody_signer: &Pubkey,
+    payer: &Pubkey,
+) -> Result<()> {
+    if custody.is_initialized() {
+        return token_2022::check_account(custody.info(), mint);
+    }
+
+    let create_ix = system_instruction::create_account(
+        payer,
+        custody.info().key,
+        Exempt.amount(Account::LEN),
+        Account::LEN as u64,
+        mint.owner,
+    );
+    invoke_seeded(
+        &create_ix,
+        ctx,
+        custody,
+        &CustodyAccountDerivationData { mint: *mint.key },
+    )?;
+
+    let init_ix =
+        token_2022::initialize_account(mint.owner, custody.info().key, mint.key, custody_signer)?;
+    invoke_signed(&init_ix, ctx.accounts, &[])?;
+    Ok(())
+}
+
+/// Create the ledger of the custody account of `mint` if it does not exist yet. Custody accounts
+/// predating their ledger are taken to hold the right balance, `custody_balance`, at this point.
+pub fn open_ledger(
+    ctx: &ExecutionContext,
+    ledger: &mut CustodyLedgerAccount<'_, { AccountState::MaybeInitialized }>,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    custody_balance: u64,
+) -> Result<()> {
+    if !ledger.is_initialized() {
+        let derivation_data = CustodyLedgerDerivationData { mint: *mint };
+        let bump = ledger.derivation_bump(ctx.program_id, &derivation_data)?;
+        ledger.create_with_bump(&derivation_data, ctx, payer, Exempt, bump)?;
+        ledger.balance = custody_balance;
+        ledger.bump = bump;
+    }
+    Ok(())
+}
+
+/// Compare the balance of a custody account with its ledger. Anyone can audit a custody account,
+/// the instruction fails if the account holds less than the ledger records. A surplus, such as
+/// tokens sent to the custody account directly, is not an error and is logged as a
+/// `CustodySurplus` event.
+#[derive(FromAccounts)]
+pub struct AuditCustody<'b> {
+    pub mint: Data<'b, TokenMint, { AccountState::Initialized }>,
+
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
+    pub custody: CustodyAccount<'b, { AccountState::Initialized }>,
+
+    #[derivation(&CustodyLedgerDerivationData { mint: *mint.info().key })]
+    pub ledger: CustodyLedgerAccount<'b, { AccountState::Initialized }>,
+}
+
+impl<'b> InstructionContext<'b> for AuditCustody<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct AuditCustodyData {}
+
+pub fn audit_custody(
+    _ctx: &ExecutionContext,
+    accs: &mut AuditCustody,
+    _data: AuditCustodyData,
+) -> Result<()> {
+    token_2022::check_mint(accs.mint.info())?;
+    token_2022::check_account(accs.custody.info(), accs.mint.info())?;
+
+    let balance = accs.custody.amount;
+    let expected = accs.ledger.balance;
+
+    if balance < expected {
+        return Err(CustodyShortfall.into());
+    }
+    if balance > expected {
+        emit(TokenBridgeEvent::CustodySurplus {
+            mint: accs.mint.info().key.to_bytes(),
+            ledger: expected,
+            balance,
+        });
+    }
+
+    Ok(())
+}
@@ -1,17 +1,37 @@
 use crate::{
     accounts::{
+        ChainLimitDerivationData,
         ConfigAccount,
         Endpoint,
         EndpointDerivationData,
+        MintLimitDerivationData,
+        MintSigner,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        TransferLimitAccount,
+        WrappedDerivationData,
+        WrappedMetadataOverrideAccount,
+        WrappedMetadataOverrideDerivationData,
+        WrappedMint,
+    },
+    api::{
+        wrapped_name,
+        wrapped_symbol,
     },
     messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetMintLimit,
+        GovernancePayloadSetWrappedMetadata,
+        GovernancePayloadUpdateChain,
         GovernancePayloadUpgrade,
         PayloadGovernanceRegisterChain,
     },
     types::*,
     TokenBridgeError::{
+        EndpointDeregistered,
         InvalidChain,
         InvalidGovernanceKey,
+        InvalidMetadata,
     },
 };
 use bridge::{
@@ -33,10 +53,18 @@ use solana_program::{
     },
 };
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
     CreationLamports::Exempt,
     *,
 };
+use spl_token_metadata::state::{
+    Data as SplData,
+    Metadata,
+    MAX_URI_LENGTH,
+};
 use std::ops::{
     Deref,
     DerefMut,
@@ -93,7 +121,7 @@ pub struct UpgradeContract<'b> {
 impl<'b> InstructionContext<'b> for UpgradeContract<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -128,7 +156,8 @@ pub struct RegisterChain<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
-    pub endpoint: Mut<Endpoint<'b, { AccountState::Uninitialized }>>,
+    /// Exists already if the endpoint was deregistered, registering it again re-enables it.
+    pub endpoint: Mut<Endpoint<'b, { AccountState::MaybeInitialized }>>,
 
     pub vaa: ClaimableVAA<'b, PayloadGovernanceRegisterChain>,
 }
@@ -145,7 +174,7 @@ impl<'a> From<&RegisterChain<'a>> for EndpointDerivationData {
 impl<'b> InstructionContext<'b> for RegisterChain<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct RegisterChainData {}
 
 pub fn register_chain(
@@ -163,11 +192,274 @@ pub fn register_chain(
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     // Create endpoint
-    accs.endpoint
-        .create(&((&*accs).into()), ctx, accs.payer.key, Exempt)?;
+    if !accs.endpoint.is_initialized() {
+        accs.endpoint
+            .create(&((&*accs).into()), ctx, accs.payer.key, Exempt)?;
+    } else if accs.endpoint.is_registered() {
+        return Err(SolitaireError::AlreadyInitialized(
+            *accs.endpoint.info().key,
+        ));
+    }
 
     accs.endpoint.chain = accs.vaa.chain;
     accs.endpoint.contract = accs.vaa.endpoint_address;
 
     Ok(())
 }
+
+/// Deregister the endpoint of a chain and optionally register its replacement. Transfers and
+/// attestations emitted by a deregistered endpoint are rejected, a replacement which was
+/// deregistered before is registered again.
+#[derive(FromAccounts)]
+pub struct UpdateChain<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: ClaimableVAA<'b, GovernancePayloadUpdateChain>,
+
+    #[derivation(&EndpointDerivationData {
+        emitter_chain: vaa.chain,
+        emitter_address: vaa.endpoint_address,
+    })]
+    pub endpoint: Mut<Endpoint<'b, { AccountState::Initialized }>>,
+
+    /// Not used when the endpoint is only deregistered.
+    #[derivation(&EndpointDerivationData {
+        emitter_chain: vaa.chain,
+        emitter_address: vaa.new_endpoint_address,
+    })]
+    pub new_endpoint: Mut<Endpoint<'b, { AccountState::MaybeInitialized }>>,
+}
+
+impl<'b> InstructionContext<'b> for UpdateChain<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct UpdateChainData {}
+
+pub fn update_chain(
+    ctx: &ExecutionContext,
+    accs: &mut UpdateChain,
+    _data: UpdateChainData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    accs.vaa.verify(ctx.program_id)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    if !accs.endpoint.is_registered() {
+        return Err(EndpointDeregistered.into());
+    }
+    // Both accounts would be the same, and the second one persisted would win.
+    if accs.vaa.new_endpoint_address == accs.vaa.endpoint_address {
+        return Err(InvalidChain.into());
+    }
+
+    accs.endpoint.contract = Address::default();
+
+    if accs.vaa.deregisters() {
+        return Ok(());
+    }
+
+    if !accs.new_endpoint.is_initialized() {
+        accs.new_endpoint.create(
+            &EndpointDerivationData {
+                emitter_chain: accs.vaa.chain,
+                emitter_address: accs.vaa.new_endpoint_address,
+            },
+            ctx,
+            accs.payer.key,
+            Exempt,
+        )?;
+    } else if accs.new_endpoint.is_registered() {
+        return Err(SolitaireError::AlreadyInitialized(
+            *accs.new_endpoint.info().key,
+        ));
+    }
+
+    accs.new_endpoint.chain = accs.vaa.chain;
+    accs.new_endpoint.contract = accs.vaa.new_endpoint_address;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetMintLimit<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetMintLimit>,
+
+    #[derivation(&MintLimitDerivationData { mint: vaa.mint })]
+    pub limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'b> InstructionContext<'b> for SetMintLimit<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct SetMintLimitData {}
+
+pub fn set_mint_limit(
+    ctx: &ExecutionContext,
+    accs: &mut SetMintLimit,
+    _data: SetMintLimitData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    accs.vaa.verify(ctx.program_id)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    if !accs.limit.is_initialized() {
+        let derivation_data = MintLimitDerivationData {
+            mint: accs.vaa.mint,
+        };
+        let bump = accs
+            .limit
+            .derivation_bump(ctx.program_id, &derivation_data)?;
+        accs.limit
+            .create_with_bump(&derivation_data, ctx, accs.payer.key, Exempt, bump)?;
+        accs.limit.bump = bump;
+    }
+
+    accs.limit.configure(
+        accs.vaa.limit,
+        accs.vaa.window,
+        accs.vaa.delay,
+        accs.clock.unix_timestamp,
+    );
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetChainLimit<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetChainLimit>,
+
+    #[derivation(&ChainLimitDerivationData { chain: vaa.chain })]
+    pub limit: Mut<TransferLimitAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'b> InstructionContext<'b> for SetChainLimit<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct SetChainLimitData {}
+
+pub fn set_chain_limit(
+    ctx: &ExecutionContext,
+    accs: &mut SetChainLimit,
+    _data: SetChainLimitData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    accs.vaa.verify(ctx.program_id)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    if !accs.limit.is_initialized() {
+        let derivation_data = ChainLimitDerivationData {
+            chain: accs.vaa.chain,
+        };
+        let bump = accs
+            .limit
+            .derivation_bump(ctx.program_id, &derivation_data)?;
+        accs.limit
+            .create_with_bump(&derivation_data, ctx, accs.payer.key, Exempt, bump)?;
+        accs.limit.bump = bump;
+    }
+
+    accs.limit.configure(
+        accs.vaa.limit,
+        accs.vaa.window,
+        accs.vaa.delay,
+        accs.clock.unix_timestamp,
+    );
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetWrappedMetadata<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetWrappedMetadata>,
+
+    #[derivation(&WrappedDerivationData {
+        token_chain: vaa.token_chain,
+        token_address: vaa.token_address,
+    })]
+    pub mint: WrappedMint<'b, { AccountState::Initialized }>,
+
+    #[derivation(&WrappedMetadataOverrideDerivationData { mint_key: *mint.info().key })]
+    pub metadata_override:
+        Mut<WrappedMetadataOverrideAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    /// SPL Metadata for the associated Mint
+    #[derivation(
+        &SplTokenMetaDerivationData { mint: *mint.info().key },
+        program = spl_token_metadata::id(),
+    )]
+    pub spl_metadata: Mut<SplTokenMeta<'b>>,
+
+    pub mint_authority: MintSigner<'b>,
+}
+
+impl<'b> InstructionContext<'b> for SetWrappedMetadata<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct SetWrappedMetadataData {}
+
+/// Replace the display metadata of a wrapped token, for tokens whose attested metadata is
+/// misleading. Later attestations no longer change it.
+pub fn set_wrapped_metadata(
+    ctx: &ExecutionContext,
+    accs: &mut SetWrappedMetadata,
+    _data: SetWrappedMetadataData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    accs.vaa.verify(ctx.program_id)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    if accs.vaa.uri.len() > MAX_URI_LENGTH {
+        return Err(InvalidMetadata.into());
+    }
+
+    if !accs.metadata_override.is_initialized() {
+        accs.metadata_override.create(
+            &WrappedMetadataOverrideDerivationData {
+                mint_key: *accs.mint.info().key,
+            },
+            ctx,
+            accs.payer.key,
+            Exempt,
+        )?;
+    }
+    accs.metadata_override.symbol = accs.vaa.symbol.clone();
+    accs.metadata_override.name = accs.vaa.name.clone();
+    accs.metadata_override.uri = accs.vaa.uri.clone();
+
+    let mut metadata: SplData = Metadata::from_account_info(accs.spl_metadata.info())
+        .ok_or(InvalidMetadata)?
+        .data;
+    metadata.name = wrapped_name(&accs.vaa.name, accs.vaa.token_chain);
+    metadata.symbol = wrapped_symbol(&accs.vaa.symbol);
+    metadata.uri = accs.vaa.uri.clone();
+
+    let spl_token_metadata_ix = spl_token_metadata::instruction::update_metadata_accounts(
+        spl_token_metadata::id(),
+        *accs.spl_metadata.key,
+        *accs.mint_authority.info().key,
+        None,
+        Some(metadata),
+        None,
+    );
+    invoke_seeded(&spl_token_metadata_ix, ctx, &accs.mint_authority, None)?;
+
+    Ok(())
+}
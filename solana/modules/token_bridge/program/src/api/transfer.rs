@@ -3,8 +3,11 @@ use crate::{
         AuthoritySigner,
         ConfigAccount,
         CoreBridge,
+        CoreEmitterFee,
         CustodyAccount,
         CustodyAccountDerivationData,
+        CustodyLedgerAccount,
+        CustodyLedgerDerivationData,
         CustodySigner,
         EmitterAccount,
         MintSigner,
@@ -13,17 +16,28 @@ use crate::{
         WrappedMint,
         WrappedTokenMeta,
     },
+    api::{
+        open_custody,
+        open_ledger,
+    },
+    decimals::normalize,
     messages::PayloadTransfer,
+    token_2022,
     types::*,
     TokenBridgeError,
     TokenBridgeError::{
+        AmountOverflow,
         InvalidChain,
         InvalidFee,
+        InvalidMint,
         WrongAccountOwner,
     },
 };
 use bridge::{
-    accounts::Bridge,
+    accounts::{
+        effective_fee,
+        Bridge,
+    },
     api::{
         PostMessage,
         PostMessageData,
@@ -39,10 +53,7 @@ use solana_program::{
         AccountMeta,
         Instruction,
     },
-    program::{
-        invoke,
-        invoke_signed,
-    },
+    program::invoke,
     program_error::ProgramError,
     program_option::COption,
     pubkey::Pubkey,
@@ -53,7 +64,6 @@ use solitaire::{
         invoke_seeded,
         Seeded,
     },
-    CreationLamports::Exempt,
     *,
 };
 use spl_token::{
@@ -74,10 +84,14 @@ pub struct TransferNative<'b> {
 
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
-    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[token(mint = mint, error = TokenBridgeError::InvalidMint)]
+    #[owner(*mint.info().owner, error = TokenBridgeError::InvalidMint)]
+    pub from: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
 
-    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+    /// Mint of either token program, checked by `token_2022::check_mint`.
+    pub mint: Mut<Data<'b, TokenMint, { AccountState::Initialized }>>,
 
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -102,20 +116,18 @@ pub struct TransferNative<'b> {
     pub fee_collector: Mut<Info<'b>>,
 
     pub clock: Sysvar<'b, Clock>,
-}
 
-impl<'a> From<&TransferNative<'a>> for CustodyAccountDerivationData {
-    fn from(accs: &TransferNative<'a>) -> Self {
-        CustodyAccountDerivationData {
-            mint: *accs.mint.info().key,
-        }
-    }
+    /// Fee override of the emitter in the core bridge
+    pub emitter_fee: CoreEmitterFee<'b, { AccountState::MaybeInitialized }>,
+
+    #[derivation(&CustodyLedgerDerivationData { mint: *mint.info().key })]
+    pub ledger: Mut<CustodyLedgerAccount<'b, { AccountState::MaybeInitialized }>>,
 }
 
 impl<'b> InstructionContext<'b> for TransferNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub amount: u64,
@@ -134,16 +146,6 @@ pub fn transfer_native(
         return Err(InvalidChain.into());
     }
 
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify mints
-    if accs.from.mint != *accs.mint.info().key {
-        return Err(TokenBridgeError::InvalidMint.into());
-    }
-
     // Fee must be less than amount
     if data.fee > data.amount {
         return Err(InvalidFee.into());
@@ -156,53 +158,216 @@ pub fn transfer_native(
         }
     }
 
-    if !accs.custody.is_initialized() {
-        accs.custody
-            .create(&(&*accs).into(), ctx, accs.payer.key, Exempt)?;
-
-        let init_ix = spl_token::instruction::initialize_account(
-            &spl_token::id(),
-            accs.custody.info().key,
-            accs.mint.info().key,
-            accs.custody_signer.key,
-        )?;
-        invoke_signed(&init_ix, ctx.accounts, &[])?;
-    }
+    token_2022::check_mint(accs.mint.info())?;
+
+    open_custody(
+        ctx,
+        &accs.custody,
+        accs.mint.info(),
+        accs.custody_signer.key,
+        accs.payer.key,
+    )?;
 
-    let trunc_divisor = 10u64.pow(8.max(accs.mint.decimals as u32) - 8);
-    // Truncate to 8 decimals
-    let amount: u64 = data.amount / trunc_divisor;
-    let fee: u64 = data.fee / trunc_divisor;
-    // Untruncate the amount to drop the remainder so we don't  "burn" user's funds.
-    let amount_trunc: u64 = amount * trunc_divisor;
+    // Only the amount representable in the payload is taken, the remainder stays with the sender.
+    let transfer = normalize(data.amount, data.fee, accs.mint.decimals)?;
 
     // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
+    let transfer_ix = token_2022::transfer(
+        accs.mint.info().owner,
         accs.from.info().key,
+        accs.mint.info().key,
         accs.custody.info().key,
         accs.authority_signer.key,
-        &[],
-        amount_trunc,
+        transfer.transferred,
+        accs.mint.decimals,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.authority_signer, None)?;
 
+    open_ledger(
+        ctx,
+        &mut accs.ledger,
+        accs.mint.info().key,
+        accs.payer.key,
+        accs.custody.amount,
+    )?;
+    accs.ledger.balance = accs
+        .ledger
+        .balance
+        .checked_add(transfer.transferred)
+        .ok_or(AmountOverflow)?;
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        effective_fee(&accs.bridge.config, &accs.emitter_fee),
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post message
+    let payload = PayloadTransfer {
+        amount: U256::from(transfer.amount),
+        token_address: accs.mint.info().key.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(transfer.fee),
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(*accs.emitter_fee.info().key, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+/// Transfer native SOL, the lamports are wrapped into the wSOL custody account so SOL leaves
+/// Solana as the wSOL token and the user does not need to hold wSOL.
+#[derive(FromAccounts)]
+pub struct TransferNativeSol<'b> {
+    /// Pays the transferred lamports along with the fees.
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// The wSOL mint, `spl_token::native_mint`.
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+
+    /// Fee override of the emitter in the core bridge
+    pub emitter_fee: CoreEmitterFee<'b, { AccountState::MaybeInitialized }>,
+
+    #[derivation(&CustodyLedgerDerivationData { mint: *mint.info().key })]
+    pub ledger: Mut<CustodyLedgerAccount<'b, { AccountState::MaybeInitialized }>>,
+}
+
+impl<'b> InstructionContext<'b> for TransferNativeSol<'b> {
+}
+
+/// Amounts are in lamports.
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct TransferNativeSolData {
+    pub nonce: u32,
+    pub amount: u64,
+    pub fee: u64,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+}
+
+pub fn transfer_native_sol(
+    ctx: &ExecutionContext,
+    accs: &mut TransferNativeSol,
+    data: TransferNativeSolData,
+) -> Result<()> {
+    // Prevent transferring to the same chain.
+    if data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    // Fee must be less than amount
+    if data.fee > data.amount {
+        return Err(InvalidFee.into());
+    }
+
+    if *accs.mint.info().key != spl_token::native_mint::id() {
+        return Err(InvalidMint.into());
+    }
+
+    open_custody(
+        ctx,
+        &accs.custody,
+        accs.mint.info(),
+        accs.custody_signer.key,
+        accs.payer.key,
+    )?;
+
+    // Only the amount representable in the payload is taken, the remainder stays with the sender.
+    let transfer = normalize(data.amount, data.fee, accs.mint.decimals)?;
+
+    // Wrap the lamports, the custody account is a native token account so the lamports only need
+    // to be synced into its token balance.
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.custody.info().key,
+        transfer.transferred,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    open_ledger(
+        ctx,
+        &mut accs.ledger,
+        accs.mint.info().key,
+        accs.payer.key,
+        accs.custody.amount,
+    )?;
+    accs.ledger.balance = accs
+        .ledger
+        .balance
+        .checked_add(transfer.transferred)
+        .ok_or(AmountOverflow)?;
+
+    let sync_ix = spl_token::instruction::sync_native(&spl_token::id(), accs.custody.info().key)?;
+    invoke(&sync_ix, ctx.accounts)?;
+
     // Pay fee
     let transfer_ix = solana_program::system_instruction::transfer(
         accs.payer.key,
         accs.fee_collector.key,
-        accs.bridge.config.fee,
+        effective_fee(&accs.bridge.config, &accs.emitter_fee),
     );
     invoke(&transfer_ix, ctx.accounts)?;
 
     // Post message
     let payload = PayloadTransfer {
-        amount: U256::from(amount),
+        amount: U256::from(transfer.amount),
         token_address: accs.mint.info().key.to_bytes(),
         token_chain: CHAIN_ID_SOLANA,
         to: data.target_address,
         to_chain: data.target_chain,
-        fee: U256::from(fee),
+        fee: U256::from(transfer.fee),
     };
     let params = (
         bridge::instruction::Instruction::PostMessage,
@@ -226,6 +391,7 @@ pub fn transfer_native(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(*accs.emitter_fee.info().key, false),
         ],
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
@@ -238,6 +404,8 @@ pub struct TransferWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
+    #[token(owner = from_owner, error = WrongAccountOwner)]
+    #[token(mint = mint, error = TokenBridgeError::InvalidMint)]
     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub from_owner: MaybeMut<Signer<Info<'b>>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
@@ -261,6 +429,9 @@ pub struct TransferWrapped<'b> {
     pub fee_collector: Mut<Info<'b>>,
 
     pub clock: Sysvar<'b, Clock>,
+
+    /// Fee override of the emitter in the core bridge
+    pub emitter_fee: CoreEmitterFee<'b, { AccountState::MaybeInitialized }>,
 }
 
 impl<'a> From<&TransferWrapped<'a>> for WrappedDerivationData {
@@ -283,7 +454,7 @@ impl<'a> From<&TransferWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub amount: u64,
@@ -302,16 +473,6 @@ pub fn transfer_wrapped(
         return Err(InvalidChain.into());
     }
 
-    // Verify that the from account is owned by the from_owner
-    if &accs.from.owner != accs.from_owner.key {
-        return Err(WrongAccountOwner.into());
-    }
-
-    // Verify mints
-    if accs.mint.info().key != &accs.from.mint {
-        return Err(TokenBridgeError::InvalidMint.into());
-    }
-
     // Fee must be less than amount
     if data.fee > data.amount {
         return Err(InvalidFee.into());
@@ -322,6 +483,9 @@ pub fn transfer_wrapped(
     accs.wrapped_meta
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
+    // Wrapped mints have at most 8 decimals, nothing is truncated but empty transfers are rejected.
+    let transfer = normalize(data.amount, data.fee, accs.mint.decimals)?;
+
     // Burn tokens
     let burn_ix = spl_token::instruction::burn(
         &spl_token::id(),
@@ -329,7 +493,7 @@ pub fn transfer_wrapped(
         accs.mint.info().key,
         accs.authority_signer.key,
         &[],
-        data.amount,
+        transfer.transferred,
     )?;
     invoke_seeded(&burn_ix, ctx, &accs.authority_signer, None)?;
 
@@ -337,19 +501,19 @@ pub fn transfer_wrapped(
     let transfer_ix = solana_program::system_instruction::transfer(
         accs.payer.key,
         accs.fee_collector.key,
-        accs.bridge.config.fee,
+        effective_fee(&accs.bridge.config, &accs.emitter_fee),
     );
 
     invoke(&transfer_ix, ctx.accounts)?;
 
     // Post message
     let payload = PayloadTransfer {
-        amount: U256::from(data.amount),
+        amount: U256::from(transfer.amount),
         token_address: accs.wrapped_meta.token_address,
         token_chain: accs.wrapped_meta.chain,
         to: data.target_address,
         to_chain: data.target_chain,
-        fee: U256::from(data.fee),
+        fee: U256::from(transfer.fee),
     };
     let params = (
         bridge::instruction::Instruction::PostMessage,
@@ -373,6 +537,7 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(*accs.emitter_fee.info().key, false),
         ],
     );
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
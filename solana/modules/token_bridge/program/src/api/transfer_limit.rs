@@ -0,0 +1,216 @@
+use crate::{
+    accounts::{
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodyLedgerAccount,
+        CustodyLedgerDerivationData,
+        CustodySigner,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        NativeUnwrapAccount,
+        PendingTransferAccount,
+    },
+    api::unwrap_custody,
+    token_2022,
+    types::*,
+    TokenBridgeError::{
+        CustodyShortfall,
+        EndpointDeregistered,
+        InvalidFee,
+        TransferPending,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::invoke_seeded,
+    CreationLamports::Exempt,
+    *,
+};
+use spl_token::state::Account;
+use std::ops::{
+    Deref,
+    DerefMut,
+};
+
+/// Count a transfer of `amount` against every configured limit. If it exceeds any of them nothing
+/// is counted and the longest delay among the exceeded limits is returned.
+pub fn consume_limits(
+    limits: &mut [&mut Data<'_, TransferLimit, { AccountState::MaybeInitialized }>],
+    amount: u64,
+    now: i64,
+) -> Option<u64> {
+    let delay = limits
+        .iter()
+        .filter(|limit| limit.is_initialized() && !limit.allows(amount, now))
+        .map(|limit| limit.delay)
+        .max();
+
+    if delay.is_none() {
+        for limit in limits.iter_mut().filter(|limit| limit.is_initialized()) {
+            limit.record(amount, now);
+        }
+    }
+
+    delay
+}
+
+/// Pay out a transfer that was held back by a limit once its delay has passed. Anyone can release
+/// a transfer, the rent of the pending account goes back to whoever paid for it.
+#[derive(FromAccounts)]
+pub struct ReleasePendingTransfer<'b> {
+    pub payer: Mut<Info<'b>>,
+    pub clock: Sysvar<'b, Clock>,
+
+    /// Must have been completed to `to` and `to_fees` with `mint`, checked when it is loaded.
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::Initialized }>>,
+
+    /// Transfers from endpoints deregistered in the meantime are not released.
+    #[derivation(&EndpointDerivationData {
+        emitter_chain: pending.load()?.emitter_chain,
+        emitter_address: pending.load()?.emitter_address,
+    })]
+    pub endpoint: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// Token accounts of either token program, or wallets for native SOL. They were checked when
+    /// the transfer was completed.
+    pub to: Mut<Info<'b>>,
+    pub to_fees: Mut<Info<'b>>,
+    pub mint: Mut<Data<'b, TokenMint, { AccountState::Initialized }>>,
+
+    /// Only used for native tokens, the custody of a wrapped mint does not exist.
+    #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+    pub custody_signer: CustodySigner<'b>,
+    pub mint_authority: MintSigner<'b>,
+
+    /// Only updated for native tokens once it exists, the payer of a pending transfer can not be
+    /// charged for creating it.
+    #[derivation(&CustodyLedgerDerivationData { mint: *mint.info().key })]
+    pub ledger: Mut<CustodyLedgerAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Only used for native SOL, which is unwrapped before it is paid out.
+    pub unwrap_account: Mut<NativeUnwrapAccount<'b>>,
+}
+
+impl<'b> InstructionContext<'b> for ReleasePendingTransfer<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, BorshSchema)]
+pub struct ReleasePendingTransferData {}
+
+pub fn release_pending_transfer(
+    ctx: &ExecutionContext,
+    accs: &mut ReleasePendingTransfer,
+    _data: ReleasePendingTransferData,
+) -> Result<()> {
+    let pending = *accs.pending.load()?;
+    for (found, expected) in [
+        (accs.payer.key, pending.payer),
+        (accs.to.key, pending.to),
+        (accs.to_fees.key, pending.to_fees),
+        (accs.mint.info().key, pending.mint),
+    ] {
+        if *found != expected {
+            return Err(SolitaireError::KeyMismatch(*found, expected));
+        }
+    }
+
+    if !accs.endpoint.is_registered() {
+        return Err(EndpointDeregistered.into());
+    }
+    if accs.clock.unix_timestamp < pending.release_after {
+        return Err(TransferPending.into());
+    }
+
+    let amount = pending.amount.checked_sub(pending.fee).ok_or(InvalidFee)?;
+    let fee = pending.fee;
+
+    if pending.native != 0 {
+        // Checked on every path moving custody, not only when tokens are locked.
+        token_2022::check_mint(accs.mint.info())?;
+        token_2022::check_account(accs.custody.info(), accs.mint.info())?;
+        if accs.ledger.is_initialized() {
+            accs.ledger.balance = accs
+                .ledger
+                .balance
+                .checked_sub(pending.amount)
+                .ok_or(CustodyShortfall)?;
+        }
+    }
+
+    if pending.lamports != 0 {
+        // The payer funded the rent of the unwrap account together with the pending account. The
+        // unwrapped lamports are paid out of the pending account, which returns the rest on close.
+        let rent = Exempt.amount(Account::LEN);
+        let missing = rent.saturating_sub(accs.unwrap_account.lamports());
+        **accs.pending.info().lamports.borrow_mut() -= missing;
+        **accs.unwrap_account.lamports.borrow_mut() += missing;
+
+        unwrap_custody(
+            ctx,
+            &accs.unwrap_account,
+            accs.custody.info().key,
+            accs.mint.info().key,
+            &accs.custody_signer,
+            pending.amount,
+            accs.pending.info().key,
+        )?;
+
+        **accs.pending.info().lamports.borrow_mut() -= pending.amount;
+        **accs.to.lamports.borrow_mut() += amount;
+        **accs.to_fees.lamports.borrow_mut() += fee;
+    } else if pending.native != 0 {
+        let transfer_ix = token_2022::transfer(
+            accs.mint.info().owner,
+            accs.custody.info().key,
+            accs.mint.info().key,
+            accs.to.key,
+            accs.custody_signer.key,
+            amount,
+            accs.mint.decimals,
+        )?;
+        invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+        let transfer_ix = token_2022::transfer(
+            accs.mint.info().owner,
+            accs.custody.info().key,
+            accs.mint.info().key,
+            accs.to_fees.key,
+            accs.custody_signer.key,
+            fee,
+            accs.mint.decimals,
+        )?;
+        invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+    } else {
+        let mint_ix = spl_token::instruction::mint_to(
+            &spl_token::id(),
+            accs.mint.info().key,
+            accs.to.key,
+            accs.mint_authority.key,
+            &[],
+            amount,
+        )?;
+        invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+
+        let mint_ix = spl_token::instruction::mint_to(
+            &spl_token::id(),
+            accs.mint.info().key,
+            accs.to_fees.key,
+            accs.mint_authority.key,
+            &[],
+            fee,
+        )?;
+        invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+    }
+
+    // The VAA stays claimed, so the pending transfer cannot be recreated once it is closed.
+    accs.pending.close(ctx, accs.payer.info())?;
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Conversion between token amounts and the amounts carried by transfer payloads.
+//!
+//! Payloads carry at most 8 decimals so that every chain can represent them. Amounts of tokens
+//! with more decimals are truncated when they leave Solana, the truncated remainder is never taken
+//! from the sender, and are scaled back up when they arrive.
+
+use crate::{
+    TokenBridgeError,
+    TokenBridgeError::{
+        AmountOverflow,
+        AmountTooSmall,
+        FeeTooSmall,
+        InvalidFee,
+    },
+};
+use primitive_types::U256;
+
+/// Decimals of payload amounts for tokens with at least as many decimals.
+pub const PAYLOAD_DECIMALS: u8 = 8;
+
+/// Token units per payload unit for a mint with `decimals` decimals.
+fn scale(decimals: u8) -> Result<u64, TokenBridgeError> {
+    10u64
+        .checked_pow(decimals.saturating_sub(PAYLOAD_DECIMALS) as u32)
+        .ok_or(AmountOverflow)
+}
+
+/// An outgoing transfer split into what the payload carries and what is taken from the sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizedTransfer {
+    /// Amount carried by the payload.
+    pub amount: u64,
+    /// Relayer fee carried by the payload.
+    pub fee: u64,
+    /// Decimals of the payload amounts, those of the token capped at 8.
+    pub decimals: u8,
+    /// Token units taken from the sender.
+    pub transferred: u64,
+    /// Token units below the payload precision which stay with the sender.
+    pub dust: u64,
+}
+
+impl NormalizedTransfer {
+    /// Amount the recipient receives once the relayer took the fee, in payload units. The token on
+    /// the target chain has the decimals of the payload unless it originates there, in which case
+    /// the amount is scaled up to its decimals without loss.
+    pub fn received(&self) -> u64 {
+        self.amount - self.fee
+    }
+}
+
+/// Normalize an outgoing transfer of `amount` token units, paying `fee` to the relayer. Fails if
+/// the amount, or a non-zero fee, would be truncated to nothing.
+pub fn normalize(
+    amount: u64,
+    fee: u64,
+    decimals: u8,
+) -> Result<NormalizedTransfer, TokenBridgeError> {
+    if fee > amount {
+        return Err(InvalidFee);
+    }
+
+    let scale = scale(decimals)?;
+    let normalized = amount / scale;
+    let normalized_fee = fee / scale;
+    if normalized == 0 {
+        return Err(AmountTooSmall);
+    }
+    if fee > 0 && normalized_fee == 0 {
+        return Err(FeeTooSmall);
+    }
+
+    // Cannot overflow, the result is at most `amount`.
+    let transferred = normalized * scale;
+
+    Ok(NormalizedTransfer {
+        amount: normalized,
+        fee: normalized_fee,
+        decimals: decimals.min(PAYLOAD_DECIMALS),
+        transferred,
+        dust: amount - transferred,
+    })
+}
+
+/// Scale a payload amount back up to the units of a token with `decimals` decimals.
+pub fn denormalize(amount: U256, decimals: u8) -> Result<u64, TokenBridgeError> {
+    if amount > U256::from(u64::MAX) {
+        return Err(AmountOverflow);
+    }
+    amount
+        .as_u64()
+        .checked_mul(scale(decimals)?)
+        .ok_or(AmountOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_truncates_dust() {
+        let transfer = normalize(1_000_000_005, 10_000_009, 9).unwrap();
+        assert_eq!(transfer.amount, 100_000_000);
+        assert_eq!(transfer.fee, 1_000_000);
+        assert_eq!(transfer.decimals, 8);
+        assert_eq!(transfer.transferred, 1_000_000_000);
+        assert_eq!(transfer.dust, 5);
+        assert_eq!(transfer.received(), 99_000_000);
+
+        let transfer = normalize(1_005, 5, 6).unwrap();
+        assert_eq!(transfer.amount, 1_005);
+        assert_eq!(transfer.decimals, 6);
+        assert_eq!(transfer.dust, 0);
+    }
+
+    #[test]
+    fn normalize_rejects_amounts_lost_to_truncation() {
+        assert_eq!(normalize(9, 0, 9), Err(AmountTooSmall));
+        assert_eq!(normalize(0, 0, 6), Err(AmountTooSmall));
+        assert_eq!(normalize(1_000, 9, 9), Err(FeeTooSmall));
+        assert_eq!(normalize(1_000, 1_001, 9), Err(InvalidFee));
+        assert_eq!(normalize(u64::MAX, 0, 30), Err(AmountOverflow));
+    }
+
+    #[test]
+    fn denormalize_is_checked() {
+        assert_eq!(denormalize(U256::from(100), 9), Ok(1_000));
+        assert_eq!(denormalize(U256::from(100), 6), Ok(100));
+        assert_eq!(denormalize(U256::from(u64::MAX), 9), Err(AmountOverflow));
+        assert_eq!(
+            denormalize(U256::from(u64::MAX) + 1, 8),
+            Err(AmountOverflow)
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Structured events logged by the token bridge, encoded like the events of the core bridge so
+//! that indexers can decode them with `bridge::events::parse_events::<TokenBridgeEvent>` and the
+//! token bridge program id.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use bridge::events::{
+    encode,
+    EVENT_PREFIX,
+};
+use solana_program::msg;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum TokenBridgeEvent {
+    /// A custody account holds more than its ledger records, such as tokens sent to it directly.
+    /// A custody account holding less fails `AuditCustody` instead.
+    CustodySurplus {
+        mint: [u8; 32],
+        ledger: u64,
+        balance: u64,
+    },
+}
+
+/// Log an event in the format expected by `bridge::events::parse_events`.
+pub fn emit(event: TokenBridgeEvent) {
+    msg!("{}{}", EVENT_PREFIX, encode(&event));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge::events::{
+        parse_events,
+        PROGRAM_LOG_PREFIX,
+    };
+
+    const TOKEN_BRIDGE: &str = "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE";
+
+    #[test]
+    fn events_are_decoded_for_the_token_bridge() {
+        let event = TokenBridgeEvent::CustodySurplus {
+            mint: [5u8; 32],
+            ledger: 1000,
+            balance: 1005,
+        };
+        let logs = vec![
+            format!("Program {} invoke [1]", TOKEN_BRIDGE),
+            format!("{}{}{}", PROGRAM_LOG_PREFIX, EVENT_PREFIX, encode(&event)),
+            format!("Program {} success", TOKEN_BRIDGE),
+        ];
+        assert_eq!(
+            vec![Ok(event)],
+            parse_events::<TokenBridgeEvent, _>(TOKEN_BRIDGE, &logs)
+        );
+    }
+}
@@ -1,34 +1,56 @@
 use crate::{
     accounts::{
         AuthoritySigner,
+        ChainLimitDerivationData,
         ConfigAccount,
         CustodyAccount,
         CustodyAccountDerivationData,
+        CustodyLedgerAccount,
+        CustodyLedgerDerivationData,
         CustodySigner,
         EmitterAccount,
         Endpoint,
         EndpointDerivationData,
+        MintLimitDerivationData,
         MintSigner,
+        NativeUnwrapAccount,
+        PendingTransferAccount,
+        PendingTransferDerivationData,
         SplTokenMeta,
         SplTokenMetaDerivationData,
+        TransferLimitAccount,
         WrappedDerivationData,
         WrappedMetaDerivationData,
+        WrappedMetadataOverrideAccount,
+        WrappedMetadataOverrideDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
     api::{
         complete_transfer::{
             CompleteNativeData,
+            CompleteNativeSolData,
             CompleteWrappedData,
         },
         AttestTokenData,
+        AuditCustodyData,
         CreateWrappedData,
         RegisterChainData,
+        ReleasePendingTransferData,
+        SetChainLimitData,
+        SetMintLimitData,
+        SetWrappedMetadataData,
         TransferNativeData,
+        TransferNativeSolData,
         TransferWrappedData,
+        UpdateChainData,
         UpgradeContractData,
     },
     messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetMintLimit,
+        GovernancePayloadSetWrappedMetadata,
+        GovernancePayloadUpdateChain,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
@@ -41,6 +63,8 @@ use bridge::{
         BridgeConfig,
         Claim,
         ClaimDerivationData,
+        EmitterFee,
+        EmitterFeeDerivationData,
         FeeCollector,
         PostedVAA,
         PostedVAAData,
@@ -118,6 +142,7 @@ pub fn complete_native(
         &program_id,
     );
     let custody_signer_key = CustodySigner::key(None, &program_id);
+    let [clock, mint_limit, chain_limit] = transfer_limits(program_id, mint, &vaa);
 
     Ok(Instruction {
         program_id,
@@ -136,17 +161,81 @@ pub fn complete_native(
             AccountMeta::new(custody_key, false),
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(custody_signer_key, false),
+            clock,
+            mint_limit,
+            chain_limit,
+            AccountMeta::new(pending_transfer_key(program_id, &vaa), false),
+            AccountMeta::new(custody_ledger_key(program_id, mint), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(crate::token_2022::id(), false),
         ],
         data: (crate::instruction::Instruction::CompleteNative, data).try_to_vec()?,
     })
 }
 
+/// Complete a transfer of native SOL to the wallet `to`, the payer keeps the relayer fee.
+pub fn complete_native_sol(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    to: Pubkey,
+    data: CompleteNativeSolData,
+) -> solitaire::Result<Instruction> {
+    let mint = spl_token::native_mint::id();
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let unwrap_key = NativeUnwrapAccount::key(None, &program_id);
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let [clock, mint_limit, chain_limit] = transfer_limits(program_id, mint, &vaa);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new_readonly(endpoint, false),
+            AccountMeta::new(to, false),
+            AccountMeta::new(payer, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(unwrap_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            clock,
+            mint_limit,
+            chain_limit,
+            AccountMeta::new(custody_ledger_key(program_id, mint), false),
+            AccountMeta::new(pending_transfer_key(program_id, &vaa), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: (crate::instruction::Instruction::CompleteNativeSol, data).try_to_vec()?,
+    })
+}
+
 pub fn complete_wrapped(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -179,6 +268,7 @@ pub fn complete_wrapped(
         &program_id,
     );
     let mint_authority_key = MintSigner::key(None, &program_id);
+    let [clock, mint_limit, chain_limit] = transfer_limits(program_id, mint_key, &vaa);
 
     Ok(Instruction {
         program_id,
@@ -197,6 +287,10 @@ pub fn complete_wrapped(
             AccountMeta::new(mint_key, false),
             AccountMeta::new_readonly(meta_key, false),
             AccountMeta::new_readonly(mint_authority_key, false),
+            clock,
+            mint_limit,
+            chain_limit,
+            AccountMeta::new(pending_transfer_key(program_id, &vaa), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -243,6 +337,11 @@ pub fn create_wrapped(
         &SplTokenMetaDerivationData { mint: mint_key },
         &spl_token_metadata::id(),
     );
+    let metadata_override =
+        WrappedMetadataOverrideAccount::<'_, { AccountState::MaybeInitialized }>::key(
+            &WrappedMetadataOverrideDerivationData { mint_key },
+            &program_id,
+        );
 
     Ok(Instruction {
         program_id,
@@ -256,6 +355,7 @@ pub fn create_wrapped(
             AccountMeta::new(mint_meta_key, false),
             AccountMeta::new(spl_metadata, false),
             AccountMeta::new_readonly(mint_authority_key, false),
+            AccountMeta::new_readonly(metadata_override, false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -305,6 +405,292 @@ pub fn register_chain(
     })
 }
 
+/// Deregister the endpoint named by `payload` and register its replacement, if any.
+pub fn update_chain(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadUpdateChain,
+    data: UpdateChainData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: payload.chain,
+            emitter_address: payload.endpoint_address,
+        },
+        &program_id,
+    );
+    let new_endpoint = Endpoint::<'_, { AccountState::MaybeInitialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: payload.chain,
+            emitter_address: payload.new_endpoint_address,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new(endpoint, false),
+            AccountMeta::new(new_endpoint, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::UpdateChain, data).try_to_vec()?,
+    })
+}
+
+/// Key of the account a transfer completed by `vaa` is held in when it exceeds a limit.
+pub fn pending_transfer_key(program_id: Pubkey, vaa: &PostVAAData) -> Pubkey {
+    PendingTransferAccount::<'_, { AccountState::Uninitialized }>::key(
+        &PendingTransferDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        &program_id,
+    )
+}
+
+/// Ledger of the custody account of `mint`, see `AuditCustody`.
+pub fn custody_ledger_key(program_id: Pubkey, mint: Pubkey) -> Pubkey {
+    CustodyLedgerAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &CustodyLedgerDerivationData { mint },
+        &program_id,
+    )
+}
+
+/// Clock and the limits a transfer of `mint` completed by `vaa` is counted against.
+fn transfer_limits(program_id: Pubkey, mint: Pubkey, vaa: &PostVAAData) -> [AccountMeta; 3] {
+    let mint_limit = TransferLimitAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &MintLimitDerivationData { mint },
+        &program_id,
+    );
+    let chain_limit = TransferLimitAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &ChainLimitDerivationData {
+            chain: vaa.emitter_chain,
+        },
+        &program_id,
+    );
+
+    [
+        AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        AccountMeta::new(mint_limit, false),
+        AccountMeta::new(chain_limit, false),
+    ]
+}
+
+pub fn set_mint_limit(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadSetMintLimit,
+    data: SetMintLimitData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let limit = TransferLimitAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &MintLimitDerivationData { mint: payload.mint },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new(limit, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::SetMintLimit, data).try_to_vec()?,
+    })
+}
+
+pub fn set_chain_limit(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadSetChainLimit,
+    data: SetChainLimitData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let limit = TransferLimitAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &ChainLimitDerivationData {
+            chain: payload.chain,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new(limit, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::SetChainLimit, data).try_to_vec()?,
+    })
+}
+
+pub fn set_wrapped_metadata(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadSetWrappedMetadata,
+    data: SetWrappedMetadataData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let mint_key = WrappedMint::<'_, { AccountState::Initialized }>::key(
+        &WrappedDerivationData {
+            token_chain: payload.token_chain,
+            token_address: payload.token_address,
+        },
+        &program_id,
+    );
+    let metadata_override =
+        WrappedMetadataOverrideAccount::<'_, { AccountState::MaybeInitialized }>::key(
+            &WrappedMetadataOverrideDerivationData { mint_key },
+            &program_id,
+        );
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint: mint_key },
+        &spl_token_metadata::id(),
+    );
+    let mint_authority_key = MintSigner::key(None, &program_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new_readonly(mint_key, false),
+            AccountMeta::new(metadata_override, false),
+            AccountMeta::new(spl_metadata, false),
+            AccountMeta::new_readonly(mint_authority_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        ],
+        data: (crate::instruction::Instruction::SetWrappedMetadata, data).try_to_vec()?,
+    })
+}
+
+/// Release the transfer completed by `vaa` once its delay has passed. `payer`, `to`, `to_fees`
+/// and `mint` are the accounts recorded in the pending transfer.
+pub fn release_pending_transfer(
+    program_id: Pubkey,
+    payer: Pubkey,
+    vaa: &PostVAAData,
+    to: Pubkey,
+    to_fees: Pubkey,
+    mint: Pubkey,
+    data: ReleasePendingTransferData,
+) -> solitaire::Result<Instruction> {
+    let custody_key = CustodyAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let mint_authority_key = MintSigner::key(None, &program_id);
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            AccountMeta::new(pending_transfer_key(program_id, vaa), false),
+            AccountMeta::new_readonly(endpoint, false),
+            AccountMeta::new(to, false),
+            AccountMeta::new(to_fees, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new_readonly(mint_authority_key, false),
+            AccountMeta::new(custody_ledger_key(program_id, mint), false),
+            AccountMeta::new(NativeUnwrapAccount::key(None, &program_id), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(crate::token_2022::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::ReleasePendingTransfer,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Check that the custody account of `mint` holds at least the balance its ledger records.
+pub fn audit_custody(
+    program_id: Pubkey,
+    mint: Pubkey,
+    data: AuditCustodyData,
+) -> solitaire::Result<Instruction> {
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(custody_key, false),
+            AccountMeta::new_readonly(custody_ledger_key(program_id, mint), false),
+        ],
+        data: (crate::instruction::Instruction::AuditCustody, data).try_to_vec()?,
+    })
+}
+
 fn claimable_vaa(
     bridge_id: Pubkey,
     message_key: Pubkey,
@@ -361,6 +747,10 @@ pub fn transfer_native(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -378,17 +768,78 @@ pub fn transfer_native(
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
             AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(emitter_fee_key, false),
+            AccountMeta::new(custody_ledger_key(program_id, mint), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(crate::token_2022::id(), false),
         ],
         data: (crate::instruction::Instruction::TransferNative, data).try_to_vec()?,
     })
 }
 
+/// Transfer lamports of `payer` as native SOL.
+pub fn transfer_native_sol(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    data: TransferNativeSolData,
+) -> solitaire::Result<Instruction> {
+    let mint = spl_token::native_mint::id();
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message_key, true),
+            AccountMeta::new_readonly(emitter_key, false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(emitter_fee_key, false),
+            AccountMeta::new(custody_ledger_key(program_id, mint), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: (crate::instruction::Instruction::TransferNativeSol, data).try_to_vec()?,
+    })
+}
+
 pub fn transfer_wrapped(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -436,6 +887,10 @@ pub fn transfer_wrapped(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -453,6 +908,7 @@ pub fn transfer_wrapped(
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
             AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(emitter_fee_key, false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -496,6 +952,10 @@ pub fn attest(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -512,6 +972,7 @@ pub fn attest(
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
             AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(emitter_fee_key, false),
             // Dependencies
             AccountMeta::new(solana_program::sysvar::rent::id(), false),
             AccountMeta::new(solana_program::system_program::id(), false),
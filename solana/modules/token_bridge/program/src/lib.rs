@@ -17,23 +17,39 @@ pub mod wasm;
 
 pub mod accounts;
 pub mod api;
+pub mod decimals;
+pub mod events;
 pub mod messages;
+pub mod query;
+pub mod token_2022;
 pub mod types;
 
 pub use api::{
     attest_token,
+    audit_custody,
     complete_native,
+    complete_native_sol,
     complete_wrapped,
     create_wrapped,
     initialize,
     register_chain,
+    release_pending_transfer,
+    set_chain_limit,
+    set_mint_limit,
+    set_wrapped_metadata,
     transfer_native,
+    transfer_native_sol,
     transfer_wrapped,
+    update_chain,
     upgrade_contract,
     AttestToken,
     AttestTokenData,
+    AuditCustody,
+    AuditCustodyData,
     CompleteNative,
     CompleteNativeData,
+    CompleteNativeSol,
+    CompleteNativeSolData,
     CompleteWrapped,
     CompleteWrappedData,
     CreateWrapped,
@@ -42,10 +58,22 @@ pub use api::{
     InitializeData,
     RegisterChain,
     RegisterChainData,
+    ReleasePendingTransfer,
+    ReleasePendingTransferData,
+    SetChainLimit,
+    SetChainLimitData,
+    SetMintLimit,
+    SetMintLimitData,
+    SetWrappedMetadata,
+    SetWrappedMetadataData,
     TransferNative,
     TransferNativeData,
+    TransferNativeSol,
+    TransferNativeSolData,
     TransferWrapped,
     TransferWrappedData,
+    UpdateChain,
+    UpdateChainData,
     UpgradeContract,
     UpgradeContractData,
 };
@@ -53,35 +81,48 @@ pub use api::{
 use solitaire::*;
 use std::error::Error;
 
-pub enum TokenBridgeError {
-    AlreadyExecuted,
-    InvalidChain,
-    InvalidGovernanceKey,
-    InvalidMetadata,
-    InvalidMint,
-    InvalidPayload,
-    InvalidUTF8String,
-    TokenNotNative,
-    UninitializedMint,
-    WrongAccountOwner,
-    InvalidFee,
-    InvalidRecipient,
-}
-
-impl From<TokenBridgeError> for SolitaireError {
-    fn from(t: TokenBridgeError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
+program_errors! {
+    pub enum TokenBridgeError: 0x2000 {
+        AlreadyExecuted = 0,
+        InvalidChain = 1,
+        InvalidGovernanceKey = 2,
+        InvalidMetadata = 3,
+        InvalidMint = 4,
+        InvalidPayload = 5,
+        InvalidUTF8String = 6,
+        TokenNotNative = 7,
+        UninitializedMint = 8,
+        WrongAccountOwner = 9,
+        InvalidFee = 10,
+        InvalidRecipient = 11,
+        TransferLimitExceeded = 12,
+        TransferPending = 13,
+        InvalidFeeRecipient = 14,
+        AmountTooSmall = 15,
+        FeeTooSmall = 16,
+        AmountOverflow = 17,
+        CustodyShortfall = 18,
+        UnsupportedMintExtension = 19,
+        EndpointDeregistered = 20,
     }
 }
 
 solitaire! {
-    Initialize(InitializeData) => initialize,
-    AttestToken(AttestTokenData) => attest_token,
-    CompleteNative(CompleteNativeData) => complete_native,
-    CompleteWrapped(CompleteWrappedData) => complete_wrapped,
-    TransferWrapped(TransferWrappedData) => transfer_wrapped,
-    TransferNative(TransferNativeData) => transfer_native,
-    RegisterChain(RegisterChainData) => register_chain,
-    CreateWrapped(CreateWrappedData) => create_wrapped,
-    UpgradeContract(UpgradeContractData) => upgrade_contract,
+    Initialize(InitializeData)                         = 0 => initialize,
+    AttestToken(AttestTokenData)                       = 1 => attest_token,
+    CompleteNative(CompleteNativeData)                 = 2 => complete_native,
+    CompleteWrapped(CompleteWrappedData)               = 3 => complete_wrapped,
+    TransferWrapped(TransferWrappedData)               = 4 => transfer_wrapped,
+    TransferNative(TransferNativeData)                 = 5 => transfer_native,
+    RegisterChain(RegisterChainData)                   = 6 => register_chain,
+    CreateWrapped(CreateWrappedData)                   = 7 => create_wrapped,
+    UpgradeContract(UpgradeContractData)               = 8 => upgrade_contract,
+    TransferNativeSol(TransferNativeSolData)           = 9 => transfer_native_sol,
+    CompleteNativeSol(CompleteNativeSolData)           = 10 => complete_native_sol,
+    SetMintLimit(SetMintLimitData)                     = 11 => set_mint_limit,
+    SetChainLimit(SetChainLimitData)                   = 12 => set_chain_limit,
+    ReleasePendingTransfer(ReleasePendingTransferData) = 13 => release_pending_transfer,
+    SetWrappedMetadata(SetWrappedMetadataData)         = 14 => set_wrapped_metadata,
+    AuditCustody(AuditCustodyData)                     = 15 => audit_custody,
+    UpdateChain(UpdateChainData)                       = 16 => update_chain,
 }
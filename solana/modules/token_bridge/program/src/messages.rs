@@ -56,6 +56,8 @@ pub struct PayloadTransfer {
     // Chain ID of the recipient
     pub to_chain: ChainID,
     // Amount of tokens (big-endian uint256) that the user is willing to pay as relayer fee. Must be <= Amount.
+    // On Solana the fee goes to an account of whoever submits the redemption, recipients that
+    // redeem the transfer themselves receive the full amount.
     pub fee: U256,
 }
 
@@ -64,7 +66,7 @@ impl DeserializePayload for PayloadTransfer {
         let mut v = Cursor::new(buf);
 
         if v.read_u8()? != 1 {
-            return Err(SolitaireError::Custom(0));
+            return Err(TokenBridgeError::InvalidPayload.into());
         };
 
         let mut am_data: [u8; 32] = [0; 32];
@@ -143,7 +145,7 @@ impl DeserializePayload for PayloadAssetMeta {
         let mut v = Cursor::new(buf);
 
         if v.read_u8()? != 2 {
-            return Err(SolitaireError::Custom(0));
+            return Err(TokenBridgeError::InvalidPayload.into());
         };
 
         let mut token_address = Address::default();
@@ -302,9 +304,271 @@ impl SerializeGovernancePayload for GovernancePayloadUpgrade {
 impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
 }
 
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetMintLimit {
+    // Mint the limit applies to, native or wrapped
+    pub mint: Pubkey,
+    // Maximum outflow in the units of transfer payloads
+    pub limit: u64,
+    // Seconds for a full limit to free up
+    pub window: u64,
+    // Seconds transfers over the limit are held for
+    pub delay: u64,
+}
+
+impl SerializePayload for GovernancePayloadSetMintLimit {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write(&self.mint.to_bytes())?;
+        v.write_u64::<BigEndian>(self.limit)?;
+        v.write_u64::<BigEndian>(self.window)?;
+        v.write_u64::<BigEndian>(self.delay)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetMintLimit
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+        let limit = c.read_u64::<BigEndian>()?;
+        let window = c.read_u64::<BigEndian>()?;
+        let delay = c.read_u64::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetMintLimit {
+            mint: Pubkey::new(&mint[..]),
+            limit,
+            window,
+            delay,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetMintLimit {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 3;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetMintLimit {
+}
+
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetChainLimit {
+    // Chain whose incoming transfers the limit applies to
+    pub chain: ChainID,
+    // Maximum outflow in the units of transfer payloads, summed over all tokens
+    pub limit: u64,
+    // Seconds for a full limit to free up
+    pub window: u64,
+    // Seconds transfers over the limit are held for
+    pub delay: u64,
+}
+
+impl SerializePayload for GovernancePayloadSetChainLimit {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_u16::<BigEndian>(self.chain)?;
+        v.write_u64::<BigEndian>(self.limit)?;
+        v.write_u64::<BigEndian>(self.window)?;
+        v.write_u64::<BigEndian>(self.delay)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetChainLimit
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let chain = c.read_u16::<BigEndian>()?;
+        let limit = c.read_u64::<BigEndian>()?;
+        let window = c.read_u64::<BigEndian>()?;
+        let delay = c.read_u64::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetChainLimit {
+            chain,
+            limit,
+            window,
+            delay,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetChainLimit {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 4;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetChainLimit {
+}
+
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetWrappedMetadata {
+    // Chain ID of the wrapped token
+    pub token_chain: ChainID,
+    // Address of the wrapped token on its chain
+    pub token_address: Address,
+    // Symbol of the token (UTF-8), at most 32 bytes
+    pub symbol: String,
+    // Name of the token (UTF-8), at most 32 bytes
+    pub name: String,
+    // URI of the token metadata (UTF-8), takes up the rest of the payload
+    pub uri: String,
+}
+
+impl SerializePayload for GovernancePayloadSetWrappedMetadata {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_u16::<BigEndian>(self.token_chain)?;
+        v.write(&self.token_address)?;
+
+        let mut symbol: [u8; 32] = [0; 32];
+        symbol[..self.symbol.len()].copy_from_slice(self.symbol.as_bytes());
+        v.write(&symbol)?;
+
+        let mut name: [u8; 32] = [0; 32];
+        name[..self.name.len()].copy_from_slice(self.name.as_bytes());
+        v.write(&name)?;
+
+        v.write(self.uri.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetWrappedMetadata
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let token_chain = c.read_u16::<BigEndian>()?;
+        let mut token_address = Address::default();
+        c.read_exact(&mut token_address)?;
+
+        let mut symbol = vec![0u8; 32];
+        c.read_exact(&mut symbol)?;
+        symbol.retain(|&b| b != 0);
+
+        let mut name = vec![0u8; 32];
+        c.read_exact(&mut name)?;
+        name.retain(|&b| b != 0);
+
+        let mut uri = Vec::new();
+        c.read_to_end(&mut uri)?;
+
+        // Governance is trusted to provide valid strings, unlike attestations they are not
+        // sanitized.
+        let utf8 = |data: Vec<u8>| {
+            String::from_utf8(data).map_err(|_| TokenBridgeError::InvalidUTF8String)
+        };
+
+        Ok(GovernancePayloadSetWrappedMetadata {
+            token_chain,
+            token_address,
+            symbol: utf8(symbol)?,
+            name: utf8(name)?,
+            uri: utf8(uri)?,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetWrappedMetadata {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 5;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetWrappedMetadata {
+}
+
+/// Replace or deregister the endpoint of a chain, for example when its token bridge is migrated to
+/// a new contract.
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadUpdateChain {
+    // Chain ID of the registered endpoint
+    pub chain: ChainID,
+    // Address of the registered endpoint
+    pub endpoint_address: Address,
+    // Address of the endpoint replacing it, zero to only deregister it
+    pub new_endpoint_address: Address,
+}
+
+impl GovernancePayloadUpdateChain {
+    /// Whether the endpoint is deregistered without a replacement.
+    pub fn deregisters(&self) -> bool {
+        self.new_endpoint_address == Address::default()
+    }
+}
+
+impl SerializePayload for GovernancePayloadUpdateChain {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_u16::<BigEndian>(self.chain)?;
+        v.write(&self.endpoint_address)?;
+        v.write(&self.new_endpoint_address)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadUpdateChain
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let chain = c.read_u16::<BigEndian>()?;
+        let mut endpoint_address = Address::default();
+        c.read_exact(&mut endpoint_address)?;
+        let mut new_endpoint_address = Address::default();
+        c.read_exact(&mut new_endpoint_address)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadUpdateChain {
+            chain,
+            endpoint_address,
+            new_endpoint_address,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadUpdateChain {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadUpdateChain {
+}
+
 #[cfg(feature = "no-entrypoint")]
 mod tests {
     use crate::messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetMintLimit,
+        GovernancePayloadSetWrappedMetadata,
+        GovernancePayloadUpdateChain,
         GovernancePayloadUpgrade,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
@@ -371,6 +635,48 @@ mod tests {
         assert_eq!(original, deser);
     }
 
+    #[test]
+    pub fn test_serde_gov_set_limits() {
+        let original = GovernancePayloadSetMintLimit {
+            mint: Pubkey::new_unique(),
+            limit: 1_000_000,
+            window: 86400,
+            delay: 3600,
+        };
+        let mut data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadSetMintLimit::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+
+        let original = GovernancePayloadSetChainLimit {
+            chain: 2,
+            limit: 1_000_000,
+            window: 86400,
+            delay: 3600,
+        };
+        let mut data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadSetChainLimit::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+    }
+
+    #[test]
+    pub fn test_serde_gov_set_wrapped_metadata() {
+        let mut token_address = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut token_address);
+
+        let original = GovernancePayloadSetWrappedMetadata {
+            token_chain: 2,
+            token_address,
+            symbol: "WETH".to_string(),
+            name: "Wrapped Ether".to_string(),
+            uri: "https://example.com/weth.json".to_string(),
+        };
+
+        let mut data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadSetWrappedMetadata::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(original, deser);
+    }
+
     #[test]
     pub fn test_serde_gov_register_chain() {
         let mut endpoint_address = [0u8; 32];
@@ -386,4 +692,26 @@ mod tests {
 
         assert_eq!(original, deser);
     }
+
+    #[test]
+    pub fn test_serde_gov_update_chain() {
+        let mut endpoint_address = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut endpoint_address);
+
+        let original = GovernancePayloadUpdateChain {
+            chain: 8,
+            endpoint_address,
+            new_endpoint_address: [0u8; 32],
+        };
+        assert!(original.deregisters());
+
+        let data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadUpdateChain::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(original, deser);
+
+        // Trailing bytes are rejected.
+        let mut data = data;
+        data.push(0);
+        assert!(GovernancePayloadUpdateChain::deserialize(&mut data.as_slice()).is_err());
+    }
 }
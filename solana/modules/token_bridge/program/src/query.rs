@@ -0,0 +1,116 @@
+//! Lookups between wrapped mints and the foreign tokens they represent, for off-chain clients.
+//!
+//! Every wrapped mint has a `WrappedMeta` account recording its origin. These accounts are the only
+//! accounts of the program with `WRAPPED_META_SIZE` bytes of data, which allows enumerating all
+//! wrapped assets by scanning the program accounts with a data size filter.
+
+use crate::{
+    accounts::{
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    },
+    types::{
+        Address,
+        ChainID,
+        WrappedMeta,
+    },
+};
+use borsh::BorshDeserialize;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+
+/// Size of the data of a `WrappedMeta` account.
+pub const WRAPPED_META_SIZE: usize = 35;
+
+/// A wrapped mint and the token it represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrappedAsset {
+    pub mint: Pubkey,
+    pub chain: ChainID,
+    pub token_address: Address,
+    pub original_decimals: u8,
+}
+
+/// Address of the mint wrapping `token_address` of `chain`, whether it was created or not.
+pub fn wrapped_mint(program_id: &Pubkey, chain: ChainID, token_address: Address) -> Pubkey {
+    WrappedMint::<'_, { AccountState::Initialized }>::key(
+        &WrappedDerivationData {
+            token_chain: chain,
+            token_address,
+        },
+        program_id,
+    )
+}
+
+/// Address of the `WrappedMeta` account of `mint`, it only exists for wrapped mints.
+pub fn wrapped_meta(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+        &WrappedMetaDerivationData { mint_key: *mint },
+        program_id,
+    )
+}
+
+/// Parse the `WrappedMeta` account at `address`. Returns `None` if the data is not a `WrappedMeta`
+/// or the account is not the one of the mint it describes.
+pub fn wrapped_asset(program_id: &Pubkey, address: &Pubkey, data: &[u8]) -> Option<WrappedAsset> {
+    if data.len() != WRAPPED_META_SIZE {
+        return None;
+    }
+    let meta = WrappedMeta::try_from_slice(data).ok()?;
+
+    let mint = wrapped_mint(program_id, meta.chain, meta.token_address);
+    if wrapped_meta(program_id, &mint) != *address {
+        return None;
+    }
+
+    Some(WrappedAsset {
+        mint,
+        chain: meta.chain,
+        token_address: meta.token_address,
+        original_decimals: meta.original_decimals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    #[test]
+    fn wrapped_asset_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let meta = WrappedMeta {
+            chain: 2,
+            token_address: [7u8; 32],
+            original_decimals: 18,
+        };
+        let data = meta.try_to_vec().unwrap();
+        assert_eq!(data.len(), WRAPPED_META_SIZE);
+
+        let mint = wrapped_mint(&program_id, 2, [7u8; 32]);
+        let address = wrapped_meta(&program_id, &mint);
+        assert_eq!(
+            wrapped_asset(&program_id, &address, &data),
+            Some(WrappedAsset {
+                mint,
+                chain: 2,
+                token_address: [7u8; 32],
+                original_decimals: 18,
+            })
+        );
+
+        // The meta of another mint, or data of another size, is not accepted.
+        let other = wrapped_meta(&program_id, &Pubkey::new_unique());
+        assert_eq!(wrapped_asset(&program_id, &other, &data), None);
+        assert_eq!(wrapped_asset(&program_id, &address, &data[1..]), None);
+    }
+}
@@ -0,0 +1,194 @@
+//! Support for mints of the Token-2022 program next to those of the legacy token program.
+//!
+//! Token-2022 keeps the legacy layouts of mints and token accounts as the prefix of its accounts,
+//! extensions are appended after an account type byte. The bridge only reads the prefix, and only
+//! accepts mints whose extensions leave custody and transfers behaving like the legacy program:
+//! transfer fees, confidential transfers, transfer hooks, non-transferable tokens, permanent
+//! delegates and frozen default states are rejected. So are close authorities, a closed mint could
+//! be recreated at the same address with other decimals or extensions while tokens are in custody.
+//!
+//! Transfers of legacy mints issue the same instructions as before, Token-2022 mints are moved with
+//! `TransferChecked`.
+
+use crate::TokenBridgeError::{
+    InvalidMint,
+    UnsupportedMintExtension,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use solitaire::Result;
+use spl_token::state::{
+    Account,
+    Mint,
+};
+
+solana_program::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Account type byte of Token-2022 mints with extensions.
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Mint extensions which do not affect custody or transfers.
+const SUPPORTED_EXTENSIONS: [u16; 7] = [
+    10, // InterestBearingConfig
+    18, // MetadataPointer
+    19, // TokenMetadata
+    20, // GroupPointer
+    21, // TokenGroup
+    22, // GroupMemberPointer
+    23, // TokenGroupMember
+];
+
+/// Whether `program` is one of the token programs the bridge accepts.
+pub fn is_token_program(program: &Pubkey) -> bool {
+    *program == spl_token::id() || *program == id()
+}
+
+/// Check that `mint` belongs to a supported token program and, for Token-2022 mints, that it only
+/// uses supported extensions.
+pub fn check_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner == spl_token::id() {
+        return Ok(());
+    }
+    if *mint.owner != id() {
+        return Err(InvalidMint.into());
+    }
+    check_extensions(&mint.data.borrow())
+}
+
+/// Check that a token account belongs to the token program owning `mint`. Token accounts of either
+/// program are peeled without checking their owner.
+pub fn check_account(account: &AccountInfo, mint: &AccountInfo) -> Result<()> {
+    if account.owner != mint.owner {
+        return Err(InvalidMint.into());
+    }
+    Ok(())
+}
+
+/// Walk the extensions of Token-2022 mint data.
+fn check_extensions(data: &[u8]) -> Result<()> {
+    if data.len() == Mint::LEN {
+        return Ok(());
+    }
+    // Extensions start after the padding up to the size of a token account and the type byte.
+    if data.len() <= Account::LEN || data[Account::LEN] != ACCOUNT_TYPE_MINT {
+        return Err(InvalidMint.into());
+    }
+    if data[Mint::LEN..Account::LEN].iter().any(|b| *b != 0) {
+        return Err(InvalidMint.into());
+    }
+
+    let mut tlv = &data[Account::LEN + 1..];
+    while tlv.len() >= 4 {
+        let extension = u16::from_le_bytes([tlv[0], tlv[1]]);
+        let length = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+        // Uninitialized space after the last extension.
+        if extension == 0 {
+            break;
+        }
+        if !SUPPORTED_EXTENSIONS.contains(&extension) {
+            return Err(UnsupportedMintExtension.into());
+        }
+        if tlv.len() < 4 + length {
+            return Err(InvalidMint.into());
+        }
+        tlv = &tlv[4 + length..];
+    }
+    Ok(())
+}
+
+/// Transfer `amount` of `mint` with the token program owning it.
+pub fn transfer(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<Instruction> {
+    if *token_program == spl_token::id() {
+        return Ok(spl_token::instruction::transfer(
+            token_program,
+            source,
+            destination,
+            authority,
+            &[],
+            amount,
+        )?);
+    }
+
+    // Token-2022 shares the instruction layout, the builder only accepts the legacy program id.
+    let mut ix = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        source,
+        mint,
+        destination,
+        authority,
+        &[],
+        amount,
+        decimals,
+    )?;
+    ix.program_id = *token_program;
+    Ok(ix)
+}
+
+/// Initialize a token account of `mint` with the token program owning it.
+pub fn initialize_account(
+    token_program: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Instruction> {
+    let mut ix =
+        spl_token::instruction::initialize_account(&spl_token::id(), account, mint, owner)?;
+    ix.program_id = *token_program;
+    Ok(ix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_data(extensions: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut data = vec![0u8; Account::LEN];
+        data.push(ACCOUNT_TYPE_MINT);
+        for (extension, value) in extensions {
+            data.extend_from_slice(&extension.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        data
+    }
+
+    #[test]
+    fn supported_extensions_are_accepted() {
+        assert!(check_extensions(&[0u8; Mint::LEN]).is_ok());
+        assert!(
+            check_extensions(&mint_data(&[(10, &[1u8; 52][..]), (18, &[2u8; 64][..])])).is_ok()
+        );
+
+        // Space reserved after the last extension is ignored.
+        let mut data = mint_data(&[(10, &[0u8; 52])]);
+        data.extend_from_slice(&[0u8; 16]);
+        assert!(check_extensions(&data).is_ok());
+    }
+
+    #[test]
+    fn unsupported_extensions_are_rejected() {
+        // Transfer fees, close authorities, confidential transfers, non-transferable mints,
+        // permanent delegates and transfer hooks.
+        for extension in [1u16, 3, 4, 9, 12, 14].iter() {
+            assert!(check_extensions(&mint_data(&[(18, &[0u8; 64]), (*extension, &[])])).is_err());
+        }
+
+        // Truncated extensions and token accounts are not mints.
+        assert!(check_extensions(&mint_data(&[(18, &[0u8; 64])])[..Account::LEN + 10]).is_err());
+        let mut data = mint_data(&[]);
+        data[Account::LEN] = 2;
+        assert!(check_extensions(&data).is_err());
+    }
+}
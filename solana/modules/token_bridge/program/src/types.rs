@@ -2,6 +2,10 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use bytemuck::{
+    Pod,
+    Zeroable,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -13,12 +17,15 @@ use solitaire::{
         AccountOwner,
         Owned,
     },
+    ZeroCopy,
+    LOADER_DATA_OFFSET,
 };
 use spl_token::state::{
     Account,
     Mint,
 };
 use spl_token_metadata::state::Metadata;
+use std::mem::size_of;
 
 pub type Address = [u8; 32];
 pub type ChainID = u16;
@@ -40,6 +47,14 @@ pub struct EndpointRegistration {
     pub contract: Address,
 }
 
+impl EndpointRegistration {
+    /// Endpoints deregistered by governance keep their account with the contract cleared. Closing
+    /// the account instead would let anyone revive it by funding it within the same transaction.
+    pub fn is_registered(&self) -> bool {
+        self.contract != Address::default()
+    }
+}
+
 impl Owned for EndpointRegistration {
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
@@ -59,5 +74,212 @@ impl Owned for WrappedMeta {
     }
 }
 
+/// Display metadata set by governance for a wrapped token, it takes precedence over attestations.
+#[derive(Default, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct WrappedMetadataOverride {
+    pub symbol: String,
+    pub name: String,
+    pub uri: String,
+}
+
+impl WrappedMetadataOverride {
+    /// Space for the longest symbol and name a payload can carry and the longest URI the metadata
+    /// program accepts, each prefixed by its length.
+    pub const MAX_SIZE: usize = 4 + 32 + 4 + 32 + 4 + 200;
+}
+
+impl Owned for WrappedMetadataOverride {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// Rate limit on the amount a bridge releases, either for a single mint or for all transfers
+/// arriving from a chain.
+///
+/// The limit behaves like a bucket that drains by `limit` every `window` seconds: at most `limit`
+/// can be released at once, and capacity frees up as time passes rather than at fixed window
+/// boundaries.
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct TransferLimit {
+    /// Amount in the 8 decimal units carried by transfer VAAs.
+    pub limit: u64,
+    /// Seconds it takes for a full limit to free up again.
+    pub window: u64,
+    /// Seconds a transfer over the limit has to wait before it can be released.
+    pub delay: u64,
+    /// Amount counted against the limit as of `last_update`.
+    pub outflow: u64,
+    pub last_update: i64,
+    /// Bump seed of the account address, stored to skip the bump search when transfers are
+    /// checked against the limit.
+    pub bump: u8,
+}
+
+impl TransferLimit {
+    /// Amount counted against the limit at `now`.
+    pub fn outflow_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.last_update).max(0) as u128;
+        if self.window == 0 {
+            return if elapsed > 0 { 0 } else { self.outflow };
+        }
+        let freed = elapsed * self.limit as u128 / self.window as u128;
+        (self.outflow as u128).saturating_sub(freed) as u64
+    }
+
+    pub fn allows(&self, amount: u64, now: i64) -> bool {
+        match self.outflow_at(now).checked_add(amount) {
+            Some(outflow) => outflow <= self.limit,
+            None => false,
+        }
+    }
+
+    /// Count `amount` against the limit, callers check `allows` first.
+    pub fn record(&mut self, amount: u64, now: i64) {
+        self.outflow = self.outflow_at(now).saturating_add(amount);
+        self.last_update = now;
+    }
+
+    /// Replace the parameters, the outflow recorded so far stays counted.
+    pub fn configure(&mut self, limit: u64, window: u64, delay: u64, now: i64) {
+        self.outflow = self.outflow_at(now);
+        self.last_update = now;
+        self.limit = limit;
+        self.window = window;
+        self.delay = delay;
+    }
+}
+
+impl Owned for TransferLimit {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// A completed transfer that exceeded a limit, the tokens stay in custody or unminted until it is
+/// released. Accessed in place through a `Loader`, flags are stored as bytes.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Serialize, Deserialize)]
+pub struct PendingTransfer {
+    /// Paid for the account and receives its rent back on release.
+    pub payer: Pubkey,
+    pub mint: Pubkey,
+    pub to: Pubkey,
+    pub to_fees: Pubkey,
+    /// Endpoint the transfer came from, it must still be registered on release.
+    pub emitter_address: Address,
+    /// Amounts in the decimals of the mint.
+    pub amount: u64,
+    pub fee: u64,
+    pub release_after: i64,
+    pub emitter_chain: ChainID,
+    /// Native tokens are released from custody, wrapped tokens are minted.
+    pub native: u8,
+    /// Native SOL is unwrapped on release, `to` and `to_fees` are then wallets receiving lamports.
+    pub lamports: u8,
+    pub _padding: [u8; 4],
+}
+
+impl PendingTransfer {
+    /// Read a pending transfer from the data of its account, for clients.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        if data.get(..LOADER_DATA_OFFSET)? != Self::DISCRIMINATOR {
+            return None;
+        }
+        let data = data.get(LOADER_DATA_OFFSET..LOADER_DATA_OFFSET + size_of::<Self>())?;
+        // Copied, client buffers are not necessarily aligned.
+        let mut pending = Self::zeroed();
+        bytemuck::bytes_of_mut(&mut pending).copy_from_slice(data);
+        Some(pending)
+    }
+}
+
+impl Owned for PendingTransfer {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+impl ZeroCopy for PendingTransfer {
+    const DISCRIMINATOR: [u8; 8] = *b"pending\0";
+}
+
+/// Balance the bridge expects the custody account of a mint to hold, kept alongside every transfer
+/// in or out of custody so that `AuditCustody` can detect accounting errors.
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct CustodyLedger {
+    pub balance: u64,
+    /// Bump seed of the account address, stored to skip the bump search on every transfer.
+    pub bump: u8,
+}
+
+impl Owned for CustodyLedger {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
 pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
 pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
+
+/// Like `pack_type!`, for the state of accounts owned by either token program. Only the legacy
+/// layout at the start of the data is read, Token-2022 extensions after it are ignored, and the
+/// owner is not checked when peeling: instructions check it against the accepted token programs.
+macro_rules! token_type {
+    ($name:ident, $embed:ty) => {
+        #[repr(transparent)]
+        pub struct $name(pub $embed);
+
+        impl BorshDeserialize for $name {
+            fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                let len = <$embed as solana_program::program_pack::Pack>::LEN;
+                if buf.len() < len {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "account data too small",
+                    ));
+                }
+                let acc = $name(
+                    solana_program::program_pack::Pack::unpack(&buf[..len])
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?,
+                );
+                // We need to clear the buf to show to Borsh that we've read all data
+                *buf = &buf[..0];
+
+                Ok(acc)
+            }
+        }
+
+        impl BorshSerialize for $name {
+            fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                let mut data = [0u8; <$embed as solana_program::program_pack::Pack>::LEN];
+                solana_program::program_pack::Pack::pack_into_slice(&self.0, &mut data);
+                writer.write(&data)?;
+
+                Ok(())
+            }
+        }
+
+        impl Owned for $name {
+            fn owner(&self) -> AccountOwner {
+                AccountOwner::Any
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $embed;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl std::default::Default for $name {
+            fn default() -> Self {
+                $name(<$embed>::default())
+            }
+        }
+    };
+}
+
+token_type!(TokenMint, Mint);
+token_type!(TokenAccount, Account);
@@ -10,30 +10,53 @@ use crate::{
     },
     instructions::{
         attest,
+        audit_custody,
         complete_native,
+        complete_native_sol,
         complete_wrapped,
         create_wrapped,
+        custody_ledger_key,
+        pending_transfer_key,
         register_chain,
+        release_pending_transfer,
+        set_wrapped_metadata,
         transfer_native,
+        transfer_native_sol,
         transfer_wrapped,
+        update_chain,
         upgrade_contract,
     },
     messages::{
+        GovernancePayloadSetWrappedMetadata,
+        GovernancePayloadUpdateChain,
         GovernancePayloadUpgrade,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
     },
+    query::{
+        wrapped_asset,
+        WRAPPED_META_SIZE,
+    },
     types::{
+        CustodyLedger,
         EndpointRegistration,
+        PendingTransfer,
+        TransferLimit,
         WrappedMeta,
     },
+    AuditCustodyData,
     CompleteNativeData,
+    CompleteNativeSolData,
     CompleteWrappedData,
     CreateWrappedData,
     RegisterChainData,
+    ReleasePendingTransferData,
+    SetWrappedMetadataData,
     TransferNativeData,
+    TransferNativeSolData,
     TransferWrappedData,
+    UpdateChainData,
 };
 use borsh::BorshDeserialize;
 use bridge::{
@@ -115,6 +138,44 @@ pub fn transfer_native_ix(
     JsValue::from_serde(&ix).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn transfer_native_sol_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    message: String,
+    nonce: u32,
+    amount: u64,
+    fee: u64,
+    target_address: Vec<u8>,
+    target_chain: u16,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let message = Pubkey::from_str(message.as_str()).unwrap();
+
+    let mut target_addr = [0u8; 32];
+    target_addr.copy_from_slice(target_address.as_slice());
+
+    let ix = transfer_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        TransferNativeSolData {
+            nonce,
+            amount,
+            fee,
+            target_address: target_addr,
+            target_chain,
+        },
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn transfer_wrapped_ix(
     program_id: String,
@@ -216,6 +277,50 @@ pub fn complete_transfer_native_ix(
     JsValue::from_serde(&ix).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn complete_transfer_native_sol_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = PayloadTransfer::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+
+    let ix = complete_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        Pubkey::new(&payload.to[..]),
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn complete_transfer_wrapped_ix(
     program_id: String,
@@ -384,6 +489,69 @@ pub fn register_chain_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_wrapped_metadata_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadSetWrappedMetadata::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let ix = set_wrapped_metadata(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        vaa.into(),
+        payload,
+        SetWrappedMetadataData {},
+    )
+    .unwrap();
+    JsValue::from_serde(&ix).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn update_chain_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = GovernancePayloadUpdateChain::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let ix = update_chain(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        vaa.into(),
+        payload,
+        UpdateChainData {},
+    )
+    .unwrap();
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn emitter_address(program_id: String) -> Vec<u8> {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -443,7 +611,86 @@ pub fn parse_wrapped_meta(data: Vec<u8>) -> JsValue {
     JsValue::from_serde(&WrappedMeta::try_from_slice(data.as_slice()).unwrap()).unwrap()
 }
 
+/// Parse the `WrappedMeta` account at `address` into the wrapped asset it describes, or `null` if
+/// it is not one. Wrapped assets can be enumerated by filtering the program accounts by the
+/// `wrapped_meta_size` data size.
+#[wasm_bindgen]
+pub fn parse_wrapped_asset(program_id: String, address: Vec<u8>, data: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let address = Pubkey::new(address.as_slice());
+
+    JsValue::from_serde(&wrapped_asset(&program_id, &address, data.as_slice())).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn wrapped_meta_size() -> usize {
+    WRAPPED_META_SIZE
+}
+
 #[wasm_bindgen]
 pub fn parse_endpoint_registration(data: Vec<u8>) -> JsValue {
     JsValue::from_serde(&EndpointRegistration::try_from_slice(data.as_slice()).unwrap()).unwrap()
 }
+
+#[wasm_bindgen]
+pub fn release_pending_transfer_ix(program_id: String, pending: Vec<u8>, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let pending = PendingTransfer::from_account_data(pending.as_slice()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+
+    let ix = release_pending_transfer(
+        program_id,
+        pending.payer,
+        &vaa.into(),
+        pending.to,
+        pending.to_fees,
+        pending.mint,
+        ReleasePendingTransferData {},
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn pending_transfer_address(program_id: String, vaa: Vec<u8>) -> Vec<u8> {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+
+    pending_transfer_key(program_id, &vaa.into())
+        .to_bytes()
+        .to_vec()
+}
+
+#[wasm_bindgen]
+pub fn parse_pending_transfer(data: Vec<u8>) -> JsValue {
+    JsValue::from_serde(&PendingTransfer::from_account_data(data.as_slice()).unwrap()).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn parse_transfer_limit(data: Vec<u8>) -> JsValue {
+    JsValue::from_serde(&TransferLimit::try_from_slice(data.as_slice()).unwrap()).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn audit_custody_ix(program_id: String, mint: String) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let mint = Pubkey::from_str(mint.as_str()).unwrap();
+
+    let ix = audit_custody(program_id, mint, AuditCustodyData {}).unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn custody_ledger_address(program_id: String, mint: String) -> Vec<u8> {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let mint = Pubkey::from_str(mint.as_str()).unwrap();
+
+    custody_ledger_key(program_id, mint).to_bytes().to_vec()
+}
+
+#[wasm_bindgen]
+pub fn parse_custody_ledger(data: Vec<u8>) -> JsValue {
+    JsValue::from_serde(&CustodyLedger::try_from_slice(data.as_slice()).unwrap()).unwrap()
+}
@@ -0,0 +1,220 @@
+//! Compute units consumed by handlers that verify derivations with stored bump seeds.
+//!
+//! Compute is only metered in the BPF VM, so the programs have to be built with `cargo build-bpf`
+//! first. `solana-program-test` loads them from their shared objects instead of running the
+//! processors natively when `BPF_OUT_DIR` points at the directory they were built into:
+//!
+//! BPF_OUT_DIR=<target/deploy> cargo bench --bench compute_units
+//!
+//! Every instruction is sent behind a compute budget request, the budget is bisected down to the
+//! smallest one the instruction still succeeds with.
+
+use bridge::{
+    accounts::{
+        Bridge,
+        BridgeData,
+        FeeCollector,
+        Sequence,
+        SequenceDerivationData,
+        SequenceTracker,
+    },
+    instructions,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+};
+use primitive_types::U256;
+use solana_program::{
+    pubkey::Pubkey,
+    rent::Rent,
+};
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    system_instruction,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+use token_bridge::{
+    messages::PayloadTransfer,
+    CompleteNativeSolData,
+    TransferNativeSolData,
+};
+use wormhole_program_test::*;
+
+/// Largest budget a transaction can request.
+const MAX_UNITS: u32 = 1_400_000;
+
+/// An instruction to measure, `run` sends a fresh instance of it with the given budget.
+enum Probe {
+    /// Post a message from an emitter with an existing sequence tracker.
+    PostMessage(Keypair),
+    /// Complete a SOL transfer from a registered chain.
+    CompleteNativeSol(Pubkey),
+}
+
+impl Probe {
+    async fn run(&self, wormhole: &mut Wormhole, units: u32) -> bool {
+        let budget = ComputeBudgetInstruction::request_units(units);
+        match self {
+            Probe::PostMessage(emitter) => {
+                let bridge_key =
+                    Bridge::<'_, { AccountState::Initialized }>::key(None, &wormhole.bridge);
+                let bridge: BridgeData = wormhole.context.data(&bridge_key).await;
+                let message = Keypair::new();
+                let payer = wormhole.context.payer();
+                let ixs = [
+                    budget,
+                    system_instruction::transfer(
+                        &payer,
+                        &FeeCollector::<'_>::key(None, &wormhole.bridge),
+                        bridge.config.fee,
+                    ),
+                    instructions::post_message(
+                        wormhole.bridge,
+                        payer,
+                        emitter.pubkey(),
+                        message.pubkey(),
+                        0,
+                        vec![0u8; 32],
+                        ConsistencyLevel::Confirmed,
+                    )
+                    .unwrap(),
+                ];
+                wormhole
+                    .context
+                    .execute(&ixs, &[emitter, &message])
+                    .await
+                    .is_ok()
+            }
+            Probe::CompleteNativeSol(recipient) => {
+                // 0.01 SOL, enough for the wallet to be rent exempt.
+                let payload = PayloadTransfer {
+                    amount: U256::from(1_000_000),
+                    token_address: spl_token::native_mint::id().to_bytes(),
+                    token_chain: CHAIN_ID_SOLANA,
+                    to: recipient.to_bytes(),
+                    to_chain: CHAIN_ID_SOLANA,
+                    fee: U256::from(0),
+                };
+                let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+                let message = wormhole.post_vaa(&vaa).await.unwrap();
+                let ix = token_bridge::instructions::complete_native_sol(
+                    wormhole.token_bridge,
+                    wormhole.bridge,
+                    wormhole.context.payer(),
+                    message,
+                    vaa,
+                    *recipient,
+                    CompleteNativeSolData {},
+                )
+                .unwrap();
+                wormhole.context.execute(&[budget, ix], &[]).await.is_ok()
+            }
+        }
+    }
+}
+
+/// Smallest budget `probe` succeeds with.
+async fn units(wormhole: &mut Wormhole, probe: &Probe) -> u32 {
+    assert!(
+        probe.run(wormhole, MAX_UNITS).await,
+        "the instruction fails with the largest budget"
+    );
+
+    // The instruction fails with `low` units and succeeds with `high`.
+    let (mut low, mut high) = (0, MAX_UNITS);
+    while high - low > 1 {
+        let units = low + (high - low) / 2;
+        match probe.run(wormhole, units).await {
+            true => high = units,
+            false => low = units,
+        }
+    }
+    high
+}
+
+fn sequence_key(emitter: &Keypair) -> Pubkey {
+    Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter.pubkey(),
+        },
+        &bridge_id(),
+    )
+}
+
+async fn run() {
+    // A tracker created before bumps were stored, verifying it has to search for the bump.
+    let legacy = Keypair::new();
+    let data = borsh::BorshSerialize::try_to_vec(&SequenceTracker {
+        sequence: 0,
+        bump: None,
+    })
+    .unwrap();
+    let mut test = Wormhole::program_test();
+    test.add_account(
+        sequence_key(&legacy),
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bridge_id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut wormhole = Wormhole::start_with(test).await;
+
+    // The first message creates the tracker, which stores its bump.
+    let stored = Keypair::new();
+    wormhole.post_message(&stored, vec![]).await.unwrap();
+
+    let searched = units(&mut wormhole, &Probe::PostMessage(legacy)).await;
+    let cached = units(&mut wormhole, &Probe::PostMessage(stored)).await;
+    println!("post_message, bump searched:  {:>7} units", searched);
+    println!("post_message, bump stored:    {:>7} units", cached);
+
+    // Completing a transfer verifies the custody signer, its ledger and the claim, custody has to
+    // hold the SOL paid out.
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+    let message = Keypair::new();
+    let ix = token_bridge::instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    let complete = units(&mut wormhole, &Probe::CompleteNativeSol(recipient)).await;
+    println!("complete_native_sol:          {:>7} units", complete);
+}
+
+fn main() {
+    if std::env::var("BPF_OUT_DIR").is_err() {
+        eprintln!("BPF_OUT_DIR is not set, native processors are not metered");
+        std::process::exit(1);
+    }
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(run());
+}
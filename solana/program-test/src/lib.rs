@@ -0,0 +1,454 @@
+//! In-process test harness for the wormhole programs.
+//!
+//! Loads the bridge, token bridge and NFT bridge into a single `solana-program-test` bank, signs
+//! VAAs with a generated guardian set and posts them through the bridge the same way the guardian
+//! network would, so program tests run under `cargo test` without a validator.
+//!
+//! ```ignore
+//! let mut wormhole = Wormhole::start().await;
+//!
+//! let vaa = wormhole.governance_vaa(payload.try_to_vec().unwrap());
+//! let message = wormhole.post_vaa(&vaa).await.unwrap();
+//!
+//! let posted: PostedVAAData = wormhole.context.data(&message).await;
+//! ```
+//!
+//! Program ids match the devnet deployment, the bridge is taken from `BRIDGE_ADDRESS` as it is
+//! compiled into the programs that call it.
+
+use bridge::{
+    accounts::{
+        Bridge,
+        BridgeData,
+        FeeCollector,
+        PostedVAA,
+        PostedVAADerivationData,
+    },
+    instructions,
+    types::ConsistencyLevel,
+    vaa::{
+        SerializePayload,
+        VAASignature,
+        VAA,
+    },
+    PostVAAData,
+    SerializeGovernancePayload,
+};
+use secp256k1::{
+    Message as Secp256k1Message,
+    PublicKey,
+    SecretKey,
+};
+use sha3::Digest;
+use solana_program::{
+    bpf_loader,
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_sdk::{
+    account::Account,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    transaction::TransactionError,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+use std::{
+    collections::HashMap,
+    io::Write,
+    str::FromStr,
+    time::SystemTime,
+};
+
+pub use solitaire_program_test::*;
+
+/// Chain id of Solana, governance VAAs are emitted from it.
+pub const CHAIN_ID_SOLANA: u16 = 1;
+
+/// Fee the bridge is initialized with.
+pub const MESSAGE_FEE: u64 = 500;
+
+/// Guardian set expiration the bridge is initialized with.
+pub const GUARDIAN_SET_EXPIRATION: u32 = 2_000_000_000;
+
+/// Number of guardians in the default guardian set.
+pub const GUARDIAN_COUNT: u8 = 19;
+
+const SPL_TOKEN_METADATA: &[u8] =
+    include_bytes!("../../modules/token_bridge/token-metadata/spl_token_metadata.so");
+
+pub fn bridge_id() -> Pubkey {
+    Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap()
+}
+
+pub fn token_bridge_id() -> Pubkey {
+    Pubkey::from_str("B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE").unwrap()
+}
+
+pub fn nft_bridge_id() -> Pubkey {
+    Pubkey::from_str("NFTWqJR8YnRVqPDvTJrYuLrQDitTG5AScqbeghi4zSA").unwrap()
+}
+
+/// Emitter that governance VAAs must originate from.
+pub fn governance_emitter() -> [u8; 32] {
+    Pubkey::from_str(env!("EMITTER_ADDRESS"))
+        .unwrap()
+        .to_bytes()
+}
+
+/// A guardian set with known secret keys.
+pub struct Guardians {
+    pub index: u32,
+    pub keys: Vec<[u8; 20]>,
+    pub secrets: Vec<SecretKey>,
+}
+
+impl Guardians {
+    /// Generate `count` random guardians for the guardian set `index`.
+    pub fn generate(index: u32, count: u8) -> Self {
+        let mut rng = rand::thread_rng();
+        let secrets: Vec<SecretKey> = std::iter::repeat_with(|| SecretKey::random(&mut rng))
+            .take(count as usize)
+            .collect();
+        let keys = secrets
+            .iter()
+            .map(|secret| {
+                let public_key = PublicKey::from_secret_key(secret);
+                let mut h = sha3::Keccak256::default();
+                h.write(&public_key.serialize()[1..]).unwrap();
+                let key: [u8; 32] = h.finalize().into();
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&key[12..]);
+                address
+            })
+            .collect();
+        Guardians {
+            index,
+            keys,
+            secrets,
+        }
+    }
+
+    /// Sign a VAA with every guardian of the set.
+    pub fn sign(&self, vaa: &PostVAAData) -> VAA {
+        let message = Secp256k1Message::parse(&instructions::hash_vaa(vaa));
+        let signatures = self
+            .secrets
+            .iter()
+            .enumerate()
+            .map(|(i, secret)| {
+                let (signature, recovery_id) = secp256k1::sign(&message, secret).unwrap();
+                let mut signature = signature.serialize().to_vec();
+                signature.push(recovery_id.serialize());
+                VAASignature {
+                    signature,
+                    guardian_index: i as u8,
+                }
+            })
+            .collect();
+
+        VAA {
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            signatures,
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload.clone(),
+        }
+    }
+}
+
+/// The wormhole programs running on an in-process bank.
+pub struct Wormhole {
+    pub context: TestContext,
+    pub bridge: Pubkey,
+    pub token_bridge: Pubkey,
+    pub nft_bridge: Pubkey,
+    pub guardians: Guardians,
+
+    /// Next sequence of every emitter VAAs were fabricated for.
+    sequences: HashMap<(u16, [u8; 32]), u64>,
+}
+
+impl Wormhole {
+    /// A bank with the bridge, token bridge and NFT bridge registered, additional programs and
+    /// accounts can be added before passing it to `start_with`.
+    pub fn program_test() -> ProgramTest {
+        let mut test = ProgramTest::default();
+        test.add_program("bridge", bridge_id(), processor!(bridge::solitaire));
+        test.add_program(
+            "token_bridge",
+            token_bridge_id(),
+            processor!(token_bridge::solitaire),
+        );
+        test.add_program(
+            "nft_bridge",
+            nft_bridge_id(),
+            processor!(nft_bridge::solitaire),
+        );
+        test
+    }
+
+    /// Add the token metadata program, which attesting tokens and creating wrapped assets call.
+    /// It is only shipped as a binary and runs in the BPF VM.
+    pub fn add_token_metadata(test: &mut ProgramTest) {
+        test.add_account(
+            spl_token_metadata_id(),
+            Account {
+                lamports: 1_000_000_000,
+                data: SPL_TOKEN_METADATA.to_vec(),
+                owner: bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    /// Add the Token-2022 program from `tests/fixtures/spl_token_2022.so`, see the README there.
+    /// Like the token metadata program it runs in the BPF VM.
+    pub fn add_token_2022(test: &mut ProgramTest) {
+        test.add_program("spl_token_2022", token_bridge::token_2022::id(), None);
+    }
+
+    /// Start all programs and initialize them with a fresh guardian set.
+    pub async fn start() -> Self {
+        Self::start_with(Self::program_test()).await
+    }
+
+    pub async fn start_with(test: ProgramTest) -> Self {
+        let context = TestContext::start(test).await;
+        let mut wormhole = Wormhole {
+            context,
+            bridge: bridge_id(),
+            token_bridge: token_bridge_id(),
+            nft_bridge: nft_bridge_id(),
+            guardians: Guardians::generate(0, GUARDIAN_COUNT),
+            sequences: HashMap::new(),
+        };
+
+        let payer = wormhole.context.payer();
+        let initialize = [
+            instructions::initialize(
+                wormhole.bridge,
+                payer,
+                MESSAGE_FEE,
+                GUARDIAN_SET_EXPIRATION,
+                &wormhole.guardians.keys,
+            )
+            .unwrap(),
+            token_bridge::instructions::initialize(wormhole.token_bridge, payer, wormhole.bridge)
+                .unwrap(),
+            nft_bridge::instructions::initialize(wormhole.nft_bridge, payer, wormhole.bridge)
+                .unwrap(),
+        ];
+        wormhole.context.execute(&initialize, &[]).await.unwrap();
+        wormhole
+    }
+
+    /// Fabricate a VAA for the current guardian set, sequences count up per emitter.
+    pub fn vaa(
+        &mut self,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        payload: Vec<u8>,
+    ) -> PostVAAData {
+        let sequence = self
+            .sequences
+            .entry((emitter_chain, emitter_address))
+            .or_insert(0);
+        let vaa = PostVAAData {
+            version: 0,
+            guardian_set_index: self.guardians.index,
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as u32,
+            nonce: rand::random(),
+            emitter_chain,
+            emitter_address,
+            sequence: *sequence,
+            consistency_level: ConsistencyLevel::Confirmed as u8,
+            payload,
+        };
+        *sequence += 1;
+        vaa
+    }
+
+    /// Fabricate a VAA from the governance emitter.
+    pub fn governance_vaa(&mut self, payload: Vec<u8>) -> PostVAAData {
+        self.vaa(CHAIN_ID_SOLANA, governance_emitter(), payload)
+    }
+
+    /// Key of the account a VAA is posted to.
+    pub fn posted_vaa_key(&self, vaa: &PostVAAData) -> Pubkey {
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: instructions::hash_vaa(vaa).to_vec(),
+            },
+            &self.bridge,
+        )
+    }
+
+    /// Sign a VAA with the guardian set, verify the signatures and post it, returns the key of
+    /// the posted VAA.
+    pub async fn post_vaa(&mut self, vaa: &PostVAAData) -> Result<Pubkey, TransactionError> {
+        self.post_vaa_with(vaa, &Keypair::new()).await
+    }
+
+    /// Like `post_vaa`, recording the signatures in the account of `signature_set`.
+    pub async fn post_vaa_with(
+        &mut self,
+        vaa: &PostVAAData,
+        signature_set: &Keypair,
+    ) -> Result<Pubkey, TransactionError> {
+        let signed = self.guardians.sign(vaa);
+        let payer = self.context.payer();
+
+        let plan = instructions::verify_signatures_plan(
+            self.bridge,
+            payer,
+            signature_set.pubkey(),
+            &self.guardians.keys,
+            &signed,
+        )
+        .unwrap();
+        for batch in plan.iter() {
+            self.context.execute(batch, &[signature_set]).await?;
+        }
+
+        let post = instructions::post_vaa(self.bridge, payer, signature_set.pubkey(), vaa.clone());
+        self.context.execute(&[post], &[]).await?;
+        Ok(self.posted_vaa_key(vaa))
+    }
+
+    /// Post a message from `emitter`, paying the bridge fee. Returns the message account.
+    pub async fn post_message(
+        &mut self,
+        emitter: &Keypair,
+        payload: Vec<u8>,
+    ) -> Result<Pubkey, TransactionError> {
+        let bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, &self.bridge);
+        let bridge: BridgeData = self.context.data(&bridge_key).await;
+        let message = Keypair::new();
+        let payer = self.context.payer();
+
+        let ixs = [
+            system_instruction::transfer(
+                &payer,
+                &FeeCollector::<'_>::key(None, &self.bridge),
+                bridge.config.fee,
+            ),
+            instructions::post_message(
+                self.bridge,
+                payer,
+                emitter.pubkey(),
+                message.pubkey(),
+                rand::random(),
+                payload,
+                ConsistencyLevel::Confirmed,
+            )
+            .unwrap(),
+        ];
+        self.context.execute(&ixs, &[emitter, &message]).await?;
+        Ok(message.pubkey())
+    }
+
+    /// Register a foreign token bridge through a governance VAA.
+    pub async fn register_token_bridge_chain(
+        &mut self,
+        chain: u16,
+        endpoint_address: [u8; 32],
+    ) -> Result<(), TransactionError> {
+        let payload = token_bridge::messages::PayloadGovernanceRegisterChain {
+            chain,
+            endpoint_address,
+        };
+        // The token bridge payloads write their governance header themselves.
+        let vaa = self.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+        let message = self.post_vaa(&vaa).await?;
+        let ix = token_bridge::instructions::register_chain(
+            self.token_bridge,
+            self.bridge,
+            self.context.payer(),
+            message,
+            vaa,
+            payload,
+            token_bridge::RegisterChainData {},
+        )
+        .unwrap();
+        self.context.execute(&[ix], &[]).await
+    }
+
+    /// Create the wrapped asset of a token attested by a registered token bridge, returns the
+    /// wrapped mint. Requires the token metadata program, see `add_token_metadata`.
+    pub async fn create_wrapped(
+        &mut self,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        payload: token_bridge::messages::PayloadAssetMeta,
+    ) -> Result<Pubkey, TransactionError> {
+        let mint = token_bridge::accounts::WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+            &token_bridge::accounts::WrappedDerivationData {
+                token_chain: payload.token_chain,
+                token_address: payload.token_address,
+            },
+            &self.token_bridge,
+        );
+        let vaa = self.vaa(
+            emitter_chain,
+            emitter_address,
+            SerializePayload::try_to_vec(&payload).unwrap(),
+        );
+        let message = self.post_vaa(&vaa).await?;
+        let ix = token_bridge::instructions::create_wrapped(
+            self.token_bridge,
+            self.bridge,
+            self.context.payer(),
+            message,
+            vaa,
+            payload,
+            token_bridge::CreateWrappedData {},
+        )
+        .unwrap();
+        self.context.execute(&[ix], &[]).await?;
+        Ok(mint)
+    }
+
+    /// Register a foreign NFT bridge through a governance VAA.
+    pub async fn register_nft_bridge_chain(
+        &mut self,
+        chain: u16,
+        endpoint_address: [u8; 32],
+    ) -> Result<(), TransactionError> {
+        let payload = nft_bridge::messages::PayloadGovernanceRegisterChain {
+            chain,
+            endpoint_address,
+        };
+        // The NFT bridge payloads write their governance header themselves.
+        let vaa = self.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+        let message = self.post_vaa(&vaa).await?;
+        let ix = nft_bridge::instructions::register_chain(
+            self.nft_bridge,
+            self.bridge,
+            self.context.payer(),
+            message,
+            vaa,
+            payload,
+            nft_bridge::RegisterChainData {},
+        )
+        .unwrap();
+        self.context.execute(&[ix], &[]).await
+    }
+}
+
+fn spl_token_metadata_id() -> Pubkey {
+    Pubkey::from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").unwrap()
+}
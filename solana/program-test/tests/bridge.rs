@@ -0,0 +1,177 @@
+use bridge::{
+    accounts::{
+        Bridge,
+        BridgeData,
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        PostedMessageData,
+        PostedVAAData,
+    },
+    error::Error as BridgeError,
+    instructions,
+    types::GovernancePayloadSetMessageFee,
+    SerializeGovernancePayload,
+};
+use primitive_types::U256;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::{
+    Keypair,
+    Signer,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+use solitaire_client::DecodedError;
+use wormhole_program_test::*;
+
+fn bridge_key(wormhole: &Wormhole) -> Pubkey {
+    Bridge::<'_, { AccountState::Initialized }>::key(None, &wormhole.bridge)
+}
+
+#[tokio::test]
+async fn initialize() {
+    let mut wormhole = Wormhole::start().await;
+
+    let key = bridge_key(&wormhole);
+    let bridge: BridgeData = wormhole.context.data(&key).await;
+    assert_eq!(bridge.guardian_set_index, 0);
+    assert_eq!(bridge.config.fee, MESSAGE_FEE);
+    assert_eq!(
+        bridge.config.guardian_set_expiration_time,
+        GUARDIAN_SET_EXPIRATION
+    );
+
+    let key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        &wormhole.bridge,
+    );
+    let guardian_set: GuardianSetData = wormhole.context.data(&key).await;
+    assert_eq!(guardian_set.index, 0);
+    assert_eq!(guardian_set.keys, wormhole.guardians.keys);
+
+    // Guardian sets reserve room for the largest set.
+    let account = wormhole.context.account(&key).await.unwrap();
+    assert_eq!(account.data.len(), GuardianSetData::MAX_SIZE);
+}
+
+#[tokio::test]
+async fn post_message() {
+    let mut wormhole = Wormhole::start().await;
+    let emitter = Keypair::new();
+
+    let message = wormhole
+        .post_message(&emitter, b"hello".to_vec())
+        .await
+        .unwrap();
+
+    let posted: PostedMessageData = wormhole.context.data(&message).await;
+    assert_eq!(posted.0.emitter_chain, CHAIN_ID_SOLANA);
+    assert_eq!(posted.0.emitter_address, emitter.pubkey().to_bytes());
+    assert_eq!(posted.0.sequence, 0);
+    assert_eq!(posted.0.payload, b"hello".to_vec());
+}
+
+#[tokio::test]
+async fn post_vaa() {
+    let mut wormhole = Wormhole::start().await;
+
+    let vaa = wormhole.vaa(2, [7u8; 32], b"payload".to_vec());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+
+    let posted: PostedVAAData = wormhole.context.data(&message).await;
+    assert_eq!(posted.0.emitter_chain, 2);
+    assert_eq!(posted.0.emitter_address, [7u8; 32]);
+    assert_eq!(posted.0.sequence, vaa.sequence);
+    assert_eq!(posted.0.payload, vaa.payload);
+}
+
+#[tokio::test]
+async fn close_signature_set() {
+    let mut wormhole = Wormhole::start().await;
+
+    let vaa = wormhole.vaa(2, [7u8; 32], b"payload".to_vec());
+    let signature_set = Keypair::new();
+    let message = wormhole.post_vaa_with(&vaa, &signature_set).await.unwrap();
+    let rent = wormhole.context.lamports(&signature_set.pubkey()).await;
+    assert!(rent > 0);
+
+    // Only the keypair that created the signature set can close it.
+    let recipient = Keypair::new().pubkey();
+    let mut ix =
+        instructions::close_signature_set(wormhole.bridge, signature_set.pubkey(), recipient)
+            .unwrap();
+    ix.accounts[0].is_signer = false;
+    assert_error(
+        wormhole.context.execute(&[ix], &[]).await,
+        0,
+        DecodedError::<BridgeError>::Framework("InvalidSigner"),
+    );
+
+    let ix = instructions::close_signature_set(wormhole.bridge, signature_set.pubkey(), recipient)
+        .unwrap();
+    wormhole
+        .context
+        .execute(&[ix], &[&signature_set])
+        .await
+        .unwrap();
+    assert_eq!(wormhole.context.lamports(&recipient).await, rent);
+    assert!(wormhole
+        .context
+        .account(&signature_set.pubkey())
+        .await
+        .is_none());
+
+    // The posted VAA does not depend on it.
+    let posted: PostedVAAData = wormhole.context.data(&message).await;
+    assert_eq!(posted.0.payload, vaa.payload);
+}
+
+#[tokio::test]
+async fn set_fees() {
+    let mut wormhole = Wormhole::start().await;
+
+    let payload = GovernancePayloadSetMessageFee {
+        fee: U256::from(1000),
+    };
+    let vaa = wormhole.governance_vaa(payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+
+    let ix = instructions::set_fees(
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        Pubkey::new_from_array(vaa.emitter_address),
+        vaa.sequence,
+    );
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let key = bridge_key(&wormhole);
+    let bridge: BridgeData = wormhole.context.data(&key).await;
+    assert_eq!(bridge.config.fee, 1000);
+}
+
+#[tokio::test]
+async fn set_fees_rejects_foreign_emitter() {
+    let mut wormhole = Wormhole::start().await;
+
+    let payload = GovernancePayloadSetMessageFee {
+        fee: U256::from(1000),
+    };
+    let vaa = wormhole.vaa(CHAIN_ID_SOLANA, [9u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+
+    let ix = instructions::set_fees(
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        Pubkey::new_from_array(vaa.emitter_address),
+        vaa.sequence,
+    );
+    assert_error(
+        wormhole.context.execute(&[ix], &[]).await,
+        0,
+        DecodedError::Program(BridgeError::InvalidGovernanceKey),
+    );
+}
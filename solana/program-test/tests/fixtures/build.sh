@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# This script builds the Token-2022 program loaded by the token bridge tests, see README.md.
+set -euo pipefail
+
+VERSION=0.2.0
+FIXTURES="$(cd "$(dirname "$0")" && pwd)"
+WORKDIR="$(mktemp -d)"
+trap 'rm -rf "$WORKDIR"' EXIT
+
+curl -sSfL "https://crates.io/api/v1/crates/spl-token-2022/$VERSION/download" |
+  tar -xz -C "$WORKDIR"
+cargo build-bpf \
+  --manifest-path "$WORKDIR/spl-token-2022-$VERSION/Cargo.toml" \
+  --bpf-out-dir "$WORKDIR/deploy"
+cp "$WORKDIR/deploy/spl_token_2022.so" "$FIXTURES/spl_token_2022.so"
@@ -0,0 +1,65 @@
+use nft_bridge::{
+    accounts::{
+        ConfigAccount,
+        Endpoint,
+        EndpointDerivationData,
+    },
+    types::{
+        Config,
+        EndpointRegistration,
+    },
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+use solitaire_client::DecodedError;
+use wormhole_program_test::*;
+
+#[tokio::test]
+async fn initialize() {
+    let mut wormhole = Wormhole::start().await;
+
+    let key = ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &wormhole.nft_bridge);
+    let config: Config = wormhole.context.data(&key).await;
+    assert_eq!(config.wormhole_bridge, wormhole.bridge);
+}
+
+#[tokio::test]
+async fn register_chain() {
+    let mut wormhole = Wormhole::start().await;
+
+    wormhole
+        .register_nft_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let key = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: 2,
+            emitter_address: [1u8; 32],
+        },
+        &wormhole.nft_bridge,
+    );
+    let endpoint: EndpointRegistration = wormhole.context.data(&key).await;
+    assert_eq!(endpoint.chain, 2);
+    assert_eq!(endpoint.contract, [1u8; 32]);
+}
+
+#[tokio::test]
+async fn register_chain_twice() {
+    let mut wormhole = Wormhole::start().await;
+
+    wormhole
+        .register_nft_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    // A second registration carries a new VAA, so it is the endpoint rather than the claim that
+    // rejects it.
+    assert_error(
+        wormhole.register_nft_bridge_chain(2, [1u8; 32]).await,
+        0,
+        DecodedError::<nft_bridge::TokenBridgeError>::Framework("AlreadyInitialized"),
+    );
+}
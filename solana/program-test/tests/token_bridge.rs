@@ -0,0 +1,1700 @@
+use bridge::{
+    accounts::{
+        Claim,
+        ClaimDerivationData,
+        PostedMessageData,
+    },
+    vaa::{
+        DeserializePayload,
+        SerializePayload,
+    },
+    PostVAAData,
+};
+use primitive_types::U256;
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+};
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    system_instruction,
+    transaction::TransactionError,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+use solitaire_client::DecodedError;
+use spl_token::state::{
+    Account,
+    Mint,
+};
+use spl_token_metadata::state::Metadata;
+use token_bridge::{
+    accounts::{
+        AuthoritySigner,
+        ChainLimitDerivationData,
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodyLedgerAccount,
+        CustodyLedgerDerivationData,
+        Endpoint,
+        EndpointDerivationData,
+        MintLimitDerivationData,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        TransferLimitAccount,
+        WrappedMetadataOverrideAccount,
+        WrappedMetadataOverrideDerivationData,
+    },
+    instructions,
+    messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetMintLimit,
+        GovernancePayloadSetWrappedMetadata,
+        GovernancePayloadUpdateChain,
+        PayloadAssetMeta,
+        PayloadTransfer,
+    },
+    token_2022,
+    types::{
+        Config,
+        CustodyLedger,
+        EndpointRegistration,
+        PendingTransfer,
+        SplAccount,
+        TransferLimit,
+        WrappedMetadataOverride,
+    },
+    AuditCustodyData,
+    CompleteNativeData,
+    CompleteNativeSolData,
+    CompleteWrappedData,
+    ReleasePendingTransferData,
+    SetChainLimitData,
+    SetMintLimitData,
+    SetWrappedMetadataData,
+    TokenBridgeError,
+    TransferNativeData,
+    TransferNativeSolData,
+    UpdateChainData,
+};
+use wormhole_program_test::*;
+
+#[tokio::test]
+async fn initialize() {
+    let mut wormhole = Wormhole::start().await;
+
+    let key = ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &wormhole.token_bridge);
+    let config: Config = wormhole.context.data(&key).await;
+    assert_eq!(config.wormhole_bridge, wormhole.bridge);
+}
+
+#[tokio::test]
+async fn register_chain() {
+    let mut wormhole = Wormhole::start().await;
+
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let key = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: 2,
+            emitter_address: [1u8; 32],
+        },
+        &wormhole.token_bridge,
+    );
+    let endpoint: EndpointRegistration = wormhole.context.data(&key).await;
+    assert_eq!(endpoint.chain, 2);
+    assert_eq!(endpoint.contract, [1u8; 32]);
+}
+
+#[tokio::test]
+async fn register_chain_twice() {
+    let mut wormhole = Wormhole::start().await;
+
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    // A second registration carries a new VAA, so it is the endpoint rather than the claim that
+    // rejects it.
+    assert_error(
+        wormhole.register_token_bridge_chain(2, [1u8; 32]).await,
+        0,
+        DecodedError::<token_bridge::TokenBridgeError>::Framework("AlreadyInitialized"),
+    );
+}
+
+#[tokio::test]
+async fn replace_chain() {
+    let mut wormhole = Wormhole::start().await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let message = Keypair::new();
+    let ix = instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let payload = GovernancePayloadUpdateChain {
+        chain: 2,
+        endpoint_address: [1u8; 32],
+        new_endpoint_address: [3u8; 32],
+    };
+    let vaa = wormhole.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::update_chain(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        payload,
+        UpdateChainData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let token_bridge = wormhole.token_bridge;
+    let endpoint = |address| {
+        Endpoint::<'_, { AccountState::Initialized }>::key(
+            &EndpointDerivationData {
+                emitter_chain: 2,
+                emitter_address: address,
+            },
+            &token_bridge,
+        )
+    };
+    let old: EndpointRegistration = wormhole.context.data(&endpoint([1u8; 32])).await;
+    assert!(!old.is_registered());
+    let new: EndpointRegistration = wormhole.context.data(&endpoint([3u8; 32])).await;
+    assert_eq!(new.chain, 2);
+    assert_eq!(new.contract, [3u8; 32]);
+
+    // Transfers are only accepted from the new endpoint.
+    let recipient = Keypair::new().pubkey();
+    let payload = PayloadTransfer {
+        amount: U256::from(10_000_000),
+        token_address: spl_token::native_mint::id().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: recipient.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0),
+    };
+    for (emitter, result) in [
+        ([1u8; 32], Err(TokenBridgeError::EndpointDeregistered)),
+        ([3u8; 32], Ok(())),
+    ] {
+        let vaa = wormhole.vaa(2, emitter, payload.try_to_vec().unwrap());
+        let message = wormhole.post_vaa(&vaa).await.unwrap();
+        let ix = instructions::complete_native_sol(
+            wormhole.token_bridge,
+            wormhole.bridge,
+            wormhole.context.payer(),
+            message,
+            vaa,
+            recipient,
+            CompleteNativeSolData {},
+        )
+        .unwrap();
+        match result {
+            Ok(()) => wormhole.context.execute(&[ix], &[]).await.unwrap(),
+            Err(error) => assert_error(
+                wormhole.context.execute(&[ix], &[]).await,
+                0,
+                DecodedError::Program(error),
+            ),
+        }
+    }
+    assert_eq!(wormhole.context.lamports(&recipient).await, 100_000_000);
+}
+
+#[tokio::test]
+async fn register_deregistered_chain() {
+    let mut wormhole = Wormhole::start().await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+    update_chain(&mut wormhole, 2, [1u8; 32], [0u8; 32])
+        .await
+        .unwrap();
+
+    let key = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: 2,
+            emitter_address: [1u8; 32],
+        },
+        &wormhole.token_bridge,
+    );
+    let endpoint: EndpointRegistration = wormhole.context.data(&key).await;
+    assert!(!endpoint.is_registered());
+
+    // The account is kept when the endpoint is deregistered, registering it again re-enables it.
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+    let endpoint: EndpointRegistration = wormhole.context.data(&key).await;
+    assert_eq!(endpoint.chain, 2);
+    assert_eq!(endpoint.contract, [1u8; 32]);
+
+    assert_error(
+        wormhole.register_token_bridge_chain(2, [1u8; 32]).await,
+        0,
+        DecodedError::<token_bridge::TokenBridgeError>::Framework("AlreadyInitialized"),
+    );
+}
+
+#[tokio::test]
+async fn native_sol_round_trip() {
+    let mut wormhole = Wormhole::start().await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let mint = spl_token::native_mint::id();
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+
+    // SOL has 9 decimals, the last digit is truncated and stays with the sender.
+    let message = Keypair::new();
+    let ix = instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_005,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert!(account.is_native());
+    assert_eq!(account.amount, 1_000_000_000);
+
+    let posted: PostedMessageData = wormhole.context.data(&message.pubkey()).await;
+    let payload = PayloadTransfer::deserialize(&mut posted.0.payload.as_slice()).unwrap();
+    assert_eq!(payload.amount, U256::from(100_000_000));
+    assert_eq!(payload.token_address, mint.to_bytes());
+    assert_eq!(payload.token_chain, CHAIN_ID_SOLANA);
+
+    // Half of it comes back to a fresh wallet. A relayer other than the payer of the transactions
+    // submits it, so that its balance can be checked exactly, and keeps the fee.
+    let relayer = Keypair::new();
+    wormhole
+        .context
+        .airdrop(&relayer.pubkey(), 1_000_000_000)
+        .await;
+    let recipient = Keypair::new().pubkey();
+    let payload = PayloadTransfer {
+        amount: U256::from(50_000_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: recipient.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(1_000_000),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+
+    let ix = instructions::complete_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        relayer.pubkey(),
+        message,
+        vaa.clone(),
+        recipient,
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&relayer]).await.unwrap();
+
+    // The relayer pays for the claim, the rent of the unwrap account is refunded.
+    let claim = claim_rent(&mut wormhole, &vaa).await;
+    assert_eq!(wormhole.context.lamports(&recipient).await, 490_000_000);
+    assert_eq!(
+        wormhole.context.lamports(&relayer.pubkey()).await,
+        1_000_000_000 + 10_000_000 - claim
+    );
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 500_000_000);
+
+    // Recipients redeeming their own transfer keep the fee.
+    let payload = PayloadTransfer {
+        amount: U256::from(20_000_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: relayer.pubkey().to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(1_000_000),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let balance = wormhole.context.lamports(&relayer.pubkey()).await;
+    let ix = instructions::complete_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        relayer.pubkey(),
+        message,
+        vaa.clone(),
+        relayer.pubkey(),
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&relayer]).await.unwrap();
+
+    let claim = claim_rent(&mut wormhole, &vaa).await;
+    assert_eq!(
+        wormhole.context.lamports(&relayer.pubkey()).await,
+        balance + 200_000_000 - claim
+    );
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 300_000_000);
+}
+
+#[tokio::test]
+async fn spl_relayer_fee() {
+    let mut wormhole = Wormhole::start().await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let payer = wormhole.context.payer();
+    let mint = create_mint(&mut wormhole, 6).await;
+    let from = create_token_account(&mut wormhole, spl_token::id(), mint, payer).await;
+    let mint_to =
+        spl_token::instruction::mint_to(&spl_token::id(), &mint, &from, &payer, &[], 1_000_000)
+            .unwrap();
+    let approve = spl_token::instruction::approve(
+        &spl_token::id(),
+        &from,
+        &AuthoritySigner::key(None, &wormhole.token_bridge),
+        &payer,
+        &[],
+        1_000_000,
+    )
+    .unwrap();
+    let message = Keypair::new();
+    let ix = instructions::transfer_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message.pubkey(),
+        from,
+        mint,
+        TransferNativeData {
+            nonce: 0,
+            amount: 1_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole
+        .context
+        .execute(&[mint_to, approve, ix], &[&message])
+        .await
+        .unwrap();
+
+    // Recipients redeeming their own transfer keep the fee, no fee account is needed.
+    let payload = PayloadTransfer {
+        amount: U256::from(300_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: from.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(20_000),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message,
+        vaa,
+        from,
+        None,
+        mint,
+        CompleteNativeData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&from).await;
+    assert_eq!(account.amount, 300_000);
+
+    // A relayer redeeming for someone else collects the fee in its own token account.
+    let relayer = Keypair::new();
+    wormhole
+        .context
+        .airdrop(&relayer.pubkey(), 1_000_000_000)
+        .await;
+    let to_fees =
+        create_token_account(&mut wormhole, spl_token::id(), mint, relayer.pubkey()).await;
+    let to = create_token_account(
+        &mut wormhole,
+        spl_token::id(),
+        mint,
+        Keypair::new().pubkey(),
+    )
+    .await;
+    let payload = PayloadTransfer {
+        amount: U256::from(200_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: to.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(20_000),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        relayer.pubkey(),
+        message,
+        vaa,
+        to,
+        Some(to_fees),
+        mint,
+        CompleteNativeData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&relayer]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&to).await;
+    assert_eq!(account.amount, 180_000);
+    let account: SplAccount = wormhole.context.data(&to_fees).await;
+    assert_eq!(account.amount, 20_000);
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 500_000);
+}
+
+#[tokio::test]
+async fn wrapped_relayer_fee() {
+    let mut test = Wormhole::program_test();
+    Wormhole::add_token_metadata(&mut test);
+    let mut wormhole = Wormhole::start_with(test).await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+    let mint = wormhole
+        .create_wrapped(
+            2,
+            [1u8; 32],
+            PayloadAssetMeta {
+                token_address: [9u8; 32],
+                token_chain: 2,
+                decimals: 8,
+                symbol: "WRP".to_string(),
+                name: "Wrapped".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // Recipients redeeming their own transfer keep the fee, no fee account is needed.
+    let payer = wormhole.context.payer();
+    let own = create_token_account(&mut wormhole, spl_token::id(), mint, payer).await;
+    let payload = PayloadTransfer {
+        amount: U256::from(5_000),
+        token_address: [9u8; 32],
+        token_chain: 2,
+        to: own.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(500),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_wrapped(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message,
+        vaa,
+        payload,
+        own,
+        None,
+        CompleteWrappedData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&own).await;
+    assert_eq!(account.amount, 5_000);
+
+    // A relayer redeeming for someone else has the fee minted to its own token account.
+    let relayer = Keypair::new();
+    wormhole
+        .context
+        .airdrop(&relayer.pubkey(), 1_000_000_000)
+        .await;
+    let to_fees =
+        create_token_account(&mut wormhole, spl_token::id(), mint, relayer.pubkey()).await;
+    let to = create_token_account(
+        &mut wormhole,
+        spl_token::id(),
+        mint,
+        Keypair::new().pubkey(),
+    )
+    .await;
+    let payload = PayloadTransfer {
+        amount: U256::from(5_000),
+        token_address: [9u8; 32],
+        token_chain: 2,
+        to: to.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(500),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_wrapped(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        relayer.pubkey(),
+        message,
+        vaa,
+        payload,
+        to,
+        Some(to_fees),
+        CompleteWrappedData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&relayer]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&to).await;
+    assert_eq!(account.amount, 4_500);
+    let account: SplAccount = wormhole.context.data(&to_fees).await;
+    assert_eq!(account.amount, 500);
+}
+
+#[tokio::test]
+async fn custody_ledger() {
+    let mut wormhole = Wormhole::start().await;
+
+    let mint = spl_token::native_mint::id();
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+    let (ledger, ledger_bump) =
+        CustodyLedgerAccount::<'_, { AccountState::Initialized }>::key_and_bump(
+            &CustodyLedgerDerivationData { mint },
+            &wormhole.token_bridge,
+        );
+
+    let message = Keypair::new();
+    let ix = instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 1_000_000_000);
+    assert_eq!(recorded.bump, ledger_bump);
+
+    let ix = instructions::audit_custody(wormhole.token_bridge, mint, AuditCustodyData {}).unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    // Lamports sent to the custody account directly are a surplus, which the audit accepts.
+    let donation = system_instruction::transfer(&wormhole.context.payer(), &custody, 5);
+    let sync = spl_token::instruction::sync_native(&spl_token::id(), &custody).unwrap();
+    wormhole
+        .context
+        .execute(&[donation, sync], &[])
+        .await
+        .unwrap();
+
+    let ix = instructions::audit_custody(wormhole.token_bridge, mint, AuditCustodyData {}).unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 1_000_000_000);
+
+    // Transfers out of custody are deducted from the ledger, the surplus stays unrecorded.
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+    let recipient = Keypair::new().pubkey();
+    let payload = PayloadTransfer {
+        amount: U256::from(40_000_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: recipient.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        recipient,
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 600_000_000);
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 600_000_005);
+
+    let ix = instructions::audit_custody(wormhole.token_bridge, mint, AuditCustodyData {}).unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn custody_shortfall() {
+    let mint = spl_token::native_mint::id();
+    let (ledger, bump) = CustodyLedgerAccount::<'_, { AccountState::Initialized }>::key_and_bump(
+        &CustodyLedgerDerivationData { mint },
+        &token_bridge_id(),
+    );
+
+    // A ledger recording more than custody will hold, as an accounting error in the bridge or
+    // tokens leaving custody behind its back would leave it.
+    let mut test = Wormhole::program_test();
+    let data = borsh::BorshSerialize::try_to_vec(&CustodyLedger { balance: 5, bump }).unwrap();
+    test.add_account(
+        ledger,
+        SolanaAccount {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: token_bridge_id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut wormhole = Wormhole::start_with(test).await;
+
+    let message = Keypair::new();
+    let ix = instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 1_000_000_005);
+
+    let ix = instructions::audit_custody(wormhole.token_bridge, mint, AuditCustodyData {}).unwrap();
+    assert_error(
+        wormhole.context.execute(&[ix], &[]).await,
+        0,
+        DecodedError::Program(TokenBridgeError::CustodyShortfall),
+    );
+}
+
+#[tokio::test]
+async fn set_wrapped_metadata() {
+    let mut test = Wormhole::program_test();
+    Wormhole::add_token_metadata(&mut test);
+    let mut wormhole = Wormhole::start_with(test).await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+    let mint = wormhole
+        .create_wrapped(
+            2,
+            [1u8; 32],
+            PayloadAssetMeta {
+                token_address: [9u8; 32],
+                token_chain: 2,
+                decimals: 8,
+                symbol: "WRP".to_string(),
+                name: "Wrapped".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint },
+        &spl_token_metadata::id(),
+    );
+    assert_eq!(
+        metadata(&mut wormhole, &spl_metadata).await,
+        (
+            "Wrapped (Wormhole ETH)".to_string(),
+            "WRP".to_string(),
+            "".to_string()
+        )
+    );
+
+    let payload = GovernancePayloadSetWrappedMetadata {
+        token_chain: 2,
+        token_address: [9u8; 32],
+        symbol: "FIX".to_string(),
+        name: "Fixed".to_string(),
+        uri: "https://example.com/fixed.json".to_string(),
+    };
+    let vaa = wormhole.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::set_wrapped_metadata(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        payload,
+        SetWrappedMetadataData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let key = WrappedMetadataOverrideAccount::<'_, { AccountState::Initialized }>::key(
+        &WrappedMetadataOverrideDerivationData { mint_key: mint },
+        &wormhole.token_bridge,
+    );
+    let stored: WrappedMetadataOverride = wormhole.context.data(&key).await;
+    assert_eq!(stored.symbol, "FIX");
+    assert_eq!(stored.name, "Fixed");
+    assert_eq!(stored.uri, "https://example.com/fixed.json");
+    let fixed = (
+        "Fixed (Wormhole ETH)".to_string(),
+        "FIX".to_string(),
+        "https://example.com/fixed.json".to_string(),
+    );
+    assert_eq!(metadata(&mut wormhole, &spl_metadata).await, fixed);
+
+    // A later attestation re-applies the metadata set by governance rather than its own.
+    wormhole
+        .create_wrapped(
+            2,
+            [1u8; 32],
+            PayloadAssetMeta {
+                token_address: [9u8; 32],
+                token_chain: 2,
+                decimals: 8,
+                symbol: "MIS".to_string(),
+                name: "Misleading".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(metadata(&mut wormhole, &spl_metadata).await, fixed);
+}
+
+#[tokio::test]
+async fn chain_limit() {
+    let mut wormhole = Wormhole::start().await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let message = Keypair::new();
+    let ix = instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let payload = GovernancePayloadSetChainLimit {
+        chain: 2,
+        limit: 10_000_000,
+        window: 86400,
+        delay: 3600,
+    };
+    let vaa = wormhole.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::set_chain_limit(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        payload,
+        SetChainLimitData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let (key, bump) = TransferLimitAccount::<'_, { AccountState::Initialized }>::key_and_bump(
+        &ChainLimitDerivationData { chain: 2 },
+        &wormhole.token_bridge,
+    );
+    let limit: TransferLimit = wormhole.context.data(&key).await;
+    assert_eq!(limit.bump, bump);
+    assert_eq!(limit.limit, 10_000_000);
+    assert_eq!(limit.window, 86400);
+    assert_eq!(limit.delay, 3600);
+    assert_eq!(limit.outflow, 0);
+
+    // A SOL transfer over the limit is queued. It is relayed by an account other than the payer of
+    // the transactions, so that its balance can be checked exactly.
+    let relayer = Keypair::new();
+    wormhole
+        .context
+        .airdrop(&relayer.pubkey(), 1_000_000_000)
+        .await;
+    let recipient = Keypair::new().pubkey();
+    let mint = spl_token::native_mint::id();
+    let payload = PayloadTransfer {
+        amount: U256::from(50_000_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: recipient.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(1_000_000),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        relayer.pubkey(),
+        message,
+        vaa.clone(),
+        recipient,
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&relayer]).await.unwrap();
+
+    assert_eq!(wormhole.context.lamports(&recipient).await, 0);
+    let pending_key = instructions::pending_transfer_key(wormhole.token_bridge, &vaa);
+    let pending = pending_transfer(&mut wormhole, &pending_key).await;
+    assert_eq!(pending.payer, relayer.pubkey());
+    assert_eq!(pending.to, recipient);
+    assert_eq!(pending.to_fees, relayer.pubkey());
+    assert_eq!(pending.amount, 500_000_000);
+    assert_eq!(pending.fee, 10_000_000);
+    assert_eq!(pending.native, 1);
+    assert_eq!(pending.lamports, 1);
+    let limit: TransferLimit = wormhole.context.data(&key).await;
+    assert_eq!(limit.outflow, 0);
+
+    // It cannot be released before the delay has passed.
+    let release = instructions::release_pending_transfer(
+        wormhole.token_bridge,
+        relayer.pubkey(),
+        &vaa,
+        recipient,
+        relayer.pubkey(),
+        mint,
+        ReleasePendingTransferData {},
+    )
+    .unwrap();
+    assert_error(
+        wormhole.context.execute(&[release.clone()], &[]).await,
+        0,
+        DecodedError::Program(TokenBridgeError::TransferPending),
+    );
+
+    // Afterwards anyone can release it, the relayer keeps the fee and gets its deposit back.
+    let deposit = wormhole.context.lamports(&pending_key).await;
+    let relayer_balance = wormhole.context.lamports(&relayer.pubkey()).await;
+    wormhole.context.warp_time(3600).await;
+    wormhole.context.execute(&[release], &[]).await.unwrap();
+
+    assert_eq!(wormhole.context.lamports(&recipient).await, 490_000_000);
+    assert_eq!(
+        wormhole.context.lamports(&relayer.pubkey()).await,
+        relayer_balance + 10_000_000 + deposit
+    );
+    assert!(wormhole.context.account(&pending_key).await.is_none());
+
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 500_000_000);
+    let ledger = CustodyLedgerAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyLedgerDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 500_000_000);
+
+    // Transfers within the limit are counted against it.
+    let payload = PayloadTransfer {
+        amount: U256::from(4_000_000),
+        fee: U256::from(0),
+        ..payload
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        recipient,
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    assert_eq!(wormhole.context.lamports(&recipient).await, 530_000_000);
+    let limit: TransferLimit = wormhole.context.data(&key).await;
+    assert_eq!(limit.outflow, 4_000_000);
+}
+
+#[tokio::test]
+async fn release_from_deregistered_endpoint() {
+    let mut wormhole = Wormhole::start().await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let message = Keypair::new();
+    let ix = instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let payload = GovernancePayloadSetChainLimit {
+        chain: 2,
+        limit: 10_000_000,
+        window: 86400,
+        delay: 3600,
+    };
+    let vaa = wormhole.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::set_chain_limit(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        payload,
+        SetChainLimitData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    let payload = PayloadTransfer {
+        amount: U256::from(50_000_000),
+        token_address: spl_token::native_mint::id().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: recipient.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa.clone(),
+        recipient,
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    // The endpoint is deregistered while the transfer is queued, it is never paid out.
+    update_chain(&mut wormhole, 2, [1u8; 32], [0u8; 32])
+        .await
+        .unwrap();
+    wormhole.context.warp_time(3600).await;
+
+    let ix = instructions::release_pending_transfer(
+        wormhole.token_bridge,
+        wormhole.context.payer(),
+        &vaa,
+        recipient,
+        wormhole.context.payer(),
+        spl_token::native_mint::id(),
+        ReleasePendingTransferData {},
+    )
+    .unwrap();
+    assert_error(
+        wormhole.context.execute(&[ix], &[]).await,
+        0,
+        DecodedError::Program(TokenBridgeError::EndpointDeregistered),
+    );
+    assert_eq!(wormhole.context.lamports(&recipient).await, 0);
+}
+
+#[tokio::test]
+async fn mint_limit() {
+    let mut test = Wormhole::program_test();
+    Wormhole::add_token_metadata(&mut test);
+    let mut wormhole = Wormhole::start_with(test).await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+    let mint = wormhole
+        .create_wrapped(
+            2,
+            [1u8; 32],
+            PayloadAssetMeta {
+                token_address: [9u8; 32],
+                token_chain: 2,
+                decimals: 8,
+                symbol: "WRP".to_string(),
+                name: "Wrapped".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let payload = GovernancePayloadSetMintLimit {
+        mint,
+        limit: 1_000,
+        window: 86400,
+        delay: 600,
+    };
+    let vaa = wormhole.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::set_mint_limit(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        payload,
+        SetMintLimitData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let key = TransferLimitAccount::<'_, { AccountState::Initialized }>::key(
+        &MintLimitDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+    let limit: TransferLimit = wormhole.context.data(&key).await;
+    assert_eq!(limit.limit, 1_000);
+    assert_eq!(limit.window, 86400);
+    assert_eq!(limit.delay, 600);
+    assert_eq!(limit.outflow, 0);
+
+    // A transfer over the limit is not minted until it is released.
+    let to = create_token_account(
+        &mut wormhole,
+        spl_token::id(),
+        mint,
+        Keypair::new().pubkey(),
+    )
+    .await;
+    let payload = PayloadTransfer {
+        amount: U256::from(5_000),
+        token_address: [9u8; 32],
+        token_chain: 2,
+        to: to.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_wrapped(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa.clone(),
+        payload,
+        to,
+        None,
+        CompleteWrappedData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&to).await;
+    assert_eq!(account.amount, 0);
+    let pending_key = instructions::pending_transfer_key(wormhole.token_bridge, &vaa);
+    let pending = pending_transfer(&mut wormhole, &pending_key).await;
+    assert_eq!(pending.mint, mint);
+    assert_eq!(pending.amount, 5_000);
+    assert_eq!(pending.native, 0);
+
+    let release = instructions::release_pending_transfer(
+        wormhole.token_bridge,
+        wormhole.context.payer(),
+        &vaa,
+        to,
+        to,
+        mint,
+        ReleasePendingTransferData {},
+    )
+    .unwrap();
+    assert_error(
+        wormhole.context.execute(&[release.clone()], &[]).await,
+        0,
+        DecodedError::Program(TokenBridgeError::TransferPending),
+    );
+
+    wormhole.context.warp_time(600).await;
+    wormhole.context.execute(&[release], &[]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&to).await;
+    assert_eq!(account.amount, 5_000);
+    assert!(wormhole.context.account(&pending_key).await.is_none());
+}
+
+#[tokio::test]
+async fn token_2022_round_trip() {
+    let mut test = Wormhole::program_test();
+    Wormhole::add_token_2022(&mut test);
+    let mut wormhole = Wormhole::start_with(test).await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let payer = wormhole.context.payer();
+    let mint = create_token_2022_mint(&mut wormhole, 6, None).await;
+    let from = create_token_account(&mut wormhole, token_2022::id(), mint, payer).await;
+    let mut mint_to =
+        spl_token::instruction::mint_to(&spl_token::id(), &mint, &from, &payer, &[], 1_000_000)
+            .unwrap();
+    mint_to.program_id = token_2022::id();
+    wormhole.context.execute(&[mint_to], &[]).await.unwrap();
+
+    // Attest
+    let message = Keypair::new();
+    let ix = instructions::attest(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message.pubkey(),
+        mint,
+        0,
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let posted: PostedMessageData = wormhole.context.data(&message.pubkey()).await;
+    let meta = PayloadAssetMeta::deserialize(&mut posted.0.payload.as_slice()).unwrap();
+    assert_eq!(meta.token_address, mint.to_bytes());
+    assert_eq!(meta.decimals, 6);
+
+    // Lock, the custody account is opened with Token-2022 and tokens move with TransferChecked.
+    let mut approve = spl_token::instruction::approve(
+        &spl_token::id(),
+        &from,
+        &AuthoritySigner::key(None, &wormhole.token_bridge),
+        &payer,
+        &[],
+        400_000,
+    )
+    .unwrap();
+    approve.program_id = token_2022::id();
+    let message = Keypair::new();
+    let ix = instructions::transfer_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message.pubkey(),
+        from,
+        mint,
+        TransferNativeData {
+            nonce: 0,
+            amount: 400_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole
+        .context
+        .execute(&[approve, ix], &[&message])
+        .await
+        .unwrap();
+
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+    let account = wormhole.context.account(&custody).await.unwrap();
+    assert_eq!(account.owner, token_2022::id());
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 400_000);
+    let ledger = CustodyLedgerAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyLedgerDerivationData { mint },
+        &wormhole.token_bridge,
+    );
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 400_000);
+
+    let posted: PostedMessageData = wormhole.context.data(&message.pubkey()).await;
+    let payload = PayloadTransfer::deserialize(&mut posted.0.payload.as_slice()).unwrap();
+    assert_eq!(payload.amount, U256::from(400_000));
+    assert_eq!(payload.token_address, mint.to_bytes());
+
+    // Part of it comes back, redeemed by the owner of the recipient account.
+    let payload = PayloadTransfer {
+        amount: U256::from(150_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: from.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message,
+        vaa,
+        from,
+        None,
+        mint,
+        CompleteNativeData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&from).await;
+    assert_eq!(account.amount, 750_000);
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 250_000);
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 250_000);
+
+    // A second lock goes into the custody account opened by the first one.
+    let mut approve = spl_token::instruction::approve(
+        &spl_token::id(),
+        &from,
+        &AuthoritySigner::key(None, &wormhole.token_bridge),
+        &payer,
+        &[],
+        100_000,
+    )
+    .unwrap();
+    approve.program_id = token_2022::id();
+    let message = Keypair::new();
+    let ix = instructions::transfer_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message.pubkey(),
+        from,
+        mint,
+        TransferNativeData {
+            nonce: 1,
+            amount: 100_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole
+        .context
+        .execute(&[approve, ix], &[&message])
+        .await
+        .unwrap();
+
+    let account: SplAccount = wormhole.context.data(&from).await;
+    assert_eq!(account.amount, 650_000);
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 350_000);
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 350_000);
+
+    // And a second redeem, to another recipient, is paid out of it.
+    let to = create_token_account(
+        &mut wormhole,
+        token_2022::id(),
+        mint,
+        Keypair::new().pubkey(),
+    )
+    .await;
+    let payload = PayloadTransfer {
+        amount: U256::from(50_000),
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: to.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let ix = instructions::complete_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message,
+        vaa,
+        to,
+        None,
+        mint,
+        CompleteNativeData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+
+    let account: SplAccount = wormhole.context.data(&to).await;
+    assert_eq!(account.amount, 50_000);
+    let account: SplAccount = wormhole.context.data(&custody).await;
+    assert_eq!(account.amount, 300_000);
+    let recorded: CustodyLedger = wormhole.context.data(&ledger).await;
+    assert_eq!(recorded.balance, 300_000);
+
+    let ix = instructions::audit_custody(wormhole.token_bridge, mint, AuditCustodyData {}).unwrap();
+    wormhole.context.execute(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn token_2022_unsupported_extension() {
+    let mut test = Wormhole::program_test();
+    Wormhole::add_token_2022(&mut test);
+    let mut wormhole = Wormhole::start_with(test).await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    // A mint with a close authority could be closed and recreated while tokens are in custody.
+    let payer = wormhole.context.payer();
+    let mint = create_token_2022_mint(&mut wormhole, 6, Some(payer)).await;
+    let from = create_token_account(&mut wormhole, token_2022::id(), mint, payer).await;
+
+    let message = Keypair::new();
+    let ix = instructions::attest(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message.pubkey(),
+        mint,
+        0,
+    )
+    .unwrap();
+    assert_error(
+        wormhole.context.execute(&[ix], &[&message]).await,
+        0,
+        DecodedError::Program(TokenBridgeError::UnsupportedMintExtension),
+    );
+
+    let message = Keypair::new();
+    let ix = instructions::transfer_native(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        payer,
+        message.pubkey(),
+        from,
+        mint,
+        TransferNativeData {
+            nonce: 0,
+            amount: 0,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    assert_error(
+        wormhole.context.execute(&[ix], &[&message]).await,
+        0,
+        DecodedError::Program(TokenBridgeError::UnsupportedMintExtension),
+    );
+}
+
+#[tokio::test]
+async fn relayer_fee_goes_to_payer() {
+    let mut wormhole = Wormhole::start().await;
+    wormhole
+        .register_token_bridge_chain(2, [1u8; 32])
+        .await
+        .unwrap();
+
+    let message = Keypair::new();
+    let ix = instructions::transfer_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message.pubkey(),
+        TransferNativeSolData {
+            nonce: 0,
+            amount: 1_000_000_000,
+            fee: 0,
+            target_address: [2u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[&message]).await.unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    let payload = PayloadTransfer {
+        amount: U256::from(50_000_000),
+        token_address: spl_token::native_mint::id().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: recipient.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(1_000_000),
+    };
+    let vaa = wormhole.vaa(2, [1u8; 32], payload.try_to_vec().unwrap());
+    let message = wormhole.post_vaa(&vaa).await.unwrap();
+    let mut ix = instructions::complete_native_sol(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        recipient,
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+
+    // Only the submitter of the transfer can collect the fee.
+    ix.accounts[6].pubkey = Keypair::new().pubkey();
+    assert_error(
+        wormhole.context.execute(&[ix], &[]).await,
+        0,
+        DecodedError::Program(TokenBridgeError::InvalidFeeRecipient),
+    );
+}
+
+/// Replace or, with a zero `new_endpoint_address`, deregister an endpoint through governance.
+async fn update_chain(
+    wormhole: &mut Wormhole,
+    chain: u16,
+    endpoint_address: [u8; 32],
+    new_endpoint_address: [u8; 32],
+) -> Result<(), TransactionError> {
+    let payload = GovernancePayloadUpdateChain {
+        chain,
+        endpoint_address,
+        new_endpoint_address,
+    };
+    let vaa = wormhole.governance_vaa(SerializePayload::try_to_vec(&payload).unwrap());
+    let message = wormhole.post_vaa(&vaa).await?;
+    let ix = instructions::update_chain(
+        wormhole.token_bridge,
+        wormhole.bridge,
+        wormhole.context.payer(),
+        message,
+        vaa,
+        payload,
+        UpdateChainData {},
+    )
+    .unwrap();
+    wormhole.context.execute(&[ix], &[]).await
+}
+
+/// Create a token account of `mint` owned by `owner` with the token program owning the mint.
+async fn create_token_account(
+    wormhole: &mut Wormhole,
+    token_program: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let mut initialize = spl_token::instruction::initialize_account(
+        &spl_token::id(),
+        &account.pubkey(),
+        &mint,
+        &owner,
+    )
+    .unwrap();
+    initialize.program_id = token_program;
+    let ixs = [
+        system_instruction::create_account(
+            &wormhole.context.payer(),
+            &account.pubkey(),
+            Rent::default().minimum_balance(Account::LEN),
+            Account::LEN as u64,
+            &token_program,
+        ),
+        initialize,
+    ];
+    wormhole.context.execute(&ixs, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// Create a Token-2022 mint with the payer as mint authority, optionally with a close authority.
+/// Pending transfers are zero-copy accounts rather than Borsh serialized.
+async fn pending_transfer(wormhole: &mut Wormhole, key: &Pubkey) -> PendingTransfer {
+    let account = wormhole.context.account(key).await.unwrap();
+    PendingTransfer::from_account_data(&account.data).unwrap()
+}
+
+/// Name, symbol and URI of the token metadata account `key`, without the padding the metadata
+/// program appends.
+async fn metadata(wormhole: &mut Wormhole, key: &Pubkey) -> (String, String, String) {
+    let account = wormhole.context.account(key).await.unwrap();
+    let data = Metadata::from_bytes(&account.data).unwrap().data;
+    let trim = |s: String| s.trim_end_matches('\0').to_string();
+    (trim(data.name), trim(data.symbol), trim(data.uri))
+}
+
+/// Rent paid for the claim of `vaa`, which the submitter of a transfer pays for.
+async fn claim_rent(wormhole: &mut Wormhole, vaa: &PostVAAData) -> u64 {
+    let claim = Claim::<'_, { AccountState::Initialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        &wormhole.token_bridge,
+    );
+    wormhole.context.lamports(&claim).await
+}
+
+async fn create_mint(wormhole: &mut Wormhole, decimals: u8) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = wormhole.context.payer();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            Rent::default().minimum_balance(Mint::LEN),
+            Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &payer,
+            None,
+            decimals,
+        )
+        .unwrap(),
+    ];
+    wormhole.context.execute(&ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+async fn create_token_2022_mint(
+    wormhole: &mut Wormhole,
+    decimals: u8,
+    close_authority: Option<Pubkey>,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = wormhole.context.payer();
+    let mut ixs = vec![];
+
+    // Extensions follow the padding up to the size of a token account and the account type.
+    let size = match close_authority {
+        Some(_) => Account::LEN + 1 + 4 + 32,
+        None => Mint::LEN,
+    };
+    ixs.push(system_instruction::create_account(
+        &payer,
+        &mint.pubkey(),
+        Rent::default().minimum_balance(size),
+        size as u64,
+        &token_2022::id(),
+    ));
+    if let Some(close_authority) = close_authority {
+        // InitializeMintCloseAuthority, which the legacy program does not know.
+        let mut data = vec![25, 1];
+        data.extend_from_slice(&close_authority.to_bytes());
+        ixs.push(Instruction::new_with_bytes(
+            token_2022::id(),
+            &data,
+            vec![AccountMeta::new(mint.pubkey(), false)],
+        ));
+    }
+    let mut initialize = spl_token::instruction::initialize_mint(
+        &spl_token::id(),
+        &mint.pubkey(),
+        &payer,
+        None,
+        decimals,
+    )
+    .unwrap();
+    initialize.program_id = token_2022::id();
+    ixs.push(initialize);
+
+    wormhole.context.execute(&ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
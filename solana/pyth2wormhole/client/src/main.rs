@@ -50,6 +50,8 @@ use cli::{
 use bridge::{
     accounts::{
         Bridge,
+        EmitterFee,
+        EmitterFeeDerivationData,
         FeeCollector,
         Sequence,
         SequenceDerivationData,
@@ -245,6 +247,12 @@ fn handle_attest(
         },
         &config.wh_prog,
     );
+    let emitter_fee_addr = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: emitter_addr,
+        },
+        &config.wh_prog,
+    );
 
     // Arrange Attest accounts
     let acc_metas = vec![
@@ -275,6 +283,8 @@ fn handle_attest(
         AccountMeta::new(seq_addr, false),
         // wh_fee_collector
         AccountMeta::new(FeeCollector::<'_>::key(None, &config.wh_prog), false),
+        // wh_emitter_fee
+        AccountMeta::new_readonly(emitter_fee_addr, false),
         AccountMeta::new_readonly(rent::id(), false),
     ];
 
@@ -22,7 +22,11 @@ use solana_program::{
 };
 
 use bridge::{
-    accounts::BridgeData,
+    accounts::{
+        fee_with_override,
+        BridgeData,
+        EmitterFeeData,
+    },
     types::ConsistencyLevel,
     PostMessageData,
 };
@@ -80,6 +84,9 @@ pub struct Attest<'b> {
     /// Account to collect tx fee
     pub wh_fee_collector: Mut<Info<'b>>,
 
+    /// Fee override for our emitter, empty unless governance has set one
+    pub wh_emitter_fee: Info<'b>,
+
     pub wh_rent: Sysvar<'b, Rent>,
 }
 
@@ -131,11 +138,19 @@ pub fn attest(ctx: &ExecutionContext, accs: &mut Attest, data: AttestData) -> So
 
     let bridge_config = BridgeData::try_from_slice(&accs.wh_bridge.try_borrow_mut_data()?)?.config;
 
+    // An initialized emitter fee account can lower the bridge wide fee
+    let fee_override = if accs.wh_emitter_fee.data_is_empty() {
+        None
+    } else {
+        Some(EmitterFeeData::try_from_slice(&accs.wh_emitter_fee.try_borrow_data()?)?.fee)
+    };
+    let fee = fee_with_override(&bridge_config, fee_override);
+
     // Pay wormhole fee
     let transfer_ix = solana_program::system_instruction::transfer(
         accs.payer.key,
         accs.wh_fee_collector.info().key,
-        bridge_config.fee,
+        fee,
     );
     solana_program::program::invoke(&transfer_ix, ctx.accounts)?;
 
@@ -162,6 +177,7 @@ pub fn attest(ctx: &ExecutionContext, accs: &mut Attest, data: AttestData) -> So
             AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(*accs.wh_emitter_fee.key, false),
         ],
     );
 
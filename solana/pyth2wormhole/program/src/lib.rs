@@ -24,9 +24,9 @@ pub use set_config::{
 };
 
 solitaire! {
-    Attest(AttestData) => attest,
-    Initialize(Pyth2WormholeConfig) => initialize,
-    SetConfig(Pyth2WormholeConfig) => set_config,
+    Attest(AttestData) = 0 => attest,
+    Initialize(Pyth2WormholeConfig) = 1 => initialize,
+    SetConfig(Pyth2WormholeConfig) = 2 => set_config,
 }
 
 
@@ -13,11 +13,13 @@ pub use solana_sdk::{
     instruction::{
         AccountMeta,
         Instruction,
+        InstructionError,
     },
     signature::{
         Keypair,
         Signer as SolSigner,
     },
+    transaction::TransactionError,
 };
 
 use borsh::BorshSerialize;
@@ -31,10 +33,16 @@ pub use solitaire::{
     Signer,
 };
 use solitaire::{
+    framework_error_name,
     AccountState,
+    ErrorCode,
     Info,
+    Loader,
+    MaybeMut,
     Mut,
+    System,
     Sysvar,
+    ZeroCopy,
 };
 
 type StdResult<T, E> = std::result::Result<T, E>;
@@ -54,6 +62,12 @@ pub enum AccEntry {
     /// Accounts that need to sign a Solana call, read-only.
     SignerRO(Keypair),
 
+    /// Accounts that need to sign a Solana call, signed outside of the client such as by a
+    /// wallet. The keypair is not known and will not be returned as a signer.
+    SignerKey(Pubkey),
+    /// Accounts that need to sign a Solana call, signed outside of the client, read-only.
+    SignerKeyRO(Pubkey),
+
     /// Program addresses for unprivileged cross calls
     CPIProgram(Pubkey),
     /// Program addresses for privileged cross calls
@@ -66,6 +80,24 @@ pub enum AccEntry {
     Derived(Pubkey),
     /// Key derived from constants and/or program address, read-only.
     DerivedRO(Pubkey),
+
+    /// Key derived from custom derivation data, see `AccEntry::derived_from`.
+    DerivedFrom(Pubkey),
+    /// Key derived from custom derivation data, read-only.
+    DerivedFromRO(Pubkey),
+}
+
+impl AccEntry {
+    /// Derive the key of an account with custom derivation data, such as a `Data` implementing
+    /// `Seeded<&DerivationData>`.
+    pub fn derived_from<A: Seeded<I>, I>(accs: I, program_id: &Pubkey) -> AccEntry {
+        AccEntry::DerivedFrom(A::key(accs, program_id))
+    }
+
+    /// Read-only variant of `AccEntry::derived_from`.
+    pub fn derived_from_ro<A: Seeded<I>, I>(accs: I, program_id: &Pubkey) -> AccEntry {
+        AccEntry::DerivedFromRO(A::key(accs, program_id))
+    }
 }
 
 /// Types implementing Wrap are those that can be turned into a
@@ -94,8 +126,10 @@ where
         match a {
             Signer(pair) => Ok(vec![AccountMeta::new(pair.pubkey(), true)]),
             SignerRO(pair) => Ok(vec![AccountMeta::new_readonly(pair.pubkey(), true)]),
+            SignerKey(k) => Ok(vec![AccountMeta::new(*k, true)]),
+            SignerKeyRO(k) => Ok(vec![AccountMeta::new_readonly(*k, true)]),
             other => Err(format!(
-                "{} must be passed as Signer or SignerRO",
+                "{} must be passed as Signer, SignerKey or the respective read-only variant",
                 std::any::type_name::<Self>()
             )
             .into()),
@@ -125,31 +159,44 @@ impl<'a, 'b: 'a, T, const Seed: &'static str> Wrap for Derive<T, Seed> {
     }
 }
 
-impl<'a, T, const IsInitialized: AccountState> Wrap for Data<'a, T, IsInitialized>
+impl<'a, T, const IsInitialized: AccountState, const MaxSize: usize> Wrap
+    for Data<'a, T, IsInitialized, MaxSize>
 where
     T: BorshSerialize + Owned + Default,
 {
     fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
-        use AccEntry::*;
-        use AccountState::*;
-        match IsInitialized {
-            Initialized => match a {
-                Unprivileged(k) => Ok(vec![AccountMeta::new(*k, false)]),
-                UnprivilegedRO(k) => Ok(vec![AccountMeta::new_readonly(*k, false)]),
-                Signer(pair) => Ok(vec![AccountMeta::new(pair.pubkey(), true)]),
-                SignerRO(pair) => Ok(vec![AccountMeta::new_readonly(pair.pubkey(), true)]),
-                _other => Err(format!("{} with IsInitialized = {:?} must be passed as Unprivileged, Signer or the respective read-only variant", std::any::type_name::<Self>(), a).into())
-            },
-            Uninitialized => match a {
-                Unprivileged(k) => Ok(vec![AccountMeta::new(*k, false)]),
-                Signer(pair) => Ok(vec![AccountMeta::new(pair.pubkey(), true)]),
-                _other => Err(format!("{} with IsInitialized = {:?} must be passed as Unprivileged or Signer (write access required for initialization)", std::any::type_name::<Self>(), a).into())
-            }
-            MaybeInitialized => match a {
-                Unprivileged(k) => Ok(vec![AccountMeta::new(*k, false)]),
-                Signer(pair) => Ok(vec![AccountMeta::new(pair.pubkey(), true)]),
-                _other => Err(format!("{} with IsInitialized = {:?} must be passed as Unprivileged or Signer (write access required in case of initialization)", std::any::type_name::<Self>(), a).into())
-            }
+        wrap_data(std::any::type_name::<Self>(), IsInitialized, a)
+    }
+}
+
+/// Zero-copy accounts are passed like `Data`.
+impl<'a, T, const IsInitialized: AccountState> Wrap for Loader<'a, T, IsInitialized>
+where
+    T: ZeroCopy,
+{
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        wrap_data(std::any::type_name::<Self>(), IsInitialized, a)
+    }
+}
+
+fn wrap_data(name: &str, state: AccountState, a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+    use AccEntry::*;
+    use AccountState::*;
+    match state {
+        Initialized | MaybeInitialized => match a {
+            Unprivileged(k) | DerivedFrom(k) => Ok(vec![AccountMeta::new(*k, false)]),
+            UnprivilegedRO(k) | DerivedFromRO(k) => Ok(vec![AccountMeta::new_readonly(*k, false)]),
+            Signer(pair) => Ok(vec![AccountMeta::new(pair.pubkey(), true)]),
+            SignerRO(pair) => Ok(vec![AccountMeta::new_readonly(pair.pubkey(), true)]),
+            SignerKey(k) => Ok(vec![AccountMeta::new(*k, true)]),
+            SignerKeyRO(k) => Ok(vec![AccountMeta::new_readonly(*k, true)]),
+            _other => Err(format!("{} with IsInitialized = {:?} must be passed as Unprivileged, Signer, SignerKey, DerivedFrom or the respective read-only variant", name, a).into())
+        },
+        Uninitialized => match a {
+            Unprivileged(k) | DerivedFrom(k) => Ok(vec![AccountMeta::new(*k, false)]),
+            Signer(pair) => Ok(vec![AccountMeta::new(pair.pubkey(), true)]),
+            SignerKey(k) => Ok(vec![AccountMeta::new(*k, true)]),
+            _other => Err(format!("{} with IsInitialized = {:?} must be passed as Unprivileged, Signer, SignerKey or DerivedFrom (write access required for initialization)", name, a).into())
         }
     }
 }
@@ -193,11 +240,13 @@ impl<'b> Wrap for Info<'b> {
 impl<T: Wrap> Wrap for Mut<T> {
     fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
         match a {
-            AccEntry::Unprivileged(_) | AccEntry::Signer(_) | AccEntry::Derived(_) => {
-                Ok(T::wrap(a)?)
-            }
+            AccEntry::Unprivileged(_)
+            | AccEntry::Signer(_)
+            | AccEntry::SignerKey(_)
+            | AccEntry::Derived(_)
+            | AccEntry::DerivedFrom(_) => Ok(T::wrap(a)?),
             _other => Err(format!(
-                "{} must be passed as Unprivileged, Signer or Derived (Must be mutable on-chain)",
+                "{} must be passed as Unprivileged, Signer, SignerKey, Derived or DerivedFrom (Must be mutable on-chain)",
                 std::any::type_name::<Self>()
             )
             .into()),
@@ -205,6 +254,19 @@ impl<T: Wrap> Wrap for Mut<T> {
     }
 }
 
+/// Mutability is up to the caller, the entry is passed on as is.
+impl<T: Wrap> Wrap for MaybeMut<T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        T::wrap(a)
+    }
+}
+
+impl<T: Wrap> Wrap for System<T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        T::wrap(a)
+    }
+}
+
 /// Trait used on client side to easily validate a program accounts + ix_data for a bare Solana call
 pub trait ToInstruction {
     fn to_ix(
@@ -213,3 +275,52 @@ pub trait ToInstruction {
         ix_data: &[u8],
     ) -> StdResult<(Instruction, Vec<Keypair>), ErrBox>;
 }
+
+/// Error recovered from the custom code of a failed instruction.
+#[derive(Debug, PartialEq)]
+pub enum DecodedError<E> {
+    /// An error raised by solitaire itself, such as a failed account check.
+    Framework(&'static str),
+
+    /// An error defined by the program, see `solitaire::program_errors!`.
+    Program(E),
+
+    /// A code outside of the framework range that `E` does not define, for example one raised by
+    /// a different program in the CPI chain.
+    Unknown(u32),
+}
+
+impl<E: ErrorCode> std::fmt::Display for DecodedError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodedError::Framework(name) => write!(f, "{}", name),
+            DecodedError::Program(e) => write!(f, "{} ({:#x})", e.name(), e.code()),
+            DecodedError::Unknown(code) => write!(f, "unknown error {:#x}", code),
+        }
+    }
+}
+
+/// Decode a code surfaced through `ProgramError::Custom`.
+pub fn decode_error_code<E: ErrorCode>(code: u32) -> DecodedError<E> {
+    if let Some(name) = framework_error_name(code) {
+        return DecodedError::Framework(name);
+    }
+
+    match E::from_code(code) {
+        Some(e) => DecodedError::Program(e),
+        None => DecodedError::Unknown(code),
+    }
+}
+
+/// Decode the error of a failed transaction, returns the index of the failing instruction along
+/// with the error. Errors that are not custom program errors yield `None`.
+pub fn decode_transaction_error<E: ErrorCode>(
+    error: &TransactionError,
+) -> Option<(u8, DecodedError<E>)> {
+    match error {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            Some((*index, decode_error_code(*code)))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,152 @@
+//! In-process test harness for solitaire programs.
+//!
+//! Programs are loaded into a `solana-program-test` bank, which runs their processors natively,
+//! so tests exercise the real instruction handlers without starting a validator. The harness
+//! only knows about solitaire itself: state is decoded with the same borsh layout `Data` peels,
+//! and failed transactions are decoded into framework or program errors.
+//!
+//! ```ignore
+//! let mut test = ProgramTest::default();
+//! test.add_program("bridge", program_id, processor!(bridge::solitaire));
+//! let mut context = TestContext::start(test).await;
+//!
+//! context.execute(&[ix], &[]).await.unwrap();
+//! let data: BridgeData = context.data(&bridge_key).await;
+//! ```
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    borsh::try_from_slice_unchecked,
+    clock::Clock,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    account::Account,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    transaction::{
+        Transaction,
+        TransactionError,
+    },
+};
+use solitaire::ErrorCode;
+use solitaire_client::{
+    decode_transaction_error,
+    DecodedError,
+};
+use std::fmt::Debug;
+
+pub use solana_program_test::{
+    processor,
+    BanksClient,
+    ProgramTest,
+};
+
+/// A running bank with a funded payer.
+pub struct TestContext {
+    pub context: ProgramTestContext,
+}
+
+impl TestContext {
+    /// Start the bank with the programs registered on `test`.
+    pub async fn start(test: ProgramTest) -> Self {
+        TestContext {
+            context: test.start_with_context().await,
+        }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub fn banks_client(&mut self) -> &mut BanksClient {
+        &mut self.context.banks_client
+    }
+
+    /// Execute instructions in a single transaction paid for by the payer, `signers` are the
+    /// signers required besides the payer.
+    ///
+    /// Every transaction is sent with a fresh blockhash, so submitting the same instructions twice
+    /// executes them twice rather than failing as a duplicate.
+    pub async fn execute(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        let (blockhash, _) = self
+            .context
+            .banks_client
+            .get_new_blockhash(&self.context.last_blockhash)
+            .await
+            .unwrap();
+        self.context.last_blockhash = blockhash;
+
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&self.payer()));
+        transaction.sign(&all_signers, blockhash);
+
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.unwrap())
+    }
+
+    pub async fn account(&mut self, key: &Pubkey) -> Option<Account> {
+        self.context.banks_client.get_account(*key).await.unwrap()
+    }
+
+    /// Lamports held by an account, zero if it does not exist.
+    pub async fn lamports(&mut self, key: &Pubkey) -> u64 {
+        self.account(key).await.map(|a| a.lamports).unwrap_or(0)
+    }
+
+    /// Decode the data of an account, panics if the account does not exist or does not hold a
+    /// `T`. Trailing bytes, such as the space reserved by a `MaxSize` bound, are ignored.
+    pub async fn data<T: BorshDeserialize>(&mut self, key: &Pubkey) -> T {
+        let account = self
+            .account(key)
+            .await
+            .unwrap_or_else(|| panic!("account {} does not exist", key));
+        try_from_slice_unchecked(&account.data)
+            .unwrap_or_else(|e| panic!("failed to decode account {}: {}", key, e))
+    }
+
+    /// Transfer lamports from the payer.
+    pub async fn airdrop(&mut self, to: &Pubkey, lamports: u64) {
+        let ix = system_instruction::transfer(&self.payer(), to, lamports);
+        self.execute(&[ix], &[]).await.unwrap();
+    }
+
+    /// Advance the bank, moving the clock forward.
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        self.context.warp_to_slot(slot).unwrap();
+    }
+
+    /// Move the clock forward by `seconds` without advancing the bank.
+    pub async fn warp_time(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+}
+
+/// Assert that a transaction failed in instruction `index` with the given error.
+pub fn assert_error<E: ErrorCode + Debug + PartialEq>(
+    result: Result<(), TransactionError>,
+    index: u8,
+    expected: DecodedError<E>,
+) {
+    let error = result.expect_err("transaction succeeded");
+    match decode_transaction_error::<E>(&error) {
+        Some(decoded) => assert_eq!(decoded, (index, expected)),
+        None => panic!("transaction failed without a custom error: {:?}", error),
+    }
+}
@@ -25,6 +25,9 @@ pub enum SolitaireError {
     /// The AccountInfo parser tried to derive the provided key, but it did not match.
     InvalidDerive(Pubkey, Pubkey),
 
+    /// The provided bump seed does not yield a valid program address for the seeds.
+    InvalidBump(u8),
+
     /// The AccountInfo has an invalid owner.
     InvalidOwner(Pubkey),
 
@@ -46,8 +49,26 @@ pub enum SolitaireError {
     /// Account has already been initialized
     AlreadyInitialized(Pubkey),
 
-    /// An instruction that wasn't recognised was sent.
-    UnknownInstruction(u8),
+    /// An instruction that wasn't recognised was sent, carries its discriminator.
+    UnknownInstruction(Vec<u8>),
+
+    /// The instruction data is shorter than the instruction discriminator.
+    InstructionDataTooShort(usize),
+
+    /// An account can not be closed into itself.
+    InvalidCloseRecipient(Pubkey),
+
+    /// Serialized data does not fit into the account, with the required and available size.
+    AccountDataTooSmall(Pubkey, usize, usize),
+
+    /// A key declared by an account constraint does not match, with the found and expected key.
+    KeyMismatch(Pubkey, Pubkey),
+
+    /// The account data is already borrowed, see `Loader`.
+    AccountBorrowFailed(Pubkey),
+
+    /// The account data does not start with the discriminator of the expected type, see `Loader`.
+    InvalidDiscriminator(Pubkey),
 
     Custom(u64),
 }
@@ -64,11 +85,85 @@ impl From<std::io::Error> for SolitaireError {
     }
 }
 
+/// Error codes of framework errors occupy the range below `FRAMEWORK_ERROR_CODE_END`. Programs
+/// map their own errors into disjoint ranges above it using `program_errors!`, so a code surfaced
+/// through a CPI chain still identifies the program that failed. Code 0 is never assigned.
+pub const FRAMEWORK_ERROR_CODE_END: u32 = 0x1000;
+
+/// Stable error code scheme implemented by program error enums, see `program_errors!`.
+pub trait ErrorCode: Sized + std::fmt::Debug {
+    /// First code of the range reserved for this program.
+    const CODE_BASE: u32;
+
+    /// Code surfaced as `ProgramError::Custom` when the error is returned.
+    fn code(&self) -> u32;
+
+    /// Map a code back to the error it was produced from.
+    fn from_code(code: u32) -> Option<Self>;
+
+    /// Name of the error variant.
+    fn name(&self) -> &'static str;
+}
+
+impl SolitaireError {
+    /// Code surfaced as `ProgramError::Custom`, forwarded program errors keep their own encoding.
+    pub fn code(&self) -> u32 {
+        match self {
+            SolitaireError::InvalidMutability(..) => 1,
+            SolitaireError::InvalidSigner(_) => 2,
+            SolitaireError::InvalidSysvar(_) => 3,
+            SolitaireError::InvalidDerive(..) => 4,
+            SolitaireError::InvalidOwner(_) => 5,
+            SolitaireError::NonWriteableAccount(_) => 6,
+            SolitaireError::InstructionDeserializeFailed(_) => 7,
+            SolitaireError::IoError(_) => 8,
+            SolitaireError::AmbiguousOwner => 9,
+            SolitaireError::AlreadyInitialized(_) => 10,
+            SolitaireError::UnknownInstruction(_) => 11,
+            SolitaireError::InvalidBump(_) => 12,
+            SolitaireError::InvalidCloseRecipient(_) => 13,
+            SolitaireError::AccountDataTooSmall(..) => 14,
+            SolitaireError::InstructionDataTooShort(_) => 15,
+            SolitaireError::KeyMismatch(..) => 16,
+            SolitaireError::AccountBorrowFailed(_) => 17,
+            SolitaireError::InvalidDiscriminator(_) => 18,
+            SolitaireError::ProgramError(ProgramError::Custom(code)) => *code,
+            SolitaireError::ProgramError(_) => 0,
+            SolitaireError::Custom(code) => *code as u32,
+        }
+    }
+}
+
+/// Name of the framework error with the given code.
+pub fn framework_error_name(code: u32) -> Option<&'static str> {
+    match code {
+        1 => Some("InvalidMutability"),
+        2 => Some("InvalidSigner"),
+        3 => Some("InvalidSysvar"),
+        4 => Some("InvalidDerive"),
+        5 => Some("InvalidOwner"),
+        6 => Some("NonWriteableAccount"),
+        7 => Some("InstructionDeserializeFailed"),
+        8 => Some("IoError"),
+        9 => Some("AmbiguousOwner"),
+        10 => Some("AlreadyInitialized"),
+        11 => Some("UnknownInstruction"),
+        12 => Some("InvalidBump"),
+        13 => Some("InvalidCloseRecipient"),
+        14 => Some("AccountDataTooSmall"),
+        15 => Some("InstructionDataTooShort"),
+        16 => Some("KeyMismatch"),
+        17 => Some("AccountBorrowFailed"),
+        18 => Some("InvalidDiscriminator"),
+        _ => None,
+    }
+}
+
 impl Into<ProgramError> for SolitaireError {
     fn into(self) -> ProgramError {
         match self {
-            SolitaireError::ProgramError(e) => return e,
-            _ => ProgramError::Custom(0),
+            SolitaireError::ProgramError(e) => e,
+            other => ProgramError::Custom(other.code()),
         }
     }
 }
@@ -0,0 +1,270 @@
+//! Machine readable description of a solitaire program.
+//!
+//! Programs built with the `idl` feature expose `instruction::idl()`, generated by `solitaire!`
+//! from the instruction table. Account requirements are collected by peeling the type layers of
+//! each instruction's `FromAccounts` struct through `Peel::describe`, argument layouts come from
+//! the `BorshSchema` of the instruction data. Seeds come from `Seeded::describe_seeds` for fields
+//! declaring a `derivation` constraint, which also tells which accounts the seeds are read from.
+
+use crate::{
+    ExecutionContext,
+    Peel,
+    Result,
+};
+use borsh::schema::{
+    BorshSchemaContainer,
+    Definition,
+    Fields,
+};
+use solana_program::pubkey::Pubkey;
+use std::fmt::Write;
+
+/// Writability an instruction requires of an account.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IdlWritable {
+    No,
+    Yes,
+    /// The program accepts the account either way, see `MaybeMut`.
+    Maybe,
+}
+
+impl Default for IdlWritable {
+    fn default() -> Self {
+        IdlWritable::No
+    }
+}
+
+/// A seed used to derive a program address. Integers are encoded big endian.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdlSeed {
+    /// A constant byte string.
+    Const(&'static str),
+    /// A constant public key, such as the id of another program.
+    Address(Pubkey),
+    /// A field of the derivation data, supplied by the client unless the accounts struct says
+    /// where it comes from, see `IdlSeed::resolve`.
+    Arg(&'static str),
+    /// The key of another account of the instruction.
+    Account(String),
+    /// A field of the data stored in another account of the instruction, such as the payload of a
+    /// posted VAA.
+    Field {
+        account: String,
+        field: &'static str,
+    },
+}
+
+impl IdlSeed {
+    /// Replace a derivation data field by its source, `sources` maps field names to the seeds the
+    /// `derivation` constraint of an accounts struct initialises them from.
+    pub fn resolve(self, sources: &[(&str, IdlSeed)]) -> IdlSeed {
+        match self {
+            IdlSeed::Arg(name) => sources
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, source)| source.clone())
+                .unwrap_or(IdlSeed::Arg(name)),
+            seed => seed,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct IdlAccount {
+    /// Field name, nested account structs are joined with dots.
+    pub name: String,
+    pub writable: IdlWritable,
+    pub signer: bool,
+    /// Seeds of the program address, if the account is derived.
+    pub seeds: Option<Vec<IdlSeed>>,
+    /// Fixed address, such as the id of a sysvar.
+    pub address: Option<Pubkey>,
+    /// Expected initialization state of a data account.
+    pub state: Option<&'static str>,
+    /// Type stored in a data account.
+    pub data: Option<&'static str>,
+}
+
+impl IdlAccount {
+    /// Description of a field nested within this account, used when flattening account structs.
+    pub fn field(&self, name: &str) -> IdlAccount {
+        IdlAccount {
+            name: match self.name.is_empty() {
+                true => name.to_string(),
+                false => format!("{}.{}", self.name, name),
+            },
+            ..self.clone()
+        }
+    }
+}
+
+pub struct IdlInstruction {
+    pub name: &'static str,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<IdlAccount>,
+    pub args: BorshSchemaContainer,
+}
+
+pub struct Idl {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub instructions: Vec<IdlInstruction>,
+}
+
+/// Describe the accounts of an instruction handler, the accounts type is taken from its
+/// signature.
+pub fn describe_handler<'a, 'b: 'a, 'c, A: Peel<'a, 'b, 'c>, D>(
+    _handler: fn(&ExecutionContext, &mut A, D) -> Result<()>,
+) -> Vec<IdlAccount> {
+    A::describe(IdlAccount::default())
+}
+
+/// Short name of a type, without its module path.
+pub fn type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let base = name.split('<').next().unwrap_or(name);
+    match base.rfind("::") {
+        Some(i) => &name[i + 2..],
+        None => name,
+    }
+}
+
+impl Idl {
+    pub fn to_json(&self) -> String {
+        let instructions: Vec<String> = self.instructions.iter().map(instruction_json).collect();
+        format!(
+            "{{\"name\":{},\"version\":{},\"instructions\":[{}]}}",
+            string(self.name),
+            string(self.version),
+            instructions.join(",")
+        )
+    }
+}
+
+fn instruction_json(instruction: &IdlInstruction) -> String {
+    let accounts: Vec<String> = instruction.accounts.iter().map(account_json).collect();
+    format!(
+        "{{\"name\":{},\"discriminator\":{:?},\"accounts\":[{}],\"args\":{}}}",
+        string(instruction.name),
+        instruction.discriminator,
+        accounts.join(","),
+        schema_json(&instruction.args)
+    )
+}
+
+fn account_json(account: &IdlAccount) -> String {
+    let writable = match account.writable {
+        IdlWritable::No => "false",
+        IdlWritable::Yes => "true",
+        IdlWritable::Maybe => "\"maybe\"",
+    };
+    let mut json = format!(
+        "{{\"name\":{},\"writable\":{},\"signer\":{}",
+        string(&account.name),
+        writable,
+        account.signer
+    );
+    if let Some(seeds) = &account.seeds {
+        let seeds: Vec<String> = seeds
+            .iter()
+            .map(|seed| match seed {
+                IdlSeed::Const(value) => {
+                    format!("{{\"kind\":\"const\",\"value\":{}}}", string(value))
+                }
+                IdlSeed::Address(address) => format!(
+                    "{{\"kind\":\"address\",\"value\":{}}}",
+                    string(&address.to_string())
+                ),
+                IdlSeed::Arg(name) => format!("{{\"kind\":\"arg\",\"name\":{}}}", string(name)),
+                IdlSeed::Account(name) => {
+                    format!("{{\"kind\":\"account\",\"name\":{}}}", string(name))
+                }
+                IdlSeed::Field { account, field } => format!(
+                    "{{\"kind\":\"field\",\"account\":{},\"field\":{}}}",
+                    string(account),
+                    string(field)
+                ),
+            })
+            .collect();
+        write!(json, ",\"seeds\":[{}]", seeds.join(",")).unwrap();
+    }
+    if let Some(address) = &account.address {
+        write!(json, ",\"address\":{}", string(&address.to_string())).unwrap();
+    }
+    if let Some(state) = account.state {
+        write!(json, ",\"state\":{}", string(state)).unwrap();
+    }
+    if let Some(data) = account.data {
+        write!(json, ",\"data\":{}", string(data)).unwrap();
+    }
+    json.push('}');
+    json
+}
+
+fn schema_json(schema: &BorshSchemaContainer) -> String {
+    // Sort definitions to keep the output stable between builds.
+    let mut definitions: Vec<_> = schema.definitions.iter().collect();
+    definitions.sort_by(|a, b| a.0.cmp(b.0));
+    let definitions: Vec<String> = definitions
+        .into_iter()
+        .map(|(name, definition)| format!("{}:{}", string(name), definition_json(definition)))
+        .collect();
+    format!(
+        "{{\"declaration\":{},\"definitions\":{{{}}}}}",
+        string(&schema.declaration),
+        definitions.join(",")
+    )
+}
+
+fn definition_json(definition: &Definition) -> String {
+    let list = |items: &[String]| items.iter().map(|s| string(s)).collect::<Vec<_>>().join(",");
+    match definition {
+        Definition::Array { length, elements } => format!(
+            "{{\"kind\":\"array\",\"length\":{},\"elements\":{}}}",
+            length,
+            string(elements)
+        ),
+        Definition::Sequence { elements } => {
+            format!("{{\"kind\":\"sequence\",\"elements\":{}}}", string(elements))
+        }
+        Definition::Tuple { elements } => {
+            format!("{{\"kind\":\"tuple\",\"elements\":[{}]}}", list(elements))
+        }
+        Definition::Enum { variants } => {
+            let variants: Vec<String> = variants
+                .iter()
+                .map(|(name, ty)| format!("{{\"name\":{},\"type\":{}}}", string(name), string(ty)))
+                .collect();
+            format!("{{\"kind\":\"enum\",\"variants\":[{}]}}", variants.join(","))
+        }
+        Definition::Struct { fields } => match fields {
+            Fields::NamedFields(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, ty)| format!("{{\"name\":{},\"type\":{}}}", string(name), string(ty)))
+                    .collect();
+                format!("{{\"kind\":\"struct\",\"fields\":[{}]}}", fields.join(","))
+            }
+            Fields::UnnamedFields(elements) => {
+                format!("{{\"kind\":\"struct\",\"elements\":[{}]}}", list(elements))
+            }
+            Fields::Empty => "{\"kind\":\"struct\",\"fields\":[]}".to_string(),
+        },
+    }
+}
+
+/// Quote and escape a JSON string.
+fn string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
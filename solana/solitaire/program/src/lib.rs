@@ -50,11 +50,17 @@ use std::{
 
 pub use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
+pub use bytemuck::{
+    Pod,
+    Zeroable,
+};
 
 // Expose all submodules for consumption.
 pub mod error;
+pub mod idl;
 pub mod macros;
 pub mod processors;
 pub mod types;
@@ -63,17 +69,22 @@ pub mod types;
 // people to be able to use from top-level.
 pub use crate::{
     error::{
+        framework_error_name,
         ErrBox,
+        ErrorCode,
         Result,
         SolitaireError,
+        FRAMEWORK_ERROR_CODE_END,
     },
     macros::*,
     processors::{
+        close::Closable,
         keyed::Keyed,
         peel::Peel,
         persist::Persist,
         seeded::{
             invoke_seeded,
+            invoke_seeded_with_bump,
             AccountOwner,
             AccountSize,
             Creatable,
@@ -29,9 +29,32 @@ macro_rules! trace_impl {
 /// - A set of functions which take as arguments the enum fields.
 /// - A Dispatcher that deserializes bytes into the enum and dispatches the function call.
 /// - A set of client calls scoped to the module `api` that can generate instructions.
+/// - With the program's `idl` feature enabled, `instruction::idl()` describing every instruction.
+///
+/// Instructions are identified by a discriminator prefixing their data, which takes one of three
+/// forms:
+///
+/// ```ignore
+/// // One byte, assigned in row order. Inserting or reordering rows breaks deployed clients.
+/// solitaire! {
+///     Initialize(InitializeData) => initialize,
+/// }
+///
+/// // One byte, assigned explicitly per row. Rows can be reordered freely.
+/// solitaire! {
+///     Initialize(InitializeData) = 0 => initialize,
+/// }
+///
+/// // Eight bytes, the prefix of sha256("<namespace>:<row>"). Instructions of different programs
+/// // or versions can not be confused with each other.
+/// solitaire! {
+///     namespace = "bridge";
+///     Initialize(InitializeData) => initialize,
+/// }
+/// ```
 #[macro_export]
 macro_rules! solitaire {
-    { $($row:ident($kind:ty) => $fn:ident),+ $(,)* } => {
+    (@program [$($row:ident($kind:ty) => $fn:ident),+] { $($instruction:tt)* }) => {
         pub mod instruction {
             use super::*;
             use borsh::{
@@ -75,26 +98,71 @@ macro_rules! solitaire {
                 }
             )*
 
+            $($instruction)*
+
             /// Generated:
-            /// This Instruction contains a 1-1 mapping for each enum variant to function call. The
-            /// function calls can be found below in the `api` module.
-            #[repr(u8)]
-            #[derive(BorshSerialize, BorshDeserialize)]
-            pub enum Instruction {
-                $($row,)*
+            /// This entrypoint is generated from the enum above, it matches the discriminator of
+            /// the incoming bytes and automatically dispatches to the correct method.
+            pub fn dispatch<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8]) -> Result<()> {
+                if d.len() < DISCRIMINATOR_LEN {
+                    return Err(SolitaireError::InstructionDataTooShort(d.len()));
+                }
+
+                let (discriminator, data) = d.split_at(DISCRIMINATOR_LEN);
+                $(
+                    if discriminator == Instruction::$row.discriminator() {
+                        return $row::execute(p, a, data);
+                    }
+                )*
+
+                Err(SolitaireError::UnknownInstruction(discriminator.to_vec()))
             }
 
-            /// This entrypoint is generated from the enum above, it deserializes incoming bytes
-            /// and automatically dispatches to the correct method.
-            pub fn dispatch<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8]) -> Result<()> {
-                match d[0] {
+            /// Generated:
+            /// Describe the program's instructions, their accounts and argument layouts.
+            #[cfg(feature = "idl")]
+            pub fn idl() -> solitaire::idl::Idl {
+                use solitaire::idl::*;
+                Idl {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    instructions: vec![$(
+                        IdlInstruction {
+                            name: stringify!($row),
+                            discriminator: Instruction::$row.discriminator().to_vec(),
+                            accounts: describe_handler($fn),
+                            args: <$kind as borsh::BorshSchema>::schema_container(),
+                        },
+                    )*],
+                }
+            }
+
+            /// Instructions are serialized as their discriminator, instruction data follows it.
+            impl BorshSerialize for Instruction {
+                fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                    writer.write_all(&self.discriminator())
+                }
+            }
+
+            impl BorshDeserialize for Instruction {
+                fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                    if buf.len() < DISCRIMINATOR_LEN {
+                        return Err(std::io::ErrorKind::UnexpectedEof.into());
+                    }
+
+                    let data: &[u8] = *buf;
+                    let (discriminator, rest) = data.split_at(DISCRIMINATOR_LEN);
                     $(
-                        n if n == Instruction::$row as u8 => $row::execute(p, a, &d[1..]),
+                        if discriminator == Instruction::$row.discriminator() {
+                            *buf = rest;
+                            return Ok(Instruction::$row);
+                        }
                     )*
 
-                    other => {
-                        Err(SolitaireError::UnknownInstruction(other))
-                    }
+                    Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "unknown instruction discriminator",
+                    ))
                 }
             }
 
@@ -111,7 +179,133 @@ macro_rules! solitaire {
         pub use instruction::solitaire;
         #[cfg(not(feature = "no-entrypoint"))]
         solana_program::entrypoint!(solitaire);
-    }
+    };
+
+    { namespace = $namespace:literal; $($row:ident($kind:ty) => $fn:ident),+ $(,)* } => {
+        $crate::solitaire!(@program [$($row($kind) => $fn),+] {
+            /// Generated:
+            /// This Instruction contains a 1-1 mapping for each enum variant to function call. The
+            /// function calls can be found below in the `api` module.
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub enum Instruction {
+                $($row,)*
+            }
+
+            /// Length of the discriminator prefixing instruction data.
+            pub const DISCRIMINATOR_LEN: usize = 8;
+
+            impl Instruction {
+                /// First eight bytes of sha256("<namespace>:<row>"), hashed at compile time.
+                pub const fn discriminator(&self) -> [u8; DISCRIMINATOR_LEN] {
+                    match self {
+                        $(Instruction::$row => $crate::namespaced_discriminator!($namespace, $row),)*
+                    }
+                }
+            }
+        });
+    };
+
+    { $($row:ident($kind:ty) = $discriminator:literal => $fn:ident),+ $(,)* } => {
+        $crate::solitaire!(@program [$($row($kind) => $fn),+] {
+            /// Generated:
+            /// This Instruction contains a 1-1 mapping for each enum variant to function call. The
+            /// function calls can be found below in the `api` module.
+            #[repr(u8)]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub enum Instruction {
+                $($row = $discriminator,)*
+            }
+
+            /// Length of the discriminator prefixing instruction data.
+            pub const DISCRIMINATOR_LEN: usize = 1;
+
+            impl Instruction {
+                pub fn discriminator(&self) -> [u8; DISCRIMINATOR_LEN] {
+                    [*self as u8]
+                }
+            }
+        });
+    };
+
+    { $($row:ident($kind:ty) => $fn:ident),+ $(,)* } => {
+        $crate::solitaire!(@program [$($row($kind) => $fn),+] {
+            /// Generated:
+            /// This Instruction contains a 1-1 mapping for each enum variant to function call. The
+            /// function calls can be found below in the `api` module.
+            #[repr(u8)]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub enum Instruction {
+                $($row,)*
+            }
+
+            /// Length of the discriminator prefixing instruction data.
+            pub const DISCRIMINATOR_LEN: usize = 1;
+
+            impl Instruction {
+                pub fn discriminator(&self) -> [u8; DISCRIMINATOR_LEN] {
+                    [*self as u8]
+                }
+            }
+        });
+    };
+}
+
+/// Define a program error enum with stable error codes. Each variant is given an explicit
+/// discriminant which is offset by the program's code base, variants can therefore be added in any
+/// position but a discriminant must never be reused.
+///
+/// ```ignore
+/// program_errors! {
+///     pub enum Error: 0x1000 {
+///         GuardianSetMismatch = 0,
+///         InsufficientFees = 1,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! program_errors {
+    {
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident: $base:expr {
+            $($variant:ident = $code:expr),+ $(,)*
+        }
+    } => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        $vis enum $name {
+            $($variant = $code,)+
+        }
+
+        impl $crate::ErrorCode for $name {
+            const CODE_BASE: u32 = $base;
+
+            fn code(&self) -> u32 {
+                Self::CODE_BASE + *self as u32
+            }
+
+            fn from_code(code: u32) -> Option<Self> {
+                match code.checked_sub(Self::CODE_BASE)? {
+                    $(c if c == $code => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant),)+
+                }
+            }
+        }
+
+        /// Errors thrown by the program bubble up to the solitaire wrapper, which surfaces them
+        /// using the code of the variant.
+        impl From<$name> for $crate::SolitaireError {
+            fn from(e: $name) -> $crate::SolitaireError {
+                $crate::trace!("ProgramError: {:?}", e);
+                $crate::SolitaireError::Custom($crate::ErrorCode::code(&e) as u64)
+            }
+        }
+    };
 }
 
 #[macro_export]
@@ -1,3 +1,4 @@
+pub mod close;
 pub mod keyed;
 pub mod peel;
 pub mod persist;
@@ -0,0 +1,174 @@
+use super::keyed::Keyed;
+use crate::{
+    AccountInfo,
+    ExecutionContext,
+    Result,
+    SolitaireError,
+};
+use solana_program::program_error::ProgramError;
+
+pub trait Closable<'a, 'b: 'a> {
+    /// Close the account, moving all of its lamports to `recipient` and zeroing its data. `Persist`
+    /// skips accounts without lamports so the closed account is not rewritten when the instruction
+    /// completes, and the runtime purges it at the end of the transaction.
+    ///
+    /// Anything can send lamports to the account again before the transaction ends, so accounts
+    /// whose existence is checked to prevent replays must never be closed.
+    fn close(&'a self, ctx: &ExecutionContext, recipient: &AccountInfo<'b>) -> Result<()>;
+}
+
+impl<'a, 'b: 'a, T: Keyed<'a, 'b>> Closable<'a, 'b> for T {
+    fn close(&'a self, ctx: &ExecutionContext, recipient: &AccountInfo<'b>) -> Result<()> {
+        let info = self.info();
+
+        // Only the owner is allowed to debit an account.
+        if info.owner != ctx.program_id {
+            return Err(SolitaireError::InvalidOwner(*info.owner));
+        }
+
+        if !info.is_writable {
+            return Err(SolitaireError::NonWriteableAccount(*info.key));
+        }
+
+        if !recipient.is_writable {
+            return Err(SolitaireError::NonWriteableAccount(*recipient.key));
+        }
+
+        if info.key == recipient.key {
+            return Err(SolitaireError::InvalidCloseRecipient(*recipient.key));
+        }
+
+        let lamports = info.lamports();
+        **recipient.lamports.borrow_mut() = recipient
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **info.lamports.borrow_mut() = 0;
+        info.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AccountOwner,
+        AccountState,
+        Data,
+        Owned,
+        Peel,
+    };
+    use borsh::{
+        BorshDeserialize,
+        BorshSerialize,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    #[derive(Default, BorshSerialize, BorshDeserialize)]
+    struct Record {
+        value: u64,
+    }
+
+    impl Owned for Record {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    type RecordAccount<'r> = Data<'r, Record, { AccountState::Initialized }>;
+
+    struct Account {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl Account {
+        fn new(owner: Pubkey, lamports: u64, data: Vec<u8>) -> Self {
+            Account {
+                key: Pubkey::new_unique(),
+                owner,
+                lamports,
+                data,
+            }
+        }
+
+        fn info(&mut self, writable: bool) -> AccountInfo {
+            AccountInfo::new(
+                &self.key,
+                false,
+                writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    #[test]
+    fn close_moves_lamports_and_zeroes_data() {
+        let program_id = Pubkey::new_unique();
+        let mut account = Account::new(program_id, 100, 7u64.to_le_bytes().to_vec());
+        let mut recipient = Account::new(Pubkey::new_unique(), 5, vec![]);
+        let info = account.info(true);
+        let recipient = recipient.info(true);
+        let ctx = ExecutionContext {
+            program_id: &program_id,
+            accounts: &[],
+        };
+
+        let record = RecordAccount(Box::new(info.clone()), Record { value: 7 });
+        record.close(&ctx, &recipient).unwrap();
+        assert_eq!(info.lamports(), 0);
+        assert_eq!(recipient.lamports(), 105);
+        assert!(info.data.borrow().iter().all(|b| *b == 0));
+
+        // Persisting after the instruction must not write the data back.
+        record.persist(&program_id).unwrap();
+        assert!(info.data.borrow().iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn close_rejects_invalid_accounts() {
+        let program_id = Pubkey::new_unique();
+        let ctx = ExecutionContext {
+            program_id: &program_id,
+            accounts: &[],
+        };
+        let mut recipient = Account::new(Pubkey::new_unique(), 5, vec![]);
+
+        // Accounts of other programs can not be debited.
+        let mut foreign = Account::new(Pubkey::new_unique(), 100, vec![0; 8]);
+        let record = RecordAccount(Box::new(foreign.info(true)), Record::default());
+        assert!(matches!(
+            record.close(&ctx, &recipient.info(true)),
+            Err(SolitaireError::InvalidOwner(_))
+        ));
+
+        let mut account = Account::new(program_id, 100, vec![0; 8]);
+        {
+            let record = RecordAccount(Box::new(account.info(false)), Record::default());
+            assert!(matches!(
+                record.close(&ctx, &recipient.info(true)),
+                Err(SolitaireError::NonWriteableAccount(_))
+            ));
+        }
+
+        let info = account.info(true);
+        let record = RecordAccount(Box::new(info.clone()), Record::default());
+        assert!(matches!(
+            record.close(&ctx, &recipient.info(false)),
+            Err(SolitaireError::NonWriteableAccount(_))
+        ));
+        assert!(matches!(
+            record.close(&ctx, &info),
+            Err(SolitaireError::InvalidCloseRecipient(_))
+        ));
+        assert_eq!(info.lamports(), 100);
+    }
+}
@@ -9,18 +9,29 @@ use crate::{
     Data,
     Derive,
     Info,
+    Loader,
+    MaybeMut,
     Mut,
     Signer,
     System,
     Sysvar,
+    ZeroCopy,
 };
 
 pub trait Keyed<'a, 'b: 'a> {
     fn info(&'a self) -> &Info<'b>;
 }
 
-impl<'a, 'b: 'a, T: Owned + Default, const IsInitialized: AccountState> Keyed<'a, 'b>
-    for Data<'b, T, IsInitialized>
+impl<'a, 'b: 'a, T: Owned + Default, const IsInitialized: AccountState, const MaxSize: usize>
+    Keyed<'a, 'b> for Data<'b, T, IsInitialized, MaxSize>
+{
+    fn info(&'a self) -> &'a Info<'b> {
+        &self.0
+    }
+}
+
+impl<'a, 'b: 'a, T: ZeroCopy, const IsInitialized: AccountState> Keyed<'a, 'b>
+    for Loader<'b, T, IsInitialized>
 {
     fn info(&'a self) -> &'a Info<'b> {
         &self.0
@@ -69,6 +80,15 @@ where
     }
 }
 
+impl<'a, 'b: 'a, T> Keyed<'a, 'b> for MaybeMut<T>
+where
+    T: Keyed<'a, 'b>,
+{
+    fn info(&'a self) -> &'a Info<'b> {
+        self.0.info()
+    }
+}
+
 impl<'a, 'b: 'a> Keyed<'a, 'b> for Info<'b> {
     fn info(&'a self) -> &'a Info<'b> {
         self
@@ -17,9 +17,15 @@ use solana_program::{
 use std::marker::PhantomData;
 
 use crate::{
+    idl::{
+        type_name,
+        IdlAccount,
+        IdlWritable,
+    },
     processors::seeded::{
         AccountOwner,
         Owned,
+        Seeded,
     },
     types::*,
     AccountState::MaybeInitialized,
@@ -39,6 +45,12 @@ pub trait Peel<'a, 'b: 'a, 'c> {
     fn deps() -> Vec<Pubkey>;
 
     fn persist(&self, program_id: &Pubkey) -> Result<()>;
+
+    /// Describe the accounts this layer expects for the IDL, `account` carries what the outer
+    /// layers have already described.
+    fn describe(account: IdlAccount) -> Vec<IdlAccount> {
+        vec![account]
+    }
 }
 
 /// Peel a Derived Key
@@ -49,7 +61,7 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>, const Seed: &'static str> Peel<'a, 'b,
         // Attempt to Derive Seed
         let (derived, bump) = Pubkey::find_program_address(&[Seed.as_ref()], ctx.this);
         match derived == *ctx.info().key {
-            true => T::peel(ctx).map(|v| Derive(v)),
+            true => T::peel(ctx).map(|v| Derive(v, bump)),
             _ => Err(SolitaireError::InvalidDerive(*ctx.info().key, derived).into()),
         }
     }
@@ -61,6 +73,11 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>, const Seed: &'static str> Peel<'a, 'b,
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(mut account: IdlAccount) -> Vec<IdlAccount> {
+        account.seeds = Some(<Self as Seeded<Option<()>>>::describe_seeds());
+        T::describe(account)
+    }
 }
 
 /// Peel a Mutable key.
@@ -82,6 +99,11 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Mut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(mut account: IdlAccount) -> Vec<IdlAccount> {
+        account.writable = IdlWritable::Yes;
+        T::describe(account)
+    }
 }
 
 impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for MaybeMut<T> {
@@ -97,6 +119,11 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for MaybeMut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(mut account: IdlAccount) -> Vec<IdlAccount> {
+        account.writable = IdlWritable::Maybe;
+        T::describe(account)
+    }
 }
 
 /// Peel a Signer.
@@ -115,6 +142,11 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Signer<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(mut account: IdlAccount) -> Vec<IdlAccount> {
+        account.signer = true;
+        T::describe(account)
+    }
 }
 
 /// Expicitly depend upon the System account.
@@ -133,6 +165,10 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for System<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(account: IdlAccount) -> Vec<IdlAccount> {
+        T::describe(account)
+    }
 }
 
 /// Peel a Sysvar
@@ -157,6 +193,11 @@ where
     fn persist(&self, _program_id: &Pubkey) -> Result<()> {
         Ok(())
     }
+
+    fn describe(mut account: IdlAccount) -> Vec<IdlAccount> {
+        account.address = Some(Var::id());
+        vec![account]
+    }
 }
 
 /// This is our structural recursion base case, the trait system will stop generating new nested
@@ -187,7 +228,8 @@ impl<
         'c,
         T: BorshDeserialize + BorshSerialize + Owned + Default,
         const IsInitialized: AccountState,
-    > Peel<'a, 'b, 'c> for Data<'b, T, IsInitialized>
+        const MaxSize: usize,
+    > Peel<'a, 'b, 'c> for Data<'b, T, IsInitialized, MaxSize>
 {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
         if ctx.immutable && ctx.info().is_writable {
@@ -205,31 +247,19 @@ impl<
                 (false, T::default())
             }
             AccountState::Initialized => {
-                (true, T::try_from_slice(&mut *ctx.info().data.borrow_mut())?)
+                (true, Self::deserialize_data(&ctx.info().data.borrow())?)
             }
             AccountState::MaybeInitialized => {
                 if **ctx.info().lamports.borrow() == 0 {
                     (false, T::default())
                 } else {
-                    (true, T::try_from_slice(&mut *ctx.info().data.borrow_mut())?)
+                    (true, Self::deserialize_data(&ctx.info().data.borrow())?)
                 }
             }
         };
 
         if initialized {
-            match data.owner() {
-                AccountOwner::This => {
-                    if ctx.info().owner != ctx.this {
-                        return Err(SolitaireError::InvalidOwner(*ctx.info().owner));
-                    }
-                }
-                AccountOwner::Other(v) => {
-                    if *ctx.info().owner != v {
-                        return Err(SolitaireError::InvalidOwner(*ctx.info().owner));
-                    }
-                }
-                AccountOwner::Any => {}
-            };
+            check_owner(ctx, data.owner())?;
         }
 
         Ok(Data(Box::new(ctx.info().clone()), data))
@@ -243,6 +273,12 @@ impl<
         vec![sysvar::rent::ID, system_program::ID]
     }
 
+    fn describe(mut account: IdlAccount) -> Vec<IdlAccount> {
+        account.state = Some(state_name(IsInitialized));
+        account.data = Some(type_name::<T>());
+        vec![account]
+    }
+
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         // TODO: Introduce Mut<> to solve the check we really want to make here.
         if self.0.owner != program_id {
@@ -254,8 +290,227 @@ impl<
             return Ok(());
         }
 
-        self.1.serialize(&mut *self.0.data.borrow_mut())?;
+        // Closed accounts have been drained and zeroed, writing the data back would revive them.
+        if **self.0.lamports.borrow() == 0 {
+            return Ok(());
+        }
+
+        let data = self.1.try_to_vec()?;
+        let mut buffer = self.0.data.borrow_mut();
+        if data.len() > buffer.len() {
+            return Err(SolitaireError::AccountDataTooSmall(
+                *self.0.key,
+                data.len(),
+                buffer.len(),
+            ));
+        }
+        buffer[..data.len()].copy_from_slice(&data);
+
+        // Clear what is left of a previous, longer encoding in the reserved space.
+        if MaxSize > 0 {
+            buffer[data.len()..].fill(0);
+        }
 
         Ok(())
     }
 }
+
+impl<
+        'b,
+        T: BorshDeserialize + BorshSerialize + Owned + Default,
+        const IsInitialized: AccountState,
+        const MaxSize: usize,
+    > Data<'b, T, IsInitialized, MaxSize>
+{
+    fn deserialize_data(data: &[u8]) -> Result<T> {
+        match MaxSize {
+            // Accounts allocated at their exact size must be consumed entirely.
+            0 => Ok(T::try_from_slice(data)?),
+            // Reserved space leaves unused bytes after the data.
+            _ => Ok(T::deserialize(&mut &data[..])?),
+        }
+    }
+}
+
+/// Zero-copy accounts are only checked here, the data is cast in place when it is loaded and
+/// there is nothing to persist.
+impl<'a, 'b: 'a, 'c, T: ZeroCopy, const IsInitialized: AccountState> Peel<'a, 'b, 'c>
+    for Loader<'b, T, IsInitialized>
+{
+    fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
+        if ctx.immutable && ctx.info().is_writable {
+            return Err(
+                SolitaireError::InvalidMutability(*ctx.info().key, ctx.info().is_writable).into(),
+            );
+        }
+
+        let initialized = match IsInitialized {
+            AccountState::Uninitialized => {
+                if **ctx.info().lamports.borrow() != 0 {
+                    return Err(SolitaireError::AlreadyInitialized(*ctx.info().key));
+                }
+                false
+            }
+            AccountState::Initialized => true,
+            AccountState::MaybeInitialized => **ctx.info().lamports.borrow() != 0,
+        };
+
+        let loader = Loader::new(ctx.info().clone());
+        if initialized {
+            check_owner(ctx, loader.owner())?;
+            Self::check_layout(ctx.info().key, &ctx.info().data.borrow())?;
+        }
+
+        Ok(loader)
+    }
+
+    fn deps() -> Vec<Pubkey> {
+        if IsInitialized == AccountState::Initialized {
+            return vec![];
+        }
+
+        vec![sysvar::rent::ID, system_program::ID]
+    }
+
+    fn describe(mut account: IdlAccount) -> Vec<IdlAccount> {
+        account.state = Some(state_name(IsInitialized));
+        account.data = Some(type_name::<T>());
+        vec![account]
+    }
+
+    fn persist(&self, _program_id: &Pubkey) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn check_owner<I>(ctx: &mut Context<'_, '_, '_, I>, owner: AccountOwner) -> Result<()> {
+    let valid = match owner {
+        AccountOwner::This => ctx.info().owner == ctx.this,
+        AccountOwner::Other(v) => *ctx.info().owner == v,
+        AccountOwner::Any => true,
+    };
+    match valid {
+        true => Ok(()),
+        false => Err(SolitaireError::InvalidOwner(*ctx.info().owner)),
+    }
+}
+
+fn state_name(state: AccountState) -> &'static str {
+    match state {
+        AccountState::Initialized => "initialized",
+        AccountState::Uninitialized => "uninitialized",
+        AccountState::MaybeInitialized => "maybeInitialized",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExecutionContext;
+    use solana_program::account_info::AccountInfo;
+
+    #[derive(Default, BorshSerialize, BorshDeserialize)]
+    struct Record {
+        values: Vec<u8>,
+    }
+
+    impl Owned for Record {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    type ExactRecord<'r> = Data<'r, Record, { AccountState::Initialized }>;
+    type ReservedRecord<'r> = Data<'r, Record, { AccountState::Initialized }, 16>;
+
+    fn record(values: &[u8]) -> Record {
+        Record {
+            values: values.to_vec(),
+        }
+    }
+
+    #[test]
+    fn persist_fails_when_data_outgrows_account() {
+        let key = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let mut lamports = 100;
+        let mut data = record(&[1, 2]).try_to_vec().unwrap();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let account = ExactRecord(Box::new(info.clone()), record(&[1, 2, 3]));
+        assert!(matches!(
+            account.persist(&program_id),
+            Err(SolitaireError::AccountDataTooSmall(k, 7, 6)) if k == key
+        ));
+        assert_eq!(*info.data.borrow(), record(&[1, 2]).try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn persist_zero_fills_reserved_space() {
+        let key = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let mut lamports = 100;
+        let mut data = vec![0xff; 16];
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let mut account = ReservedRecord(Box::new(info.clone()), record(&[1, 2, 3, 4]));
+        account.persist(&program_id).unwrap();
+        account.values = vec![9];
+        account.persist(&program_id).unwrap();
+        assert_eq!(
+            *info.data.borrow(),
+            [1, 0, 0, 0, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        // The zeroed tail is ignored when the account is read back, unless it was allocated at its
+        // exact size.
+        let data = info.data.borrow();
+        assert_eq!(ReservedRecord::deserialize_data(&data).unwrap().values, [9]);
+        assert!(ExactRecord::deserialize_data(&data).is_err());
+    }
+
+    #[test]
+    fn realloc_is_a_noop_when_data_fits() {
+        let key = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let mut lamports = 100;
+        let mut data = vec![0; 16];
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        let ctx = ExecutionContext {
+            program_id: &program_id,
+            accounts: &[],
+        };
+
+        let account = ReservedRecord(Box::new(info.clone()), record(&[1; 12]));
+        account.realloc(&ctx, &Pubkey::new_unique()).unwrap();
+        assert_eq!(info.data_len(), 16);
+        assert_eq!(info.lamports(), 100);
+    }
+}
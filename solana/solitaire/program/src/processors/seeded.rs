@@ -1,5 +1,6 @@
 use super::keyed::Keyed;
 use crate::{
+    idl::IdlSeed,
     system_instruction,
     AccountInfo,
     AccountState,
@@ -10,12 +11,14 @@ use crate::{
     ExecutionContext,
     FromAccounts,
     Info,
+    Loader,
     Peel,
     Result,
     Signer,
     SolitaireError,
     System,
     Sysvar,
+    ZeroCopy,
 };
 use borsh::{
     BorshSchema,
@@ -51,14 +54,20 @@ pub trait Owned {
     }
 }
 
-impl<'a, T: Owned + Default, const IsInitialized: AccountState> Owned
-    for Data<'a, T, IsInitialized>
+impl<'a, T: Owned + Default, const IsInitialized: AccountState, const MaxSize: usize> Owned
+    for Data<'a, T, IsInitialized, MaxSize>
 {
     fn owner(&self) -> AccountOwner {
         self.1.owner()
     }
 }
 
+impl<'a, T: ZeroCopy, const IsInitialized: AccountState> Owned for Loader<'a, T, IsInitialized> {
+    fn owner(&self) -> AccountOwner {
+        T::zeroed().owner()
+    }
+}
+
 pub trait Seeded<I> {
     fn seeds(accs: I) -> Vec<Vec<u8>>;
 
@@ -66,6 +75,8 @@ pub trait Seeded<I> {
         Self::seeds(accs)
     }
 
+    /// Derive the address by searching for the bump. Handlers deriving an account at hand should
+    /// use `verify_derivation` or `self_bumped_seeds`, which skip the search with a stored bump.
     fn key(accs: I, program_id: &Pubkey) -> Pubkey {
         let mut seeds = Self::seeds(accs);
         let mut s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
@@ -75,6 +86,7 @@ pub trait Seeded<I> {
         addr
     }
 
+    /// Seeds extended by the bump, found by searching for it.
     fn bumped_seeds(accs: I, program_id: &Pubkey) -> Vec<Vec<u8>> {
         let mut seeds = Self::seeds(accs);
         let mut s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
@@ -85,25 +97,102 @@ pub trait Seeded<I> {
         seeds
     }
 
+    /// Like `bumped_seeds`, skipping the bump search if the account knows its bump.
     fn self_bumped_seeds(&self, accs: I, program_id: &Pubkey) -> Vec<Vec<u8>> {
-        Self::bumped_seeds(accs, program_id)
+        match self.stored_bump() {
+            Some(bump) => Self::seeds_with_bump(accs, bump),
+            None => Self::bumped_seeds(accs, program_id),
+        }
     }
 
+    /// Seeds in the order `seeds` returns them, for the IDL. Fields of the derivation data are
+    /// described as `IdlSeed::Arg`, named like the field.
+    fn describe_seeds() -> Vec<IdlSeed> {
+        Vec::new()
+    }
+
+    /// Canonical bump seed of the account address if it is known without a search, either because
+    /// the account stores it in its data or because it was found while peeling. Implementations
+    /// must only return bumps of initialized accounts, the data of any other account is default.
+    fn stored_bump(&self) -> Option<u8> {
+        None
+    }
+
+    /// Verify the derivation, using the stored bump if there is one.
     fn verify_derivation<'a, 'b: 'a>(&'a self, program_id: &'a Pubkey, accs: I) -> Result<()>
     where
         Self: Keyed<'a, 'b>,
     {
+        match self.stored_bump() {
+            Some(bump) => self.verify_derivation_with_bump(program_id, accs, bump),
+            None => self.derivation_bump(program_id, accs).map(|_| ()),
+        }
+    }
+
+    /// Derive the address together with its canonical bump seed.
+    fn key_and_bump(accs: I, program_id: &Pubkey) -> (Pubkey, u8) {
         let seeds = Self::seeds(accs);
         let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
-        let seed_slice = s.as_slice();
+        Pubkey::find_program_address(s.as_slice(), program_id)
+    }
+
+    /// Seeds extended by an already known bump, skipping the bump search.
+    fn seeds_with_bump(accs: I, bump: u8) -> Vec<Vec<u8>> {
+        let mut seeds = Self::seeds(accs);
+        seeds.push(vec![bump]);
+        seeds
+    }
+
+    /// Derive the address from an already known bump, this costs a single hash compared to the
+    /// search `key` performs.
+    fn key_with_bump(accs: I, bump: u8, program_id: &Pubkey) -> Result<Pubkey> {
+        let seeds = Self::seeds_with_bump(accs, bump);
+        let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+        Pubkey::create_program_address(s.as_slice(), program_id)
+            .map_err(|_| SolitaireError::InvalidBump(bump))
+    }
 
-        let (derived, bump) = Pubkey::find_program_address(seed_slice, program_id);
+    /// Verify the derivation and return the canonical bump seed, used when the bump should be
+    /// stored in the account for later verifications.
+    fn derivation_bump<'a, 'b: 'a>(&'a self, program_id: &'a Pubkey, accs: I) -> Result<u8>
+    where
+        Self: Keyed<'a, 'b>,
+    {
+        let (derived, bump) = Self::key_and_bump(accs, program_id);
         if &derived == self.info().key {
-            Ok(())
+            Ok(bump)
         } else {
             Err(SolitaireError::InvalidDerive(*self.info().key, derived))
         }
     }
+
+    /// Verify the derivation using a stored or caller supplied bump.
+    ///
+    /// Any bump that yields a valid address is accepted by `create_program_address`, only the
+    /// canonical one is unique though. The fast path is therefore only taken for accounts that are
+    /// already initialized and owned by this program, which can only have been created using the
+    /// canonical bump. Every other account falls back to the full search.
+    fn verify_derivation_with_bump<'a, 'b: 'a>(
+        &'a self,
+        program_id: &'a Pubkey,
+        accs: I,
+        bump: u8,
+    ) -> Result<()>
+    where
+        Self: Keyed<'a, 'b>,
+    {
+        let info = self.info();
+        if info.owner != program_id || info.data_is_empty() {
+            return self.derivation_bump(program_id, accs).map(|_| ());
+        }
+
+        let derived = Self::key_with_bump(accs, bump, program_id)?;
+        if &derived == info.key {
+            Ok(())
+        } else {
+            Err(SolitaireError::InvalidDerive(*info.key, derived))
+        }
+    }
 }
 
 pub trait Creatable<'a, I> {
@@ -114,13 +203,30 @@ pub trait Creatable<'a, I> {
         payer: &'a Pubkey,
         lamports: CreationLamports,
     ) -> Result<()>;
+
+    /// Create the account using a bump obtained from a previous derivation, which saves repeating
+    /// the bump search. The bump must be canonical, see `Seeded::derivation_bump`.
+    fn create_with_bump(
+        &'a self,
+        accs: I,
+        ctx: &'a ExecutionContext,
+        payer: &'a Pubkey,
+        lamports: CreationLamports,
+        bump: u8,
+    ) -> Result<()>;
 }
 
-impl<T: BorshSerialize + Owned + Default, const IsInitialized: AccountState> AccountSize
-    for Data<'_, T, IsInitialized>
+impl<T: BorshSerialize + Owned + Default, const IsInitialized: AccountState, const MaxSize: usize>
+    AccountSize for Data<'_, T, IsInitialized, MaxSize>
 {
     fn size(&self) -> usize {
-        self.1.try_to_vec().unwrap().len()
+        self.1.try_to_vec().unwrap().len().max(MaxSize)
+    }
+}
+
+impl<T: ZeroCopy, const IsInitialized: AccountState> AccountSize for Loader<'_, T, IsInitialized> {
+    fn size(&self) -> usize {
+        Self::SIZE
     }
 }
 
@@ -132,28 +238,59 @@ impl<'a, 'b: 'a, K, T: AccountSize + Seeded<K> + Keyed<'a, 'b> + Owned> Creatabl
         payer: &'a Pubkey,
         lamports: CreationLamports,
     ) -> Result<()> {
-        let seeds = T::bumped_seeds(accs, ctx.program_id);
-        let size = self.size();
+        let seeds = self.self_bumped_seeds(accs, ctx.program_id);
+        create_seeded(self, seeds, ctx, payer, lamports)
+    }
 
-        let mut s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
-        let mut seed_slice = s.as_slice();
+    fn create_with_bump(
+        &'a self,
+        accs: K,
+        ctx: &'a ExecutionContext<'_, '_>,
+        payer: &'a Pubkey,
+        lamports: CreationLamports,
+        bump: u8,
+    ) -> Result<()> {
+        let seeds = T::seeds_with_bump(accs, bump);
+        create_seeded(self, seeds, ctx, payer, lamports)
+    }
+}
 
-        let ix = system_instruction::create_account(
-            payer,
-            self.info().key,
-            lamports.amount(size),
-            size as u64,
-            &self.owner_pubkey(ctx.program_id)?,
-        );
+fn create_seeded<'a, 'b: 'a, T: AccountSize + Keyed<'a, 'b> + Owned>(
+    account: &'a T,
+    seeds: Vec<Vec<u8>>,
+    ctx: &'a ExecutionContext<'_, '_>,
+    payer: &'a Pubkey,
+    lamports: CreationLamports,
+) -> Result<()> {
+    let size = account.size();
 
-        Ok(invoke_signed(&ix, ctx.accounts, &[seed_slice])?)
-    }
+    let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+    let seed_slice = s.as_slice();
+
+    let ix = system_instruction::create_account(
+        payer,
+        account.info().key,
+        lamports.amount(size),
+        size as u64,
+        &account.owner_pubkey(ctx.program_id)?,
+    );
+
+    Ok(invoke_signed(&ix, ctx.accounts, &[seed_slice])?)
 }
 
 impl<'a, const Seed: &'static str, T> Seeded<Option<()>> for Derive<T, Seed> {
     fn seeds(accs: Option<()>) -> Vec<Vec<u8>> {
         vec![Seed.as_bytes().to_vec()]
     }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::Const(Seed)]
+    }
+
+    /// The bump found when the account was peeled, so that signing with it does not search again.
+    fn stored_bump(&self) -> Option<u8> {
+        Some(self.1)
+    }
 }
 
 pub fn invoke_seeded<I, T: Seeded<I>>(
@@ -167,3 +304,16 @@ pub fn invoke_seeded<I, T: Seeded<I>>(
     let seed_slice = s.as_slice();
     invoke_signed(instruction, context.accounts, &[seed_slice])
 }
+
+/// Like `invoke_seeded` but signs with an already known bump instead of searching for it.
+pub fn invoke_seeded_with_bump<I, T: Seeded<I>>(
+    instruction: &Instruction,
+    context: &ExecutionContext,
+    accs: I,
+    bump: u8,
+) -> ProgramResult {
+    let seeds = T::seeds_with_bump(accs, bump);
+    let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+    let seed_slice = s.as_slice();
+    invoke_signed(instruction, context.accounts, &[seed_slice])
+}
@@ -5,16 +5,32 @@
 //! types that describe different kinds of accounts to target.
 
 use borsh::BorshSerialize;
+use bytemuck::Pod;
 use solana_program::{
     account_info::AccountInfo,
-    program::invoke_signed,
+    program::{
+        invoke,
+        invoke_signed,
+    },
+    program_error::ProgramError,
     pubkey::Pubkey,
     system_instruction,
     sysvar::Sysvar as SolanaSysvar,
 };
-use std::ops::{
-    Deref,
-    DerefMut,
+use std::{
+    cell::{
+        Ref,
+        RefMut,
+    },
+    marker::PhantomData,
+    mem::{
+        align_of,
+        size_of,
+    },
+    ops::{
+        Deref,
+        DerefMut,
+    },
 };
 
 use crate::{
@@ -23,6 +39,7 @@ use crate::{
     Derive,
     ExecutionContext,
     Result,
+    SolitaireError,
 };
 
 /// A short alias for AccountInfo.
@@ -44,14 +61,19 @@ pub enum AccountState {
 /// use this type by writing for example:
 ///
 /// Data<(), { AccountState::Uninitialized }>
+///
+/// `MaxSize` reserves space for data that grows after creation, such as Vec based state. Accounts
+/// are allocated at the larger of `MaxSize` and the serialized size of the data at creation, and
+/// persisting data that outgrows the account fails with `AccountDataTooSmall` unless the handler
+/// grows it with `realloc` first.
 #[rustfmt::skip]
-pub struct Data<'r, T: Owned + Default, const IsInitialized: AccountState> (
+pub struct Data<'r, T: Owned + Default, const IsInitialized: AccountState, const MaxSize: usize = 0> (
     pub Box<Info<'r>>,
     pub T,
 );
 
-impl<'r, T: Owned + Default, const IsInitialized: AccountState> Deref
-    for Data<'r, T, IsInitialized>
+impl<'r, T: Owned + Default, const IsInitialized: AccountState, const MaxSize: usize> Deref
+    for Data<'r, T, IsInitialized, MaxSize>
 {
     type Target = T;
     fn deref(&self) -> &Self::Target {
@@ -59,21 +81,163 @@ impl<'r, T: Owned + Default, const IsInitialized: AccountState> Deref
     }
 }
 
-impl<'r, T: Owned + Default, const IsInitialized: AccountState> DerefMut
-    for Data<'r, T, IsInitialized>
+impl<'r, T: Owned + Default, const IsInitialized: AccountState, const MaxSize: usize> DerefMut
+    for Data<'r, T, IsInitialized, MaxSize>
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.1
     }
 }
 
-impl<'r, T: Owned + Default, const IsInitialized: AccountState> Data<'r, T, IsInitialized> {
+impl<'r, T: Owned + Default, const IsInitialized: AccountState, const MaxSize: usize>
+    Data<'r, T, IsInitialized, MaxSize>
+{
     /// Is the account already initialized / created
     pub fn is_initialized(&self) -> bool {
         **self.0.lamports.borrow() != 0
     }
 }
 
+impl<
+        'r,
+        T: BorshSerialize + Owned + Default,
+        const IsInitialized: AccountState,
+        const MaxSize: usize,
+    > Data<'r, T, IsInitialized, MaxSize>
+{
+    /// Grow the account so that the current data fits when it is persisted, `payer` tops up the
+    /// rent for the extra space. This is a no-op if the data already fits. The runtime limits how
+    /// much an account can grow within one instruction to `MAX_PERMITTED_DATA_INCREASE`, state that
+    /// can outgrow that should reserve space with `MaxSize` instead.
+    pub fn realloc(&self, ctx: &ExecutionContext, payer: &Pubkey) -> Result<()> {
+        let size = self.1.try_to_vec()?.len().max(MaxSize);
+        if size <= self.0.data_len() {
+            return Ok(());
+        }
+
+        let missing = CreationLamports::Exempt
+            .amount(size)
+            .saturating_sub(self.0.lamports());
+        if missing > 0 {
+            let ix = system_instruction::transfer(payer, self.0.key, missing);
+            invoke(&ix, ctx.accounts)?;
+        }
+
+        self.0.realloc(size, true)?;
+        Ok(())
+    }
+}
+
+/// Types stored in `Loader` accounts.
+pub trait ZeroCopy: Pod + Owned {
+    /// Written in front of the data when the account is initialized and checked whenever it is
+    /// loaded, so that an account of another type with the same owner and a compatible size is
+    /// not reinterpreted as this one.
+    const DISCRIMINATOR: [u8; 8];
+}
+
+/// An account holding a `Pod` struct that is accessed in place.
+///
+/// Unlike `Data`, peeling does not deserialize the account and persisting does not write it back,
+/// the data is only touched through `load` and `load_mut` which borrow the account data and cast
+/// it to `T`. This keeps the cost independent of the account size, so large state should prefer
+/// it. Borrows are checked at runtime, loading an account mutably while it is already loaded
+/// fails with `AccountBorrowFailed` rather than aliasing.
+///
+/// Accounts are allocated with room for `T::DISCRIMINATOR` followed by `T`, and are set up with
+/// `init` after they are created.
+///
+/// Loader<'b, PendingTransfer, { AccountState::Initialized }>
+pub struct Loader<'r, T: ZeroCopy, const IsInitialized: AccountState>(
+    pub Box<Info<'r>>,
+    pub PhantomData<T>,
+);
+
+/// Offset of `T` in the data of a `Loader` account, the discriminator comes first. Account data
+/// is 8 byte aligned, which the offset preserves.
+pub const LOADER_DATA_OFFSET: usize = 8;
+
+impl<'r, T: ZeroCopy, const IsInitialized: AccountState> Loader<'r, T, IsInitialized> {
+    /// Size of the account data.
+    pub const SIZE: usize = LOADER_DATA_OFFSET + size_of::<T>();
+
+    pub fn new(info: Info<'r>) -> Self {
+        Loader(Box::new(info), PhantomData)
+    }
+
+    /// Is the account already initialized / created
+    pub fn is_initialized(&self) -> bool {
+        **self.0.lamports.borrow() != 0
+    }
+
+    /// Write the discriminator into a freshly created account and borrow its zeroed data as a
+    /// mutable `T`.
+    pub fn init(&self) -> Result<RefMut<'_, T>> {
+        let mut data = self.borrow_mut()?;
+        Self::check_size(self.0.key, &data)?;
+        if data[..LOADER_DATA_OFFSET].iter().any(|b| *b != 0) {
+            return Err(SolitaireError::AlreadyInitialized(*self.0.key));
+        }
+        data[..LOADER_DATA_OFFSET].copy_from_slice(&T::DISCRIMINATOR);
+        Ok(RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut(&mut data[LOADER_DATA_OFFSET..Self::SIZE])
+        }))
+    }
+
+    /// Borrow the account data as `T`.
+    pub fn load(&self) -> Result<Ref<'_, T>> {
+        let data = self
+            .0
+            .try_borrow_data()
+            .map_err(|_| SolitaireError::AccountBorrowFailed(*self.0.key))?;
+        Self::check_layout(self.0.key, &data)?;
+        Ok(Ref::map(data, |data| {
+            bytemuck::from_bytes(&data[LOADER_DATA_OFFSET..Self::SIZE])
+        }))
+    }
+
+    /// Borrow the account data as a mutable `T`, changes are written to the account directly.
+    pub fn load_mut(&self) -> Result<RefMut<'_, T>> {
+        let data = self.borrow_mut()?;
+        Self::check_layout(self.0.key, &data)?;
+        Ok(RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut(&mut data[LOADER_DATA_OFFSET..Self::SIZE])
+        }))
+    }
+
+    fn borrow_mut(&self) -> Result<RefMut<'_, &'r mut [u8]>> {
+        if !self.0.is_writable {
+            return Err(SolitaireError::NonWriteableAccount(*self.0.key));
+        }
+        self.0
+            .try_borrow_mut_data()
+            .map_err(|_| SolitaireError::AccountBorrowFailed(*self.0.key))
+    }
+
+    /// Check that the account data holds a `T` that can be cast in place.
+    pub(crate) fn check_layout(key: &Pubkey, data: &[u8]) -> Result<()> {
+        Self::check_size(key, data)?;
+        if data[..LOADER_DATA_OFFSET] != T::DISCRIMINATOR {
+            return Err(SolitaireError::InvalidDiscriminator(*key));
+        }
+        Ok(())
+    }
+
+    fn check_size(key: &Pubkey, data: &[u8]) -> Result<()> {
+        if data.len() < Self::SIZE {
+            return Err(SolitaireError::AccountDataTooSmall(
+                *key,
+                Self::SIZE,
+                data.len(),
+            ));
+        }
+        if data[LOADER_DATA_OFFSET..].as_ptr() as usize % align_of::<T>() != 0 {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+        Ok(())
+    }
+}
+
 pub struct Sysvar<'b, Var: SolanaSysvar>(pub AccountInfo<'b>, pub Var);
 
 impl<'b, Var: SolanaSysvar> Deref for Sysvar<'b, Var> {
@@ -99,13 +263,12 @@ impl<const Seed: &'static str> Derive<AccountInfo<'_>, Seed> {
             space as u64,
             owner,
         );
-        let (_, bump_seed) = Pubkey::find_program_address(&[Seed.as_bytes()][..], ctx.program_id);
-        invoke_signed(&ix, ctx.accounts, &[&[Seed.as_bytes(), &[bump_seed]]]).map_err(|e| e.into())
+        invoke_signed(&ix, ctx.accounts, &[&[Seed.as_bytes(), &[self.1]]]).map_err(|e| e.into())
     }
 }
 
-impl<const Seed: &'static str, T: BorshSerialize + Owned + Default>
-    Derive<Data<'_, T, { AccountState::Uninitialized }>, Seed>
+impl<const Seed: &'static str, T: BorshSerialize + Owned + Default, const MaxSize: usize>
+    Derive<Data<'_, T, { AccountState::Uninitialized }, MaxSize>, Seed>
 {
     pub fn create(
         &self,
@@ -114,7 +277,7 @@ impl<const Seed: &'static str, T: BorshSerialize + Owned + Default>
         lamports: CreationLamports,
     ) -> Result<()> {
         // Get serialized struct size
-        let size = self.0.try_to_vec().unwrap().len();
+        let size = self.0.try_to_vec().unwrap().len().max(MaxSize);
         let ix = system_instruction::create_account(
             payer,
             self.0 .0.key,
@@ -122,7 +285,163 @@ impl<const Seed: &'static str, T: BorshSerialize + Owned + Default>
             size as u64,
             ctx.program_id,
         );
-        let (_, bump_seed) = Pubkey::find_program_address(&[Seed.as_bytes()][..], ctx.program_id);
-        invoke_signed(&ix, ctx.accounts, &[&[Seed.as_bytes(), &[bump_seed]]]).map_err(|e| e.into())
+        invoke_signed(&ix, ctx.accounts, &[&[Seed.as_bytes(), &[self.1]]]).map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccountOwner;
+    use bytemuck::Zeroable;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Counter {
+        count: u64,
+        last: [u8; 32],
+    }
+
+    impl Owned for Counter {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    impl ZeroCopy for Counter {
+        const DISCRIMINATOR: [u8; 8] = *b"counter\0";
+    }
+
+    /// Same size and owner as `Counter`.
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Other {
+        data: [u8; 40],
+    }
+
+    impl Owned for Other {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    impl ZeroCopy for Other {
+        const DISCRIMINATOR: [u8; 8] = *b"other\0\0\0";
+    }
+
+    type CounterLoader<'r> = Loader<'r, Counter, { AccountState::Initialized }>;
+
+    /// Run `f` with an account of `size` bytes. The data is backed by `u64`s so that it is aligned
+    /// like account data in the runtime.
+    fn with_account(size: usize, writable: bool, f: impl FnOnce(Info)) {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 1;
+        let mut data = vec![0u64; (size + 7) / 8];
+        let data = &mut bytemuck::cast_slice_mut(&mut data)[..size];
+        f(AccountInfo::new(
+            &key,
+            false,
+            writable,
+            &mut lamports,
+            data,
+            &owner,
+            false,
+            0,
+        ));
+    }
+
+    #[test]
+    fn init_and_load() {
+        with_account(CounterLoader::SIZE, true, |info| {
+            let loader = CounterLoader::new(info);
+            assert!(matches!(
+                loader.load(),
+                Err(SolitaireError::InvalidDiscriminator(_))
+            ));
+
+            loader.init().unwrap().count = 7;
+            assert_eq!(&loader.0.data.borrow()[..8], b"counter\0");
+            assert_eq!(loader.load().unwrap().count, 7);
+
+            loader.load_mut().unwrap().count += 1;
+            assert_eq!(loader.load().unwrap().count, 8);
+
+            assert!(matches!(
+                loader.init(),
+                Err(SolitaireError::AlreadyInitialized(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn other_types_are_rejected() {
+        with_account(CounterLoader::SIZE, true, |info| {
+            Loader::<Other, { AccountState::Initialized }>::new(info.clone())
+                .init()
+                .unwrap();
+            assert!(matches!(
+                CounterLoader::new(info).load(),
+                Err(SolitaireError::InvalidDiscriminator(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn borrow_conflicts_fail() {
+        with_account(CounterLoader::SIZE, true, |info| {
+            let loader = CounterLoader::new(info);
+            drop(loader.init().unwrap());
+
+            let counter = loader.load().unwrap();
+            assert!(matches!(
+                loader.load_mut(),
+                Err(SolitaireError::AccountBorrowFailed(_))
+            ));
+            // Shared borrows can coexist.
+            assert_eq!(loader.load().unwrap().count, counter.count);
+            drop(counter);
+
+            let _counter = loader.load_mut().unwrap();
+            assert!(matches!(
+                loader.load(),
+                Err(SolitaireError::AccountBorrowFailed(_))
+            ));
+            assert!(matches!(
+                loader.load_mut(),
+                Err(SolitaireError::AccountBorrowFailed(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn data_too_small_fails() {
+        with_account(CounterLoader::SIZE - 1, true, |info| {
+            let loader = CounterLoader::new(info);
+            assert!(matches!(
+                loader.init(),
+                Err(SolitaireError::AccountDataTooSmall(_, required, found))
+                    if required == CounterLoader::SIZE && found == CounterLoader::SIZE - 1
+            ));
+            assert!(matches!(
+                loader.load(),
+                Err(SolitaireError::AccountDataTooSmall(..))
+            ));
+        });
+    }
+
+    #[test]
+    fn read_only_accounts_are_not_mutable() {
+        with_account(CounterLoader::SIZE, false, |info| {
+            let loader = CounterLoader::new(info);
+            assert!(matches!(
+                loader.init(),
+                Err(SolitaireError::NonWriteableAccount(_))
+            ));
+            assert!(matches!(
+                loader.load_mut(),
+                Err(SolitaireError::NonWriteableAccount(_))
+            ));
+        });
     }
 }
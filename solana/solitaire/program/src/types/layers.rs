@@ -3,7 +3,8 @@
 //!
 //! These structs are always single field (or single + PhantomData) and so can be represented with
 //! the transparent repr layout. When each layer is removed the data can be transmuted safely to
-//! the layer below, allowing for optimized recursion.
+//! the layer below, allowing for optimized recursion. `Derive` is the exception, it also keeps the
+//! bump seed found while checking the derivation.
 
 use std::{
     io::{
@@ -35,8 +36,8 @@ pub struct Signer<Next>(pub Next);
 #[repr(transparent)]
 pub struct System<Next>(pub Next);
 
-#[repr(transparent)]
-pub struct Derive<Next, const Seed: &'static str>(pub Next);
+/// A layer derived from `Seed`, the second field is the canonical bump of the address.
+pub struct Derive<Next, const Seed: &'static str>(pub Next, pub u8);
 
 // Several traits are required for types defined here, they cannot be defined in another file due
 // to orphan instance limitations.
@@ -96,12 +97,12 @@ impl<T> DerefMut for System<T> {
 impl<T, const Seed: &'static str> Deref for Derive<T, Seed> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
-        unsafe { std::mem::transmute(&self.0) }
+        &self.0
     }
 }
 
 impl<T, const Seed: &'static str> DerefMut for Derive<T, Seed> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { std::mem::transmute(&mut self.0) }
+        &mut self.0
     }
 }
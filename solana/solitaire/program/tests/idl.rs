@@ -0,0 +1,151 @@
+//! Seeds of derived accounts in the description of an accounts struct.
+
+#![allow(incomplete_features)]
+#![feature(adt_const_params)]
+
+use borsh::{
+    BorshDeserialize,
+    BorshSchema,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    idl::{
+        Idl,
+        IdlAccount,
+        IdlInstruction,
+        IdlSeed,
+    },
+    processors::seeded::Seeded,
+    *,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct VaultData {
+    pub owner: Pubkey,
+}
+
+impl Owned for VaultData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+pub type Vault<'b, const State: AccountState> = Data<'b, VaultData, { State }>;
+
+pub struct VaultDerivationData {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub index: u16,
+}
+
+impl<'b, const State: AccountState> Seeded<&VaultDerivationData> for Vault<'b, { State }> {
+    fn seeds(data: &VaultDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            "vault".as_bytes().to_vec(),
+            data.mint.to_bytes().to_vec(),
+            data.owner.to_bytes().to_vec(),
+            data.index.to_be_bytes().to_vec(),
+        ]
+    }
+
+    fn describe_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::Const("vault"),
+            IdlSeed::Arg("mint"),
+            IdlSeed::Arg("owner"),
+            IdlSeed::Arg("index"),
+        ]
+    }
+}
+
+#[derive(FromAccounts)]
+pub struct Open<'b> {
+    pub mint: Info<'b>,
+    pub config: Vault<'b, { AccountState::Initialized }>,
+
+    /// The index is not read from another account, it stays an argument.
+    #[derivation(&VaultDerivationData {
+        mint: *mint.info().key,
+        owner: config.owner,
+        index: 0,
+    })]
+    pub vault: Mut<Vault<'b, { AccountState::MaybeInitialized }>>,
+
+    pub authority: Derive<Info<'b>, "authority">,
+
+    /// Described without seeds, the constraint is the only source of them.
+    pub unchecked: Vault<'b, { AccountState::MaybeInitialized }>,
+}
+
+#[derive(FromAccounts)]
+pub struct Nested<'b> {
+    pub open: Open<'b>,
+}
+
+fn seeds(accounts: &[IdlAccount], name: &str) -> Option<Vec<IdlSeed>> {
+    accounts
+        .iter()
+        .find(|account| account.name == name)
+        .unwrap()
+        .seeds
+        .clone()
+}
+
+#[test]
+fn derivation_data_fields_resolve_to_their_accounts() {
+    let accounts = <Open as Peel>::describe(IdlAccount::default());
+    assert_eq!(
+        seeds(&accounts, "vault"),
+        Some(vec![
+            IdlSeed::Const("vault"),
+            IdlSeed::Account("mint".to_string()),
+            IdlSeed::Field {
+                account: "config".to_string(),
+                field: "owner",
+            },
+            IdlSeed::Arg("index"),
+        ])
+    );
+    assert_eq!(
+        seeds(&accounts, "authority"),
+        Some(vec![IdlSeed::Const("authority")])
+    );
+    assert_eq!(seeds(&accounts, "mint"), None);
+    assert_eq!(seeds(&accounts, "unchecked"), None);
+}
+
+#[test]
+fn nested_seeds_name_sibling_accounts() {
+    let accounts = <Nested as Peel>::describe(IdlAccount::default());
+    assert_eq!(
+        seeds(&accounts, "open.vault").unwrap()[1..3],
+        [
+            IdlSeed::Account("open.mint".to_string()),
+            IdlSeed::Field {
+                account: "open.config".to_string(),
+                field: "owner",
+            },
+        ]
+    );
+}
+
+#[test]
+fn seeds_are_written_to_json() {
+    let idl = Idl {
+        name: "test",
+        version: "0.1.0",
+        instructions: vec![IdlInstruction {
+            name: "Open",
+            discriminator: vec![0],
+            accounts: <Open as Peel>::describe(IdlAccount::default()),
+            args: <()>::schema_container(),
+        }],
+    };
+    assert!(idl.to_json().contains(concat!(
+        r#""seeds":[{"kind":"const","value":"vault"},"#,
+        r#"{"kind":"account","name":"mint"},"#,
+        r#"{"kind":"field","account":"config","field":"owner"},"#,
+        r#"{"kind":"arg","name":"index"}]"#,
+    )));
+}
@@ -0,0 +1,127 @@
+//! A program dispatching on namespaced discriminators.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    hash::hashv,
+    pubkey::Pubkey,
+};
+use solitaire::*;
+
+#[derive(FromAccounts)]
+pub struct Accounts<'b> {
+    pub caller: Info<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RejectData {}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct AcceptData {
+    pub value: u64,
+}
+
+pub fn reject(_ctx: &ExecutionContext, _accs: &mut Accounts, _data: RejectData) -> Result<()> {
+    Err(SolitaireError::Custom(1))
+}
+
+pub fn accept(_ctx: &ExecutionContext, _accs: &mut Accounts, data: AcceptData) -> Result<()> {
+    match data.value {
+        7 => Ok(()),
+        _ => Err(SolitaireError::Custom(2)),
+    }
+}
+
+solitaire! {
+    namespace = "test";
+    Reject(RejectData) => reject,
+    Accept(AcceptData) => accept,
+}
+
+use instruction::{
+    dispatch,
+    Instruction,
+    DISCRIMINATOR_LEN,
+};
+
+fn call(data: &[u8]) -> Result<()> {
+    let program_id = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut account_data: [u8; 0] = [];
+    let accounts = [AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut account_data,
+        &program_id,
+        false,
+        0,
+    )];
+    dispatch(&program_id, &accounts, data)
+}
+
+#[test]
+fn discriminators_are_namespaced_hashes() {
+    for (instruction, name) in &[
+        (Instruction::Reject, "Reject"),
+        (Instruction::Accept, "Accept"),
+    ] {
+        let hash = hashv(&["test:".as_bytes(), name.as_bytes()]);
+        assert_eq!(
+            instruction.discriminator(),
+            hash.to_bytes()[..DISCRIMINATOR_LEN]
+        );
+    }
+}
+
+#[test]
+fn instructions_round_trip() {
+    for instruction in &[Instruction::Reject, Instruction::Accept] {
+        let data = instruction.try_to_vec().unwrap();
+        assert_eq!(data, instruction.discriminator());
+        assert_eq!(Instruction::try_from_slice(&data).unwrap(), *instruction);
+    }
+}
+
+#[test]
+fn rows_are_dispatched_by_discriminator() {
+    let mut data = (Instruction::Accept, AcceptData { value: 7 })
+        .try_to_vec()
+        .unwrap();
+    call(&data).unwrap();
+
+    data = (Instruction::Accept, AcceptData { value: 8 })
+        .try_to_vec()
+        .unwrap();
+    assert!(matches!(call(&data), Err(SolitaireError::Custom(2))));
+
+    data = (Instruction::Reject, RejectData {}).try_to_vec().unwrap();
+    assert!(matches!(call(&data), Err(SolitaireError::Custom(1))));
+}
+
+#[test]
+fn short_and_unknown_input_is_rejected() {
+    assert!(matches!(
+        call(&[]),
+        Err(SolitaireError::InstructionDataTooShort(0))
+    ));
+
+    let discriminator = Instruction::Accept.discriminator();
+    assert!(matches!(
+        call(&discriminator[..DISCRIMINATOR_LEN - 1]),
+        Err(SolitaireError::InstructionDataTooShort(7))
+    ));
+    assert!(Instruction::try_from_slice(&discriminator[..DISCRIMINATOR_LEN - 1]).is_err());
+
+    let unknown = [0u8; DISCRIMINATOR_LEN];
+    assert!(matches!(
+        call(&unknown),
+        Err(SolitaireError::UnknownInstruction(d)) if d == unknown
+    ));
+    assert!(Instruction::try_from_slice(&unknown).is_err());
+}
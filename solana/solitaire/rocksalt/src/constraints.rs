@@ -0,0 +1,366 @@
+//! Derive macro logic for account constraints.
+//!
+//! Fields of a `FromAccounts` struct can declare constraints which are checked once every field
+//! has been peeled, constraints can therefore refer to any other field of the struct by name.
+//!
+//! ```ignore
+//! #[derive(FromAccounts)]
+//! pub struct Transfer<'b> {
+//!     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+//!     pub authority: Signer<Info<'b>>,
+//!
+//!     /// Mint and owner of the token account must be the accounts passed as `mint` and
+//!     /// `authority`.
+//!     #[token(mint = mint, owner = authority, error = InvalidMint)]
+//!     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+//!
+//!     /// Derived from the mint, checked with `Seeded::verify_derivation`.
+//!     #[derivation(&CustodyAccountDerivationData { mint: *mint.info().key })]
+//!     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+//!
+//!     /// The `config` field of the data must be the key of the `config` account.
+//!     #[has_one(config)]
+//!     pub pool: Data<'b, PoolData, { AccountState::Initialized }>,
+//!
+//!     /// The account must be owned by the given program.
+//!     #[owner(spl_token_metadata::id())]
+//!     pub spl_metadata: Info<'b>,
+//! }
+//! ```
+//!
+//! Constraints fail with framework errors unless an `error = <expr>` argument names an error that
+//! converts into `SolitaireError`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    punctuated::Punctuated,
+    Attribute,
+    Expr,
+    Field,
+    GenericArgument,
+    Ident,
+    Member,
+    Path,
+    PathArguments,
+    Token,
+    Type,
+    UnOp,
+};
+
+/// Attributes recognised on fields, these are registered with the FromAccounts derive.
+pub const CONSTRAINT_ATTRIBUTES: [&str; 4] = ["derivation", "has_one", "owner", "token"];
+
+/// A single `key` or `key = value` argument.
+struct Arg {
+    key: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        let value = match input.peek(Token![=]) {
+            true => {
+                input.parse::<Token![=]>()?;
+                Some(input.parse()?)
+            }
+            false => None,
+        };
+        Ok(Arg { key, value })
+    }
+}
+
+fn parse_args(input: ParseStream) -> syn::Result<Vec<Arg>> {
+    Ok(Punctuated::<Arg, Token![,]>::parse_terminated(input)?
+        .into_iter()
+        .collect())
+}
+
+/// `#[derivation(<derivation data>, program = <program id>)]`, the program defaults to the
+/// executing program.
+struct Derivation {
+    data: Expr,
+    program: Option<Expr>,
+}
+
+impl Parse for Derivation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let data = input.parse()?;
+        let mut program = None;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            for arg in parse_args(input)? {
+                match (arg.key.to_string().as_str(), arg.value) {
+                    ("program", Some(value)) => program = Some(value),
+                    _ => return Err(syn::Error::new(arg.key.span(), "expected `program = <id>`")),
+                }
+            }
+        }
+        Ok(Derivation { data, program })
+    }
+}
+
+/// Split off the `error = <expr>` argument shared by key constraints.
+fn split_error(args: Vec<Arg>) -> syn::Result<(Vec<Arg>, Option<Expr>)> {
+    let mut error = None;
+    let mut rest = Vec::new();
+    for arg in args {
+        if arg.key == "error" {
+            match arg.value {
+                Some(value) => error = Some(value),
+                None => return Err(syn::Error::new(arg.key.span(), "expected `error = <error>`")),
+            }
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((rest, error))
+}
+
+/// Compare a key stored in the data of `name` with the key of the account `target`.
+fn key_check(name: &Ident, field: &Ident, target: &Expr, error: &Option<Expr>) -> TokenStream2 {
+    let error = match error {
+        Some(error) => quote!(#error.into()),
+        None => quote!(solitaire::SolitaireError::KeyMismatch(*#target.info().key, #name.#field)),
+    };
+    quote! {
+        if #name.#field != *#target.info().key {
+            return Err(#error);
+        }
+    }
+}
+
+fn generate_attribute(name: &Ident, attr: &Attribute) -> syn::Result<TokenStream2> {
+    let kind = attr.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+    match kind.as_str() {
+        "derivation" => {
+            let Derivation { data, program } = attr.parse_args()?;
+            let program = match program {
+                Some(program) => quote!(&#program),
+                None => quote!(pid),
+            };
+            Ok(quote! {
+                #name.verify_derivation(#program, #data)?;
+            })
+        }
+
+        "has_one" => {
+            let (args, error) = split_error(attr.parse_args_with(parse_args)?)?;
+            let checks = args.into_iter().map(|arg| {
+                // `has_one(config)` is short for `has_one(config = config)`.
+                let target = match arg.value {
+                    Some(value) => value,
+                    None => {
+                        let key = &arg.key;
+                        syn::parse_quote!(#key)
+                    }
+                };
+                key_check(name, &arg.key, &target, &error)
+            });
+            Ok(quote!(#(#checks)*))
+        }
+
+        "token" => {
+            let (args, error) = split_error(attr.parse_args_with(parse_args)?)?;
+            let mut checks = Vec::new();
+            for arg in args {
+                match (arg.key.to_string().as_str(), &arg.value) {
+                    ("mint", Some(target)) | ("owner", Some(target)) => {
+                        checks.push(key_check(name, &arg.key, target, &error))
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            arg.key.span(),
+                            "expected `mint = <field>` or `owner = <field>`",
+                        ))
+                    }
+                }
+            }
+            Ok(quote!(#(#checks)*))
+        }
+
+        "owner" => {
+            let (owner, error) = attr.parse_args_with(|input: ParseStream| {
+                let owner: Expr = input.parse()?;
+                let mut error = None;
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                    error = split_error(parse_args(input)?)?.1;
+                }
+                Ok((owner, error))
+            })?;
+            let error = match error {
+                Some(error) => quote!(#error.into()),
+                None => quote!(solitaire::SolitaireError::InvalidOwner(*#name.info().owner)),
+            };
+            Ok(quote! {
+                if *#name.info().owner != #owner {
+                    return Err(#error);
+                }
+            })
+        }
+
+        _ => Ok(quote!()),
+    }
+}
+
+/// Generate the checks for every constraint declared on the given fields.
+pub fn generate_constraints<'a>(fields: impl Iterator<Item = &'a Field>) -> TokenStream2 {
+    let checks = fields.flat_map(|field| {
+        let name = field.ident.as_ref().unwrap();
+        field
+            .attrs
+            .iter()
+            .filter(|attr| {
+                CONSTRAINT_ATTRIBUTES
+                    .iter()
+                    .any(|kind| attr.path.is_ident(kind))
+            })
+            .map(move |attr| generate_attribute(name, attr).unwrap_or_else(|e| e.to_compile_error()))
+    });
+
+    quote! {
+        #[allow(unused_imports)]
+        use solitaire::{
+            processors::seeded::Seeded as _,
+            Keyed as _,
+        };
+        #(#checks)*
+    }
+}
+
+/// Layers wrapping a derived account in a field type, `Seeded` is implemented by the account.
+const LAYERS: [&str; 4] = ["Mut", "MaybeMut", "Signer", "System"];
+
+/// Type of the account below the layers of a field.
+fn account_type(ty: &Type) -> &Type {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if LAYERS.iter().any(|layer| segment.ident == layer) {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let GenericArgument::Type(next) = arg {
+                            return account_type(next);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    ty
+}
+
+/// Type of the derivation data, named either by a struct literal or by the path of a
+/// constructor such as `PendingTransferDerivationData::from`.
+fn data_type(data: &Expr) -> Option<Path> {
+    match strip_reference(data) {
+        Expr::Struct(s) => Some(s.path.clone()),
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(func) if func.path.segments.len() > 1 => {
+                let mut path = func.path.clone();
+                let len = path.segments.len();
+                path.segments = path.segments.into_iter().take(len - 1).collect();
+                Some(path)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Seed a derivation data field is initialised with, if it is read from another field of the
+/// struct: `*mint.info().key` is the key of `mint`, `vaa.meta().emitter_chain` a field of its data.
+fn seed_source(value: &Expr, fields: &[&Ident]) -> Option<TokenStream2> {
+    let mut members = Vec::new();
+    let mut info = false;
+    let mut expr = value;
+    let root = loop {
+        expr = match expr {
+            Expr::Unary(u) if matches!(u.op, UnOp::Deref(_)) => &u.expr,
+            Expr::Reference(r) => &r.expr,
+            Expr::Paren(p) => &p.expr,
+            Expr::Try(t) => &t.expr,
+            Expr::Field(f) => {
+                match &f.member {
+                    Member::Named(name) => members.push(name.to_string()),
+                    Member::Unnamed(_) => return None,
+                }
+                &f.base
+            }
+            Expr::MethodCall(m) => {
+                info |= m.method == "info";
+                &m.receiver
+            }
+            Expr::Path(p) => break p.path.get_ident()?,
+            _ => return None,
+        }
+    };
+    if !fields.contains(&root) {
+        return None;
+    }
+
+    members.reverse();
+    match (info, members.as_slice()) {
+        (true, [key]) if key == "key" => Some(quote! {
+            solitaire::idl::IdlSeed::Account(account.field(stringify!(#root)).name)
+        }),
+        (false, [_, ..]) => {
+            let field = members.join(".");
+            Some(quote! {
+                solitaire::idl::IdlSeed::Field {
+                    account: account.field(stringify!(#root)).name,
+                    field: #field,
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
+/// An expression evaluating to the seeds of a field with a `derivation` constraint, as described
+/// by the `Seeded` implementation of its account with the derivation data fields resolved to the
+/// accounts they are read from. Derivations by another program are not described, the IDL has no
+/// way to name the program.
+pub fn describe_seeds(field: &Field, fields: &[&Ident]) -> Option<TokenStream2> {
+    let attr = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("derivation"))?;
+    let Derivation { data, program } = attr.parse_args().ok()?;
+    if program.is_some() {
+        return None;
+    }
+
+    let account = account_type(&field.ty);
+    let data_type = data_type(&data)?;
+    let mut sources = Vec::new();
+    if let Expr::Struct(s) = strip_reference(&data) {
+        for value in &s.fields {
+            if let (Member::Named(name), Some(source)) =
+                (&value.member, seed_source(&value.expr, fields))
+            {
+                sources.push(quote!((stringify!(#name), #source)));
+            }
+        }
+    }
+
+    Some(quote! {
+        <#account as solitaire::processors::seeded::Seeded<&#data_type>>::describe_seeds()
+            .into_iter()
+            .map(|seed| seed.resolve(&[#(#sources),*]))
+            .collect::<Vec<_>>()
+    })
+}
+
+fn strip_reference(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Reference(r) => strip_reference(&r.expr),
+        Expr::Paren(p) => strip_reference(&p.expr),
+        expr => expr,
+    }
+}
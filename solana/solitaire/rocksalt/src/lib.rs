@@ -1,7 +1,9 @@
 #![allow(warnings)]
 
+mod constraints;
 mod to_instruction;
 
+use constraints::*;
 use to_instruction::*;
 
 use solana_program::{
@@ -71,9 +73,39 @@ pub fn derive_to_instruction(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Discriminator of an instruction of a namespaced `solitaire!` program, the first eight bytes of
+/// sha256("<namespace>:<row>"). It is hashed here so that programs compare instruction data
+/// against a byte array literal instead of hashing every row on each call.
+///
+/// ```ignore
+/// const INITIALIZE: [u8; 8] = namespaced_discriminator!("bridge", Initialize);
+/// ```
+#[proc_macro]
+pub fn namespaced_discriminator(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let namespace: syn::LitStr = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let row: syn::Ident = input.parse()?;
+        Ok((namespace, row))
+    };
+    let (namespace, row) = match syn::parse::Parser::parse(parser, input) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let hash = solana_program::hash::hashv(&[
+        namespace.value().as_bytes(),
+        b":",
+        row.to_string().as_bytes(),
+    ]);
+    let bytes = &hash.to_bytes()[..8];
+    TokenStream::from(quote! { [#(#bytes),*] })
+}
+
 /// Generate a FromAccounts implementation for a product of accounts. Each field is constructed by
-/// a call to the Verify::verify instance of its type.
-#[proc_macro_derive(FromAccounts)]
+/// a call to the Verify::verify instance of its type. Fields can declare constraints that are
+/// checked once all fields are peeled, see the `constraints` module.
+#[proc_macro_derive(FromAccounts, attributes(derivation, has_one, owner, token))]
 pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
@@ -109,6 +141,7 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
     let from_method = generate_fields(&name, &input.data);
     let persist_method = generate_persist(&name, &input.data);
     let deps_method = generate_deps_fields(&name, &input.data);
+    let describe_method = generate_describe_fields(&name, &input.data);
     let expanded = quote! {
         /// Macro generated implementation of FromAccounts by Solitaire.
         impl #combined_impl_g solitaire::FromAccounts #peel_type_g for #name #type_g {
@@ -127,6 +160,10 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
                 #deps_method
             }
 
+            fn describe(account: solitaire::idl::IdlAccount) -> Vec<solitaire::idl::IdlAccount> {
+                #describe_method
+            }
+
             fn persist(&self, program_id: &solana_program::pubkey::Pubkey) -> solitaire::Result<()> {
                 solitaire::Persist::persist(self, program_id)
             }
@@ -176,12 +213,16 @@ fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
                         quote!(#name)
                     });
 
+                    // Constraints see every field, so they are checked once all are peeled.
+                    let constraints = generate_constraints(fields.named.iter());
+
                     // Write out our iterator and return the filled structure.
                     quote! {
                         use solana_program::account_info::next_account_info;
                         use solitaire::trace;
                         trace!("Peeling:");
                         #(#recurse;)*
+                        #constraints
                         Ok(#name { #(#names,)* })
                     }
                 }
@@ -239,6 +280,66 @@ fn generate_deps_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
     }
 }
 
+/// Generate the IDL description of each field, flattened into a single account list.
+fn generate_describe_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
+    match *data {
+        // We only care about structures.
+        Data::Struct(ref data) => {
+            // We want to inspect its fields.
+            match data.fields {
+                // For now, we only care about struct { a: T } forms, not struct(T);
+                Fields::Named(ref fields) => {
+                    let names: Vec<&syn::Ident> = fields
+                        .named
+                        .iter()
+                        .filter_map(|f| f.ident.as_ref())
+                        .collect();
+
+                    // For each field, describe it under its own name within the parent account.
+                    // Fields with a derivation constraint are described with their seeds.
+                    let recurse = fields.named.iter().map(|f| {
+                        let name = &f.ident;
+                        let ty = &f.ty;
+                        let seeds = match describe_seeds(f, &names) {
+                            Some(seeds) => quote! {
+                                let seeds = #seeds;
+                                if !seeds.is_empty() {
+                                    for described in &mut described {
+                                        described.seeds = Some(seeds.clone());
+                                    }
+                                }
+                            },
+                            None => quote!(),
+                        };
+                        quote! {
+                            let mut described = <#ty as Peel>::describe(account.field(stringify!(#name)));
+                            #seeds
+                            accounts.append(&mut described);
+                        }
+                    });
+
+                    // Write out our iterator and return the filled structure.
+                    quote! {
+                        let mut accounts = Vec::new();
+                        #(#recurse;)*
+                        accounts
+                    }
+                }
+
+                Fields::Unnamed(_) => {
+                    unimplemented!()
+                }
+
+                Fields::Unit => {
+                    unimplemented!()
+                }
+            }
+        }
+
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    }
+}
+
 /// This function does the heavy lifting of generating the field parsers.
 fn generate_persist(name: &syn::Ident, data: &Data) -> TokenStream2 {
     match *data {
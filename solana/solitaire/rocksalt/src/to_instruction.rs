@@ -71,9 +71,14 @@ pub fn generate_to_instruction(
 
             #(#expanded_appends;)*
 
-            // Add dependencies
-            deps.dedup();
-            let mut dep_ams = deps.iter().map(|v| solana_program::instruction::AccountMeta::new_readonly(*v, false)).collect();
+            // Add dependencies, each only once even when several fields depend on it
+            let mut unique_deps: Vec<Pubkey> = Vec::new();
+            for dep in deps {
+                if !unique_deps.contains(&dep) {
+                    unique_deps.push(dep);
+                }
+            }
+            let mut dep_ams = unique_deps.iter().map(|v| solana_program::instruction::AccountMeta::new_readonly(*v, false)).collect();
             account_metas.append(&mut dep_ams);
 
             Ok((solana_program::instruction::Instruction::new_with_bytes(program_id,
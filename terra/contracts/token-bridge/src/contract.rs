@@ -54,6 +54,7 @@ use crate::{
         TokenBridgeMessage,
         TransferInfo,
         TransferState,
+        UpdateChain,
         UpgradeContract,
     },
 };
@@ -562,6 +563,7 @@ fn handle_governance_payload(deps: DepsMut, env: Env, data: &Vec<u8>) -> StdResu
     match gov_packet.action {
         1u8 => handle_register_chain(deps, env, &gov_packet.payload),
         2u8 => handle_upgrade_contract(deps, env, &gov_packet.payload),
+        6u8 => handle_update_chain(deps, env, &gov_packet.payload),
         _ => ContractError::InvalidVAAAction.std_err(),
     }
 }
@@ -599,6 +601,33 @@ fn handle_register_chain(deps: DepsMut, _env: Env, data: &Vec<u8>) -> StdResult<
         .add_attribute("chain_address", hex::encode(chain_address)))
 }
 
+/// Replace the endpoint registered for a chain, or deregister it when no replacement is given.
+/// Transfers and attestations of a deregistered chain are rejected until it is registered again.
+fn handle_update_chain(deps: DepsMut, _env: Env, data: &Vec<u8>) -> StdResult<Response> {
+    let update = UpdateChain::deserialize(&data)?;
+
+    let existing = bridge_contracts_read(deps.storage).load(&update.chain_id.to_be_bytes());
+    if existing.ok() != Some(update.chain_address.clone()) {
+        return Err(StdError::generic_err(
+            "bridge contract is not registered for this chain",
+        ));
+    }
+
+    let mut bucket = bridge_contracts(deps.storage);
+    let response = Response::new().add_attribute("chain_id", update.chain_id.to_string());
+
+    // Deregistrations report the removed endpoint rather than the all-zero replacement.
+    if update.deregisters() {
+        bucket.remove(&update.chain_id.to_be_bytes());
+        Ok(response
+            .add_attribute("chain_address", hex::encode(update.chain_address))
+            .add_attribute("deregistered", "true"))
+    } else {
+        bucket.save(&update.chain_id.to_be_bytes(), &update.new_chain_address)?;
+        Ok(response.add_attribute("chain_address", hex::encode(update.new_chain_address)))
+    }
+}
+
 fn handle_complete_transfer(
     deps: DepsMut,
     env: Env,
@@ -1172,12 +1201,129 @@ fn build_native_id(denom: &str) -> Vec<u8> {
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{
-        to_binary,
-        Binary,
-        StdResult,
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies,
+        mock_env,
+        mock_info,
     };
 
+    const CHAIN: u16 = 2;
+    const ENDPOINT: [u8; 32] = [1u8; 32];
+    const REPLACEMENT: [u8; 32] = [2u8; 32];
+
+    fn update_chain(chain_address: [u8; 32], new_chain_address: [u8; 32]) -> Vec<u8> {
+        [
+            CHAIN.to_be_bytes().to_vec(),
+            chain_address.to_vec(),
+            new_chain_address.to_vec(),
+        ]
+        .concat()
+    }
+
+    fn attribute<'a>(response: &'a Response, key: &str) -> Option<&'a str> {
+        response
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    #[test]
+    fn update_chain_replaces_endpoint() {
+        let mut deps = mock_dependencies(&[]);
+        bridge_contracts(&mut deps.storage)
+            .save(&CHAIN.to_be_bytes(), &ENDPOINT.to_vec())
+            .unwrap();
+
+        let response = handle_update_chain(
+            deps.as_mut(),
+            mock_env(),
+            &update_chain(ENDPOINT, REPLACEMENT),
+        )
+        .unwrap();
+        assert_eq!(
+            bridge_contracts_read(&deps.storage)
+                .load(&CHAIN.to_be_bytes())
+                .unwrap(),
+            REPLACEMENT.to_vec()
+        );
+        assert_eq!(
+            attribute(&response, "chain_address"),
+            Some(hex::encode(REPLACEMENT).as_str())
+        );
+        assert_eq!(attribute(&response, "deregistered"), None);
+    }
+
+    #[test]
+    fn update_chain_deregisters_endpoint() {
+        let mut deps = mock_dependencies(&[]);
+        bridge_contracts(&mut deps.storage)
+            .save(&CHAIN.to_be_bytes(), &ENDPOINT.to_vec())
+            .unwrap();
+
+        let response = handle_update_chain(
+            deps.as_mut(),
+            mock_env(),
+            &update_chain(ENDPOINT, [0u8; 32]),
+        )
+        .unwrap();
+        assert!(bridge_contracts_read(&deps.storage)
+            .may_load(&CHAIN.to_be_bytes())
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            attribute(&response, "chain_address"),
+            Some(hex::encode(ENDPOINT).as_str())
+        );
+        assert_eq!(attribute(&response, "deregistered"), Some("true"));
+
+        // Transfers from the deregistered endpoint are no longer accepted.
+        let transfer = TransferInfo {
+            amount: (0, 1),
+            token_address: [3u8; 32].to_vec(),
+            token_chain: CHAIN,
+            recipient: [4u8; 32].to_vec(),
+            recipient_chain: CHAIN_ID,
+            fee: (0, 0),
+        };
+        let err = handle_complete_transfer(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            CHAIN,
+            ENDPOINT.to_vec(),
+            &transfer.serialize(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, StdError::NotFound { .. }));
+    }
+
+    #[test]
+    fn update_chain_requires_registered_endpoint() {
+        let mut deps = mock_dependencies(&[]);
+        bridge_contracts(&mut deps.storage)
+            .save(&CHAIN.to_be_bytes(), &ENDPOINT.to_vec())
+            .unwrap();
+
+        let err = handle_update_chain(
+            deps.as_mut(),
+            mock_env(),
+            &update_chain(REPLACEMENT, [0u8; 32]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("bridge contract is not registered for this chain")
+        );
+        assert_eq!(
+            bridge_contracts_read(&deps.storage)
+                .load(&CHAIN.to_be_bytes())
+                .unwrap(),
+            ENDPOINT.to_vec()
+        );
+    }
+
     #[test]
     fn test_me() -> StdResult<()> {
         let x = vec![
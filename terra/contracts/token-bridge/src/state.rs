@@ -278,6 +278,16 @@ pub struct RegisterChain {
     pub chain_address: Vec<u8>,
 }
 
+// 0  uint16     ChainID
+// 2  [32]uint8  ChainAddress, the registered endpoint
+// 34 [32]uint8  NewChainAddress, zero to deregister the endpoint
+
+pub struct UpdateChain {
+    pub chain_id: u16,
+    pub chain_address: Vec<u8>,
+    pub new_chain_address: Vec<u8>,
+}
+
 impl UpgradeContract {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
         let data = data.as_slice();
@@ -298,3 +308,26 @@ impl RegisterChain {
         })
     }
 }
+
+impl UpdateChain {
+    pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
+        let data = data.as_slice();
+        if data.len() != 66 {
+            return Err(StdError::generic_err("invalid update chain payload"));
+        }
+        let chain_id = data.get_u16(0);
+        let chain_address = data.get_bytes32(2).to_vec();
+        let new_chain_address = data.get_bytes32(34).to_vec();
+
+        Ok(UpdateChain {
+            chain_id,
+            chain_address,
+            new_chain_address,
+        })
+    }
+
+    /// Whether the endpoint is deregistered without a replacement.
+    pub fn deregisters(&self) -> bool {
+        self.new_chain_address.iter().all(|b| *b == 0)
+    }
+}
@@ -49,6 +49,7 @@ use crate::{
         GuardianSetUpgrade,
         ParsedVAA,
         SetFee,
+        SetGuardianSetExpiration,
         TransferFee,
     },
 };
@@ -168,6 +169,7 @@ fn handle_governance_payload(deps: DepsMut, env: Env, data: &Vec<u8>) -> StdResu
         2u8 => vaa_update_guardian_set(deps, env, &gov_packet.payload),
         3u8 => handle_set_fee(deps, env, &gov_packet.payload),
         4u8 => handle_transfer_fee(deps, env, &gov_packet.payload),
+        6u8 => vaa_update_guardian_set_expiration(deps, env, &gov_packet.payload),
         _ => ContractError::InvalidVAAAction.std_err(),
     }
 }
@@ -279,6 +281,39 @@ fn vaa_update_guardian_set(deps: DepsMut, env: Env, data: &Vec<u8>) -> StdResult
         .add_attribute("new", state.guardian_set_index.to_string()))
 }
 
+fn vaa_update_guardian_set_expiration(
+    deps: DepsMut,
+    _env: Env,
+    data: &Vec<u8>,
+) -> StdResult<Response> {
+    /* Payload format
+    0   uint32 guardian_set_index
+    4   uint32 expiration_time
+    */
+
+    let state = config_read(deps.storage).load()?;
+
+    let SetGuardianSetExpiration {
+        guardian_set_index,
+        expiration_time,
+    } = SetGuardianSetExpiration::deserialize(&data)?;
+
+    // The current set must never expire, and an expiration of 0 would revive a previous set.
+    if guardian_set_index >= state.guardian_set_index || expiration_time == 0 {
+        return ContractError::InvalidGuardianSetExpiration.std_err();
+    }
+
+    let mut guardian_set = guardian_set_get(deps.storage, guardian_set_index)
+        .or_else(|_| ContractError::InvalidGuardianSetIndex.std_err())?;
+    guardian_set.expiration_time = expiration_time as u64;
+    guardian_set_set(deps.storage, guardian_set_index, &guardian_set)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "guardian_set_expiration")
+        .add_attribute("index", guardian_set_index.to_string())
+        .add_attribute("expiration_time", expiration_time.to_string()))
+}
+
 fn vaa_update_contract(_deps: DepsMut, env: Env, data: &Vec<u8>) -> StdResult<Response> {
     /* Payload format
     0   [][32]uint8 new_contract
@@ -415,3 +450,127 @@ fn keys_equal(a: &VerifyingKey, b: &GuardianAddress) -> bool {
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{
+        testing::{
+            mock_dependencies,
+            mock_env,
+            MockApi,
+            MockQuerier,
+            MockStorage,
+        },
+        OwnedDeps,
+    };
+
+    const EXPIRATION: u32 = 1_000;
+
+    // Two guardian sets of a single guardian, the second one is current.
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies(&[]);
+        config(&mut deps.storage)
+            .save(&ConfigInfo {
+                guardian_set_index: 1,
+                guardian_set_expirity: 86400,
+                gov_chain: 1,
+                gov_address: vec![4u8; 32],
+                fee: Coin::new(0, FEE_DENOMINATION),
+            })
+            .unwrap();
+        for index in 0..2 {
+            guardian_set_set(
+                &mut deps.storage,
+                index,
+                &GuardianSetInfo {
+                    addresses: vec![GuardianAddress {
+                        bytes: vec![index as u8; 20].into(),
+                    }],
+                    expiration_time: 0,
+                },
+            )
+            .unwrap();
+        }
+        deps
+    }
+
+    fn expiration_payload(guardian_set_index: u32, expiration_time: u32) -> Vec<u8> {
+        [
+            guardian_set_index.to_be_bytes().to_vec(),
+            expiration_time.to_be_bytes().to_vec(),
+        ]
+        .concat()
+    }
+
+    fn assert_contract_error<T: std::fmt::Debug>(result: StdResult<T>, error: ContractError) {
+        assert_eq!(result.unwrap_err().to_string(), error.std().to_string());
+    }
+
+    #[test]
+    fn guardian_set_expiration_rejects_current_and_future_sets() {
+        let mut deps = setup();
+        for index in 1..3 {
+            assert_contract_error(
+                vaa_update_guardian_set_expiration(
+                    deps.as_mut(),
+                    mock_env(),
+                    &expiration_payload(index, EXPIRATION),
+                ),
+                ContractError::InvalidGuardianSetExpiration,
+            );
+        }
+        assert_eq!(
+            guardian_set_get(&deps.storage, 1).unwrap().expiration_time,
+            0
+        );
+    }
+
+    #[test]
+    fn guardian_set_expiration_rejects_zero() {
+        let mut deps = setup();
+        assert_contract_error(
+            vaa_update_guardian_set_expiration(
+                deps.as_mut(),
+                mock_env(),
+                &expiration_payload(0, 0),
+            ),
+            ContractError::InvalidGuardianSetExpiration,
+        );
+    }
+
+    #[test]
+    fn expired_guardian_set_fails_verification() {
+        let mut deps = setup();
+        vaa_update_guardian_set_expiration(
+            deps.as_mut(),
+            mock_env(),
+            &expiration_payload(0, EXPIRATION),
+        )
+        .unwrap();
+        assert_eq!(
+            guardian_set_get(&deps.storage, 0).unwrap().expiration_time,
+            EXPIRATION as u64
+        );
+
+        // A VAA without signatures of the first set, its body is never checked.
+        let vaa = [
+            vec![1u8],
+            0u32.to_be_bytes().to_vec(),
+            vec![0u8],
+            vec![0u8; ParsedVAA::VAA_PAYLOAD_POS],
+            vec![1u8],
+        ]
+        .concat();
+
+        // Until it expires the set is used and the VAA falls short of its quorum.
+        assert_contract_error(
+            parse_and_verify_vaa(&deps.storage, &vaa, EXPIRATION as u64),
+            ContractError::NoQuorum,
+        );
+        assert_contract_error(
+            parse_and_verify_vaa(&deps.storage, &vaa, EXPIRATION as u64 + 1),
+            ContractError::GuardianSetExpired,
+        );
+    }
+}
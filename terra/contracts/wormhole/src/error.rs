@@ -98,6 +98,10 @@ pub enum ContractError {
     /// Registering asset outside of the wormhole
     #[error("RegistrationForbidden")]
     RegistrationForbidden,
+
+    /// Only previous guardian sets can be given a non-zero expiration time
+    #[error("InvalidGuardianSetExpiration")]
+    InvalidGuardianSetExpiration,
 }
 
 impl ContractError {
@@ -392,6 +392,28 @@ impl TransferFee {
     }
 }
 
+// action 6
+pub struct SetGuardianSetExpiration {
+    pub guardian_set_index: u32,
+    pub expiration_time: u32,
+}
+
+impl SetGuardianSetExpiration {
+    pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
+        let data = data.as_slice();
+        if data.len() != 8 {
+            return ContractError::InvalidVAA.std_err();
+        }
+
+        let guardian_set_index = data.get_u32(0);
+        let expiration_time = data.get_u32(4);
+        Ok(SetGuardianSetExpiration {
+            guardian_set_index,
+            expiration_time,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -429,6 +451,17 @@ mod tests {
         assert_eq!(build_guardian_set(100).quorum(), 67);
     }
 
+    #[test]
+    fn test_deserialize_guardian_set_expiration() {
+        let x = hex::decode("0000000261a4c3f0").unwrap();
+        let v = SetGuardianSetExpiration::deserialize(&x).unwrap();
+        assert_eq!(v.guardian_set_index, 2);
+        assert_eq!(v.expiration_time, 1638188016);
+
+        assert!(SetGuardianSetExpiration::deserialize(&x[..7].to_vec()).is_err());
+        assert!(SetGuardianSetExpiration::deserialize(&[x.clone(), vec![0]].concat()).is_err());
+    }
+
     #[test]
     fn test_deserialize() {
         let x = hex::decode("080000000901007bfa71192f886ab6819fa4862e34b4d178962958d9b2e3d9437338c9e5fde1443b809d2886eaa69e0f0158ea517675d96243c9209c3fe1d94d5b19866654c6980000000b150000000500020001020304000000000000000000000000000000000000000000000000000000000000000000000a0261626364").unwrap();